@@ -0,0 +1,289 @@
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::io::Write;
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::time::Duration;
+
+use crate::geometry::Point2;
+use crate::grid::Grid;
+use crate::parse::ParseError;
+use crate::render::{Color, Frame};
+use crate::solution::{Answer, Part};
+use crate::{day11, day13, day6};
+
+/// Gets to see the state of a simulation before the first and after every step.
+pub trait Observer<S: ?Sized> {
+    fn observe(&mut self, step: usize, state: &S);
+}
+
+impl<S: ?Sized> Observer<S> for () {
+    fn observe(&mut self, _: usize, _: &S) {}
+}
+
+impl<S: ?Sized, F: FnMut(usize, &S)> Observer<S> for F {
+    fn observe(&mut self, step: usize, state: &S) {
+        self(step, state)
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Control {
+    TogglePause,
+    Step,
+    Quit,
+}
+
+impl Control {
+    /// `p` pauses or resumes, an empty line or `n` advances one step while paused, `q` stops drawing.
+    pub fn parse(line: &str) -> Option<Control> {
+        match line.trim() {
+            "p" => Some(Control::TogglePause),
+            "" | "n" => Some(Control::Step),
+            "q" => Some(Control::Quit),
+            _ => None,
+        }
+    }
+}
+
+/// Draws pictures one after another, waiting `delay` in between or for a [`Control`] while paused.
+pub struct Player<W: Write> {
+    out: W,
+    delay: Duration,
+    paused: bool,
+    quit: bool,
+    controls: Receiver<Control>,
+}
+
+impl<W: Write> Player<W> {
+    pub fn new(out: W, delay: Duration, controls: Receiver<Control>) -> Player<W> {
+        Player {
+            out,
+            delay,
+            paused: false,
+            quit: false,
+            controls,
+        }
+    }
+
+    pub fn with_paused(mut self, paused: bool) -> Player<W> {
+        self.paused = paused;
+        self
+    }
+
+    pub fn show(&mut self, step: usize, picture: &str) {
+        if self.quit {
+            return;
+        }
+
+        let _ = write!(self.out, "\x1b[H\x1b[2J{}\nstep {}\n", picture, step);
+        let _ = self.out.flush();
+        self.wait();
+    }
+
+    fn wait(&mut self) {
+        loop {
+            let control = match self.paused {
+                true => self
+                    .controls
+                    .recv()
+                    .map_err(|_| RecvTimeoutError::Disconnected),
+                false => self.controls.recv_timeout(self.delay),
+            };
+
+            match control {
+                Ok(Control::TogglePause) if self.paused => {
+                    self.paused = false;
+                    return;
+                }
+                Ok(Control::TogglePause) => self.paused = true,
+                Ok(Control::Step) if self.paused => return,
+                Ok(Control::Step) => {}
+                Ok(Control::Quit) => {
+                    self.quit = true;
+                    return;
+                }
+                Err(RecvTimeoutError::Timeout) => return,
+                Err(RecvTimeoutError::Disconnected) if self.paused => self.paused = false,
+                Err(RecvTimeoutError::Disconnected) => {
+                    std::thread::sleep(self.delay);
+                    return;
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum AnimateError {
+    Unsupported(u8),
+    Parse(ParseError),
+}
+
+impl Display for AnimateError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AnimateError::Unsupported(day) => write!(f, "day {} cannot be animated", day),
+            AnimateError::Parse(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl Error for AnimateError {}
+
+impl From<ParseError> for AnimateError {
+    fn from(error: ParseError) -> Self {
+        AnimateError::Parse(error)
+    }
+}
+
+/// Solves a part while the player draws every step.
+pub fn animate<W: Write>(
+    day: u8,
+    part: Part,
+    input: &str,
+    player: &mut Player<W>,
+) -> Result<Answer, AnimateError> {
+    match (day, part) {
+        (6, part) => {
+            let days = match part {
+                Part::One => 80,
+                Part::Two => 256,
+            };
+            let mut observer =
+                |step, state: &HashMap<u8, usize>| player.show(step, &day6::draw_fish(state));
+            Ok(day6::grow_observed(day6::parse(input)?, days, &mut observer).into())
+        }
+        (11, part) => {
+            let mut observer =
+                |step, state: &Grid<usize>| player.show(step, &day11::draw_energy(state));
+            Ok(match part {
+                Part::One => day11::count_flashes_observed(day11::parse(input)?, &mut observer),
+                Part::Two => {
+                    day11::find_synchronization_step_observed(day11::parse(input)?, &mut observer)
+                }
+            }
+            .into())
+        }
+        (13, Part::One) => {
+            let mut observer =
+                |step, state: &HashSet<Point2>| player.show(step, &day13::draw_paper(state));
+            Ok(day13::fold_once_observed(day13::parse(input)?, &mut observer).into())
+        }
+        (13, Part::Two) => {
+            let mut observer =
+                |step, state: &HashSet<Point2>| player.show(step, &day13::draw_paper(state));
+            Ok(day13::fold_all_observed(day13::parse(input)?, &mut observer).into())
+        }
+        (day, _) => Err(AnimateError::Unsupported(day)),
+    }
+}
+
+/// Two rows of a frame per line of text, using the upper half block with foreground and background colors.
+pub fn draw_frame(frame: &Frame) -> String {
+    let rows = frame.rows().collect::<Vec<&[Color]>>();
+
+    rows.chunks(2)
+        .map(|pair| {
+            let cells = (0..pair[0].len())
+                .map(|x| {
+                    let top = pair[0][x];
+                    let bottom = pair.get(1).map(|row| row[x]).unwrap_or(Color::BLACK);
+                    format!(
+                        "\x1b[38;2;{};{};{}m\x1b[48;2;{};{};{}m\u{2580}",
+                        top.r, top.g, top.b, bottom.r, bottom.g, bottom.b
+                    )
+                })
+                .collect::<String>();
+            format!("{}\x1b[0m", cells)
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Colors the text with a 24 bit foreground color.
+pub fn paint(text: &str, color: Color) -> String {
+    format!(
+        "\x1b[38;2;{};{};{}m{}\x1b[0m",
+        color.r, color.g, color.b, text
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc::channel;
+
+    use rstest::*;
+
+    use crate::animate::*;
+
+    #[rstest]
+    #[case("p", Some(Control::TogglePause))]
+    #[case("", Some(Control::Step))]
+    #[case("n\n", Some(Control::Step))]
+    #[case("q", Some(Control::Quit))]
+    #[case("x", None)]
+    fn test_parse_control(#[case] line: &str, #[case] expected: Option<Control>) {
+        assert_eq!(expected, Control::parse(line));
+    }
+
+    #[test]
+    fn test_paused_player_waits_for_steps() {
+        let (sender, receiver) = channel();
+        let mut out = vec![];
+        let mut player = Player::new(&mut out, Duration::from_secs(60), receiver).with_paused(true);
+        sender.send(Control::Step).unwrap();
+        sender.send(Control::Quit).unwrap();
+
+        player.show(0, "first");
+        player.show(1, "second");
+        player.show(2, "third");
+
+        let output = String::from_utf8(out).unwrap();
+        assert!(output.contains("first\nstep 0"));
+        assert!(output.contains("second\nstep 1"));
+        assert!(!output.contains("third"));
+    }
+
+    #[test]
+    fn test_animate_day_11() {
+        let (_sender, receiver) = channel();
+        let mut out = vec![];
+        let mut player = Player::new(&mut out, Duration::ZERO, receiver);
+
+        let answer = animate(
+            11,
+            Part::Two,
+            "11111\n19991\n19191\n19991\n11111",
+            &mut player,
+        );
+
+        assert_eq!(Answer::Number(6), answer.unwrap());
+        let output = String::from_utf8(out).unwrap();
+        assert_eq!(7, output.matches("\x1b[2J").count());
+    }
+
+    #[test]
+    fn test_unsupported_day() {
+        let (_sender, receiver) = channel();
+        let mut player = Player::new(vec![], Duration::ZERO, receiver);
+
+        let result = animate(1, Part::One, "199", &mut player);
+
+        assert!(matches!(result, Err(AnimateError::Unsupported(1))));
+    }
+
+    #[test]
+    fn test_draw_frame() {
+        let frame = Grid::from_fn(1, 3, |point| match point.y {
+            1 => Color::WHITE,
+            _ => Color::BLACK,
+        });
+
+        assert_eq!(
+            "\x1b[38;2;0;0;0m\x1b[48;2;255;255;255m\u{2580}\x1b[0m\n\
+             \x1b[38;2;0;0;0m\x1b[48;2;0;0;0m\u{2580}\x1b[0m",
+            draw_frame(&frame)
+        );
+    }
+}
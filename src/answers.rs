@@ -0,0 +1,276 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::fs::read_to_string;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::input::read_input;
+use crate::runner::solve_timed;
+use crate::solution::{Answer, Part};
+
+#[derive(Debug)]
+pub enum AnswersError {
+    Io { path: PathBuf, source: io::Error },
+    Format(String),
+}
+
+impl Display for AnswersError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AnswersError::Io { path, source } => {
+                write!(f, "could not read {}: {}", path.display(), source)
+            }
+            AnswersError::Format(message) => write!(f, "invalid answers file: {}", message),
+        }
+    }
+}
+
+impl Error for AnswersError {}
+
+/// The known answer of one part of one day for a named input.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Expectation {
+    pub input: String,
+    pub day: u8,
+    pub part: Part,
+    pub answer: Answer,
+}
+
+impl Expectation {
+    pub fn input_path(&self) -> PathBuf {
+        input_path(&self.input, self.day)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Outcome {
+    Correct,
+    Wrong(Answer),
+    Failed(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct Check {
+    pub expectation: Expectation,
+    pub outcome: Outcome,
+    pub duration: Duration,
+}
+
+/// The checked-in `answers.toml` in the crate root.
+pub fn default_answers_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("answers.toml")
+}
+
+/// Input names are file stems next to the solution of a day, e.g. `test_input` for `src/dayN/test_input.txt`.
+pub fn input_path(input: &str, day: u8) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join(format!("src/day{}/{}.txt", day, input))
+}
+
+pub fn load(path: impl AsRef<Path>) -> Result<Vec<Expectation>, AnswersError> {
+    let content = read_to_string(path.as_ref()).map_err(|source| AnswersError::Io {
+        path: path.as_ref().to_path_buf(),
+        source,
+    })?;
+    parse(&content)
+}
+
+pub fn parse(content: &str) -> Result<Vec<Expectation>, AnswersError> {
+    let inputs =
+        toml::from_str::<BTreeMap<String, BTreeMap<String, BTreeMap<String, toml::Value>>>>(
+            content,
+        )
+        .map_err(|error| AnswersError::Format(error.message().to_string()))?;
+
+    let mut expectations = vec![];
+    for (input, days) in inputs {
+        for (day_key, parts) in days {
+            let day = parse_key(&day_key, "day")?;
+            for (part_key, value) in parts {
+                let part = Part::from_number(parse_key(&part_key, "part")?)
+                    .ok_or_else(|| AnswersError::Format(format!("unknown part {}", part_key)))?;
+                let answer = match value {
+                    toml::Value::Integer(number) => Answer::Number(number),
+                    toml::Value::String(text) => Answer::Text(text),
+                    value => {
+                        return Err(AnswersError::Format(format!(
+                            "{}.{}.{} must be a number or a string, found {}",
+                            input, day_key, part_key, value
+                        )))
+                    }
+                };
+                expectations.push(Expectation {
+                    input: input.clone(),
+                    day,
+                    part,
+                    answer,
+                });
+            }
+        }
+    }
+
+    expectations.sort_by_key(|expectation| {
+        (
+            expectation.input.clone(),
+            expectation.day,
+            expectation.part.number(),
+        )
+    });
+    Ok(expectations)
+}
+
+fn parse_key(key: &str, prefix: &str) -> Result<u8, AnswersError> {
+    key.strip_prefix(prefix)
+        .and_then(|number| number.parse().ok())
+        .ok_or_else(|| AnswersError::Format(format!("expected {}N, found {}", prefix, key)))
+}
+
+pub fn verify(expectation: &Expectation) -> Check {
+    let result = read_input(expectation.input_path())
+        .map_err(|error| error.to_string())
+        .and_then(|input| {
+            solve_timed(expectation.day, expectation.part, &input)
+                .map_err(|error| error.to_string())
+        });
+
+    let (outcome, duration) = match result {
+        Ok((answer, duration)) if answer == expectation.answer => (Outcome::Correct, duration),
+        Ok((answer, duration)) => (Outcome::Wrong(answer), duration),
+        Err(error) => (Outcome::Failed(error), Duration::ZERO),
+    };
+
+    Check {
+        expectation: expectation.clone(),
+        outcome,
+        duration,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use crate::answers::*;
+    use crate::input::has_input;
+    use crate::registry;
+
+    #[test]
+    fn test_parse() {
+        let expectations = parse(
+            "[input.day13]\npart2 = '''\n#.\n.#'''\npart1 = 693\n\n[test_input.day2]\npart1 = 150\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            vec![
+                Expectation {
+                    input: "input".to_string(),
+                    day: 13,
+                    part: Part::One,
+                    answer: Answer::Number(693)
+                },
+                Expectation {
+                    input: "input".to_string(),
+                    day: 13,
+                    part: Part::Two,
+                    answer: Answer::Text("#.\n.#".to_string())
+                },
+                Expectation {
+                    input: "test_input".to_string(),
+                    day: 2,
+                    part: Part::One,
+                    answer: Answer::Number(150)
+                },
+            ],
+            expectations
+        );
+    }
+
+    #[rstest]
+    #[case("[input.1]\npart1 = 3\n")]
+    #[case("[input.day1]\npart3 = 3\n")]
+    #[case("[input.day1]\npart1 = 3.5\n")]
+    fn test_parse_invalid(#[case] content: &str) {
+        assert!(matches!(parse(content), Err(AnswersError::Format(_))));
+    }
+
+    #[test]
+    fn test_wrong_answer_is_reported() {
+        let expectation = Expectation {
+            input: "test_input".to_string(),
+            day: 7,
+            part: Part::One,
+            answer: Answer::Number(38),
+        };
+
+        let check = verify(&expectation);
+
+        assert_eq!(Outcome::Wrong(Answer::Number(37)), check.outcome);
+    }
+
+    /// Days without a committed input yet, like freshly scaffolded ones, cannot have answers.
+    #[test]
+    fn test_every_day_has_answers_for_its_input() {
+        let expectations = load(default_answers_path()).unwrap();
+
+        for solver in registry::solvers()
+            .iter()
+            .filter(|solver| has_input(solver.day()))
+        {
+            for part in Part::BOTH {
+                assert!(
+                    expectations.iter().any(|expectation| {
+                        (
+                            expectation.input.as_str(),
+                            expectation.day,
+                            expectation.part,
+                        ) == ("input", solver.day(), part)
+                    }),
+                    "day {} part {} has no answer",
+                    solver.day(),
+                    part
+                );
+            }
+        }
+    }
+
+    #[rstest]
+    #[case(1)]
+    #[case(2)]
+    #[case(3)]
+    #[case(4)]
+    #[case(5)]
+    #[case(6)]
+    #[case(7)]
+    #[case(8)]
+    #[case(9)]
+    #[case(10)]
+    #[case(11)]
+    #[case(12)]
+    #[case(13)]
+    #[case(14)]
+    #[case(15)]
+    #[case(16)]
+    #[case(17)]
+    #[case(18)]
+    fn test_answers(#[case] day: u8) {
+        let expectations = load(default_answers_path()).unwrap();
+
+        for expectation in expectations
+            .iter()
+            .filter(|expectation| expectation.day == day)
+        {
+            let check = verify(expectation);
+
+            assert_eq!(
+                Outcome::Correct,
+                check.outcome,
+                "{} day {} part {}",
+                expectation.input,
+                day,
+                expectation.part
+            );
+        }
+    }
+}
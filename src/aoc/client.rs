@@ -0,0 +1,223 @@
+use std::env;
+use std::fs::{create_dir_all, read_to_string, write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::thread::sleep;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use ureq::{Agent, AgentBuilder};
+
+use crate::aoc::inputs::{default_cache_dir, Fetcher};
+use crate::aoc::AocError;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+pub const USER_AGENT: &str = concat!(
+    "aoc2021/",
+    env!("CARGO_PKG_VERSION"),
+    " (+https://github.com/fengelniederhammer/aoc2021)"
+);
+
+/// Talks to the Advent of Code website with a session cookie. Requests are spaced at least
+/// `min_interval` apart so that the site is not hammered. With a throttle file the time of the
+/// last request is kept on disk, so the spacing holds across runs of the command too.
+pub struct AocClient {
+    agent: Agent,
+    base_url: String,
+    session: String,
+    min_interval: Duration,
+    throttle_file: Option<PathBuf>,
+    last_request: Mutex<Option<SystemTime>>,
+}
+
+impl AocClient {
+    pub fn new(session: impl Into<String>) -> AocClient {
+        AocClient {
+            agent: AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(Duration::from_secs(30))
+                .build(),
+            base_url: DEFAULT_BASE_URL.to_string(),
+            session: session.into(),
+            min_interval: Duration::from_secs(5),
+            throttle_file: None,
+            last_request: Mutex::new(None),
+        }
+    }
+
+    /// The client of `AOC_SESSION`, throttled through `last_request` in the cache dir.
+    pub fn from_env() -> Result<AocClient, AocError> {
+        env::var("AOC_SESSION")
+            .map(|session| {
+                AocClient::new(session).with_throttle_file(default_cache_dir().join("last_request"))
+            })
+            .map_err(|_| AocError::MissingSession)
+    }
+
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> AocClient {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn with_min_interval(mut self, min_interval: Duration) -> AocClient {
+        self.min_interval = min_interval;
+        self
+    }
+
+    pub fn with_throttle_file(mut self, throttle_file: impl Into<PathBuf>) -> AocClient {
+        self.throttle_file = Some(throttle_file.into());
+        self
+    }
+
+    pub fn get(&self, path: &str) -> Result<String, AocError> {
+        self.throttle();
+        let response = self
+            .agent
+            .get(&self.url(path))
+            .set("Cookie", &self.cookie())
+            .call();
+
+        read_response(response)
+    }
+
+    pub fn post_form(&self, path: &str, form: &[(&str, &str)]) -> Result<String, AocError> {
+        self.throttle();
+        let response = self
+            .agent
+            .post(&self.url(path))
+            .set("Cookie", &self.cookie())
+            .send_form(form);
+
+        read_response(response)
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    /// The throttle file is best effort: an unreadable one counts as no earlier request and one
+    /// that cannot be written does not stop the request.
+    fn throttle(&self) {
+        let mut last_request = self.last_request.lock().unwrap();
+        let stored = self.throttle_file.as_deref().and_then(read_last_request);
+        if let Some(last) = (*last_request).max(stored) {
+            // A request from the future means the clock went back, so wait the full interval
+            let elapsed = last.elapsed().unwrap_or_default();
+            if elapsed < self.min_interval {
+                sleep(self.min_interval - elapsed);
+            }
+        }
+
+        let now = SystemTime::now();
+        *last_request = Some(now);
+        if let Some(path) = &self.throttle_file {
+            write_last_request(path, now);
+        }
+    }
+}
+
+impl Fetcher for AocClient {
+    fn fetch_input(&self, year: u16, day: u8) -> Result<String, AocError> {
+        self.get(&format!("/{}/day/{}/input", year, day))
+    }
+}
+
+fn read_last_request(path: &Path) -> Option<SystemTime> {
+    let nanos = read_to_string(path).ok()?.trim().parse::<u64>().ok()?;
+    UNIX_EPOCH.checked_add(Duration::from_nanos(nanos))
+}
+
+fn write_last_request(path: &Path, time: SystemTime) {
+    let nanos = time
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_nanos())
+        .unwrap_or(0);
+    if let Some(parent) = path.parent() {
+        let _ = create_dir_all(parent);
+    }
+    let _ = write(path, nanos.to_string());
+}
+
+fn read_response(response: Result<ureq::Response, ureq::Error>) -> Result<String, AocError> {
+    match response {
+        Ok(response) => response
+            .into_string()
+            .map_err(|error| AocError::Transport(error.to_string())),
+        Err(ureq::Error::Status(status, response)) => Err(AocError::Http {
+            status,
+            body: response.into_string().unwrap_or_default(),
+        }),
+        Err(error) => Err(AocError::Transport(error.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env::temp_dir;
+    use std::fs::remove_file;
+    use std::time::Instant;
+
+    use crate::aoc::client::*;
+    use crate::aoc::stub_server::serve;
+
+    #[test]
+    fn test_fetch_input_sends_session_and_user_agent() {
+        let (base_url, requests) = serve(vec![(200, "199\n200\n")]);
+        let client = AocClient::new("abc123").with_base_url(base_url);
+
+        let input = client.fetch_input(2021, 1).unwrap();
+
+        assert_eq!("199\n200\n", input);
+        let request = requests.recv().unwrap().to_lowercase();
+        assert!(request.starts_with("get /2021/day/1/input http/1.1"));
+        assert!(request.contains("cookie: session=abc123"));
+        assert!(request.contains(&format!("user-agent: {}", USER_AGENT.to_lowercase())));
+    }
+
+    #[test]
+    fn test_error_status_is_reported() {
+        let (base_url, _requests) = serve(vec![(400, "Puzzle inputs differ by user.")]);
+        let client = AocClient::new("expired").with_base_url(base_url);
+
+        let error = client.fetch_input(2021, 1).unwrap_err();
+
+        assert!(matches!(error, AocError::Http { status: 400, .. }));
+    }
+
+    #[test]
+    fn test_requests_are_rate_limited() {
+        let (base_url, _requests) = serve(vec![(200, "1"), (200, "2")]);
+        let client = AocClient::new("abc123")
+            .with_base_url(base_url)
+            .with_min_interval(Duration::from_millis(200));
+
+        let start = Instant::now();
+        client.fetch_input(2021, 1).unwrap();
+        client.fetch_input(2021, 2).unwrap();
+
+        assert!(start.elapsed() >= Duration::from_millis(200));
+    }
+
+    #[test]
+    fn test_rate_limit_holds_across_clients() {
+        let (base_url, _requests) = serve(vec![(200, "1"), (200, "2")]);
+        let throttle_file = temp_dir().join(format!("aoc2021-throttle-{}", std::process::id()));
+        let client = |base_url: &str| {
+            AocClient::new("abc123")
+                .with_base_url(base_url)
+                .with_min_interval(Duration::from_millis(200))
+                .with_throttle_file(&throttle_file)
+        };
+
+        let start = Instant::now();
+        client(&base_url).fetch_input(2021, 1).unwrap();
+        client(&base_url).fetch_input(2021, 2).unwrap();
+
+        assert!(start.elapsed() >= Duration::from_millis(200));
+        remove_file(throttle_file).unwrap();
+    }
+}
@@ -0,0 +1,174 @@
+use std::env;
+use std::fs::{create_dir_all, write};
+use std::path::{Path, PathBuf};
+
+use crate::aoc::AocError;
+
+pub trait Fetcher {
+    fn fetch_input(&self, year: u16, day: u8) -> Result<String, AocError>;
+}
+
+/// Stands in for the client without a session cookie, so that only cached inputs resolve.
+pub struct Offline;
+
+impl Fetcher for Offline {
+    fn fetch_input(&self, _year: u16, _day: u8) -> Result<String, AocError> {
+        Err(AocError::MissingSession)
+    }
+}
+
+/// Puzzle inputs on disk, stored as `<dir>/<year>/dayNN.txt`.
+#[derive(Debug, Clone)]
+pub struct InputCache {
+    dir: PathBuf,
+}
+
+impl InputCache {
+    pub fn new(dir: impl Into<PathBuf>) -> InputCache {
+        InputCache { dir: dir.into() }
+    }
+
+    pub fn path_for(&self, year: u16, day: u8) -> PathBuf {
+        self.dir
+            .join(year.to_string())
+            .join(format!("day{:02}.txt", day))
+    }
+
+    pub fn contains(&self, year: u16, day: u8) -> bool {
+        self.path_for(year, day).is_file()
+    }
+
+    pub fn store(&self, year: u16, day: u8, content: &str) -> Result<PathBuf, AocError> {
+        let path = self.path_for(year, day);
+        let io_error = |source| AocError::Io {
+            path: path.clone(),
+            source,
+        };
+
+        if let Some(parent) = path.parent() {
+            create_dir_all(parent).map_err(io_error)?;
+        }
+        write(&path, content).map_err(io_error)?;
+        Ok(path)
+    }
+}
+
+/// `AOC_CACHE_DIR` if set, `inputs/` in the crate root otherwise.
+pub fn default_cache_dir() -> PathBuf {
+    env::var_os("AOC_CACHE_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join("inputs"))
+}
+
+/// Resolves puzzle inputs from the cache and only downloads the ones that are missing.
+pub struct InputManager<F: Fetcher> {
+    cache: InputCache,
+    fetcher: F,
+}
+
+impl<F: Fetcher> InputManager<F> {
+    pub fn new(cache: InputCache, fetcher: F) -> InputManager<F> {
+        InputManager { cache, fetcher }
+    }
+
+    pub fn resolve(&self, year: u16, day: u8) -> Result<PathBuf, AocError> {
+        match self.cache.contains(year, day) {
+            true => Ok(self.cache.path_for(year, day)),
+            false => self.fetch(year, day),
+        }
+    }
+
+    /// Downloads an input that is not cached yet. Cached inputs are never fetched again.
+    pub fn fetch(&self, year: u16, day: u8) -> Result<PathBuf, AocError> {
+        if self.cache.contains(year, day) {
+            return Err(AocError::AlreadyCached(self.cache.path_for(year, day)));
+        }
+
+        let content = self.fetcher.fetch_input(year, day)?;
+        self.cache.store(year, day, &content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::env::temp_dir;
+    use std::fs::{read_to_string, remove_dir_all};
+
+    use crate::aoc::inputs::*;
+
+    struct CountingFetcher {
+        calls: Cell<usize>,
+    }
+
+    impl Fetcher for CountingFetcher {
+        fn fetch_input(&self, year: u16, day: u8) -> Result<String, AocError> {
+            self.calls.set(self.calls.get() + 1);
+            Ok(format!("input of {} day {}", year, day))
+        }
+    }
+
+    fn manager_in(name: &str) -> (PathBuf, InputManager<CountingFetcher>) {
+        let dir = temp_dir().join(format!("aoc2021-{}-{}", name, std::process::id()));
+        let _ = remove_dir_all(&dir);
+        let fetcher = CountingFetcher {
+            calls: Cell::new(0),
+        };
+        (
+            dir.clone(),
+            InputManager::new(InputCache::new(dir), fetcher),
+        )
+    }
+
+    #[test]
+    fn test_path_for() {
+        let cache = InputCache::new("/tmp/inputs");
+
+        assert_eq!(
+            PathBuf::from("/tmp/inputs/2021/day05.txt"),
+            cache.path_for(2021, 5)
+        );
+    }
+
+    #[test]
+    fn test_resolve_fetches_missing_input_once() {
+        let (dir, manager) = manager_in("resolve");
+
+        let first = manager.resolve(2021, 5).unwrap();
+        let second = manager.resolve(2021, 5).unwrap();
+
+        assert_eq!(first, second);
+        assert_eq!(1, manager.fetcher.calls.get());
+        assert_eq!("input of 2021 day 5", read_to_string(first).unwrap());
+        remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_offline_resolves_only_cached_inputs() {
+        let (dir, manager) = manager_in("offline");
+        manager.fetch(2021, 3).unwrap();
+        let offline = InputManager::new(InputCache::new(&dir), Offline);
+
+        assert_eq!(
+            manager.resolve(2021, 3).unwrap(),
+            offline.resolve(2021, 3).unwrap()
+        );
+        assert!(matches!(
+            offline.resolve(2021, 4),
+            Err(AocError::MissingSession)
+        ));
+        remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_fetch_refuses_cached_input() {
+        let (dir, manager) = manager_in("refuse");
+        manager.fetch(2021, 7).unwrap();
+
+        let result = manager.fetch(2021, 7);
+
+        assert!(matches!(result, Err(AocError::AlreadyCached(_))));
+        assert_eq!(1, manager.fetcher.calls.get());
+        remove_dir_all(dir).unwrap();
+    }
+}
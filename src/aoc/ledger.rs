@@ -0,0 +1,284 @@
+use std::fmt::{Display, Formatter};
+use std::fs::{create_dir_all, read_to_string, write};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::aoc::inputs::default_cache_dir;
+use crate::aoc::submit::Verdict;
+use crate::aoc::AocError;
+use crate::solution::{Answer, Part};
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Attempt {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// When a `Verdict::Wait` ends, in seconds since the Unix epoch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wait_until: Option<u64>,
+}
+
+impl Attempt {
+    /// The attempt of `answer`, which got `verdict` at `time`.
+    pub fn new(
+        year: u16,
+        day: u8,
+        part: Part,
+        answer: &Answer,
+        verdict: Verdict,
+        time: SystemTime,
+    ) -> Attempt {
+        Attempt {
+            year,
+            day,
+            part: part.number(),
+            answer: answer.to_string(),
+            verdict,
+            wait_until: match verdict {
+                Verdict::Wait { seconds } => Some(unix_seconds(time) + seconds),
+                _ => None,
+            },
+        }
+    }
+}
+
+/// Why an answer is not worth submitting, judging by earlier attempts.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Refusal {
+    AlreadySolved(String),
+    KnownWrong,
+    TooHigh { bound: i64 },
+    TooLow { bound: i64 },
+    TooEarly { seconds: u64 },
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadySolved(answer) => write!(f, "already solved with {}", answer),
+            Refusal::KnownWrong => write!(f, "answer was already rejected"),
+            Refusal::TooHigh { bound } => write!(f, "{} was already too high", bound),
+            Refusal::TooLow { bound } => write!(f, "{} was already too low", bound),
+            Refusal::TooEarly { seconds } => write!(f, "too early, wait {}s", seconds),
+        }
+    }
+}
+
+/// Every submitted answer with its verdict, kept as a JSON file.
+#[derive(Debug)]
+pub struct Ledger {
+    path: PathBuf,
+    attempts: Vec<Attempt>,
+}
+
+/// `ledger.json` next to the cached inputs.
+pub fn default_ledger_path() -> PathBuf {
+    default_cache_dir().join("ledger.json")
+}
+
+impl Ledger {
+    pub fn load(path: impl AsRef<Path>) -> Result<Ledger, AocError> {
+        let path = path.as_ref().to_path_buf();
+        let attempts = match read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content)
+                .map_err(|error| AocError::Ledger(error.to_string()))?,
+            Err(error) if error.kind() == ErrorKind::NotFound => vec![],
+            Err(source) => return Err(AocError::Io { path, source }),
+        };
+
+        Ok(Ledger { path, attempts })
+    }
+
+    pub fn attempts(&self) -> &[Attempt] {
+        &self.attempts
+    }
+
+    pub fn record(&mut self, attempt: Attempt) -> Result<(), AocError> {
+        self.attempts.push(attempt);
+        self.save()
+    }
+
+    /// Whether `answer` is worth submitting at `now`.
+    pub fn check(
+        &self,
+        year: u16,
+        day: u8,
+        part: Part,
+        answer: &Answer,
+        now: SystemTime,
+    ) -> Result<(), Refusal> {
+        let answer_text = answer.to_string();
+
+        for attempt in self.attempts_for(year, day, part) {
+            if let Some(seconds) = attempt
+                .wait_until
+                .and_then(|until| until.checked_sub(unix_seconds(now)))
+                .filter(|seconds| *seconds > 0)
+            {
+                return Err(Refusal::TooEarly { seconds });
+            }
+
+            let bound = attempt.answer.parse::<i64>().ok();
+            match (attempt.verdict, answer, bound) {
+                (Verdict::Correct, _, _) => {
+                    return Err(Refusal::AlreadySolved(attempt.answer.clone()))
+                }
+                (Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong, _, _)
+                    if attempt.answer == answer_text =>
+                {
+                    return Err(Refusal::KnownWrong)
+                }
+                (Verdict::TooHigh, Answer::Number(number), Some(bound)) if *number >= bound => {
+                    return Err(Refusal::TooHigh { bound })
+                }
+                (Verdict::TooLow, Answer::Number(number), Some(bound)) if *number <= bound => {
+                    return Err(Refusal::TooLow { bound })
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    fn attempts_for(&self, year: u16, day: u8, part: Part) -> impl Iterator<Item = &Attempt> {
+        self.attempts.iter().filter(move |attempt| {
+            (attempt.year, attempt.day, attempt.part) == (year, day, part.number())
+        })
+    }
+
+    fn save(&self) -> Result<(), AocError> {
+        let content = serde_json::to_string_pretty(&self.attempts)
+            .map_err(|error| AocError::Ledger(error.to_string()))?;
+        let io_error = |source| AocError::Io {
+            path: self.path.clone(),
+            source,
+        };
+
+        if let Some(parent) = self.path.parent() {
+            create_dir_all(parent).map_err(io_error)?;
+        }
+        write(&self.path, content).map_err(io_error)
+    }
+}
+
+fn unix_seconds(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use std::time::Duration;
+
+    use crate::aoc::ledger::*;
+
+    fn ledger_with(attempts: Vec<(&str, Verdict)>) -> Ledger {
+        Ledger {
+            path: PathBuf::from("unused.json"),
+            attempts: attempts
+                .into_iter()
+                .map(|(answer, verdict)| Attempt {
+                    year: 2021,
+                    day: 1,
+                    part: 1,
+                    answer: answer.to_string(),
+                    verdict,
+                    wait_until: None,
+                })
+                .collect(),
+        }
+    }
+
+    #[rstest]
+    #[case(vec![], "1500", Ok(()))]
+    #[case(vec![("1400", Verdict::Wrong)], "1400", Err(Refusal::KnownWrong))]
+    #[case(vec![("1400", Verdict::TooHigh)], "1450", Err(Refusal::TooHigh { bound: 1400 }))]
+    #[case(vec![("1400", Verdict::TooLow)], "1300", Err(Refusal::TooLow { bound: 1400 }))]
+    #[case(vec![("1400", Verdict::TooLow), ("1600", Verdict::TooHigh)], "1500", Ok(()))]
+    #[case(vec![("1400", Verdict::Wait { seconds: 30 })], "1400", Ok(()))]
+    #[case(vec![("1400", Verdict::Correct)], "1500", Err(Refusal::AlreadySolved("1400".to_string())))]
+    fn test_check(
+        #[case] attempts: Vec<(&str, Verdict)>,
+        #[case] answer: &str,
+        #[case] expected: Result<(), Refusal>,
+    ) {
+        let ledger = ledger_with(attempts);
+
+        let result = ledger.check(
+            2021,
+            1,
+            Part::One,
+            &answer.parse().unwrap(),
+            SystemTime::now(),
+        );
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn test_other_parts_do_not_interfere() {
+        let ledger = ledger_with(vec![("1400", Verdict::Wrong)]);
+
+        let result = ledger.check(2021, 1, Part::Two, &Answer::Number(1400), SystemTime::now());
+
+        assert_eq!(Ok(()), result);
+    }
+
+    #[rstest]
+    #[case(0, Err(Refusal::TooEarly { seconds: 30 }))]
+    #[case(29, Err(Refusal::TooEarly { seconds: 1 }))]
+    #[case(30, Ok(()))]
+    fn test_check_waits(#[case] later: u64, #[case] expected: Result<(), Refusal>) {
+        let submitted = UNIX_EPOCH + Duration::from_secs(1_000_000);
+        let mut ledger = ledger_with(vec![]);
+        ledger.attempts.push(Attempt::new(
+            2021,
+            1,
+            Part::One,
+            &Answer::Number(1400),
+            Verdict::Wait { seconds: 30 },
+            submitted,
+        ));
+
+        let now = submitted + Duration::from_secs(later);
+        let result = ledger.check(2021, 1, Part::One, &Answer::Number(1400), now);
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn test_attempts_are_stored_as_json() {
+        let attempt = Attempt::new(
+            2021,
+            13,
+            Part::Two,
+            &Answer::Text("EFJKZLBL".to_string()),
+            Verdict::Wait { seconds: 42 },
+            UNIX_EPOCH + Duration::from_secs(1_000_000),
+        );
+
+        let json = serde_json::to_string(&attempt).unwrap();
+
+        assert_eq!(
+            r#"{"year":2021,"day":13,"part":2,"answer":"EFJKZLBL","verdict":{"wait":{"seconds":42}},"wait_until":1000042}"#,
+            json
+        );
+        assert_eq!(
+            None,
+            serde_json::from_str::<Attempt>(
+                r#"{"year":2021,"day":1,"part":1,"answer":"1","verdict":"wrong"}"#
+            )
+            .unwrap()
+            .wait_until
+        );
+    }
+}
@@ -0,0 +1,110 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::path::PathBuf;
+
+#[cfg(feature = "json")]
+use crate::aoc::ledger::Refusal;
+
+pub mod client;
+pub mod inputs;
+// Submitting keeps its ledger of attempts as JSON
+#[cfg(feature = "json")]
+pub mod ledger;
+#[cfg(feature = "json")]
+pub mod submit;
+
+#[derive(Debug)]
+pub enum AocError {
+    Http {
+        status: u16,
+        body: String,
+    },
+    Transport(String),
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+    AlreadyCached(PathBuf),
+    MissingSession,
+    #[cfg(feature = "json")]
+    Ledger(String),
+    #[cfg(feature = "json")]
+    Refused(Refusal),
+    #[cfg(feature = "json")]
+    UnknownVerdict,
+}
+
+impl Display for AocError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocError::Http { status, body } => {
+                write!(f, "server answered with {}: {}", status, body.trim())
+            }
+            AocError::Transport(message) => write!(f, "request failed: {}", message),
+            AocError::Io { path, source } => write!(f, "{}: {}", path.display(), source),
+            AocError::AlreadyCached(path) => {
+                write!(f, "input is already cached at {}", path.display())
+            }
+            AocError::MissingSession => write!(f, "no session cookie, set AOC_SESSION"),
+            #[cfg(feature = "json")]
+            AocError::Ledger(message) => write!(f, "broken submission ledger: {}", message),
+            #[cfg(feature = "json")]
+            AocError::Refused(refusal) => write!(f, "not submitted, {}", refusal),
+            #[cfg(feature = "json")]
+            AocError::UnknownVerdict => write!(f, "could not understand the verdict page"),
+        }
+    }
+}
+
+impl Error for AocError {}
+
+#[cfg(test)]
+pub(crate) mod stub_server {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc::{channel, Receiver};
+    use std::thread;
+
+    /// Serves the given responses in order and hands every received request back as raw text.
+    pub fn serve(responses: Vec<(u16, &'static str)>) -> (String, Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, receiver) = channel();
+
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+
+                let mut request = String::new();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(length) = line.to_lowercase().strip_prefix("content-length:") {
+                        content_length = length.trim().parse().unwrap();
+                    }
+                    request.push_str(&line);
+                    if line == "\r\n" || line.is_empty() {
+                        break;
+                    }
+                }
+                let mut body_bytes = vec![0; content_length];
+                reader.read_exact(&mut body_bytes).unwrap();
+                request.push_str(&String::from_utf8(body_bytes).unwrap());
+                sender.send(request).unwrap();
+
+                let response = format!(
+                    "HTTP/1.1 {} Stub\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                reader.get_mut().write_all(response.as_bytes()).unwrap();
+            }
+        });
+
+        (base_url, receiver)
+    }
+}
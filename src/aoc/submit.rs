@@ -0,0 +1,208 @@
+use std::fmt::{Display, Formatter};
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
+use crate::aoc::client::AocClient;
+use crate::aoc::ledger::{Attempt, Ledger};
+use crate::aoc::AocError;
+use crate::solution::{Answer, Part};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    Wait { seconds: u64 },
+    AlreadySolved,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "wrong, too high"),
+            Verdict::TooLow => write!(f, "wrong, too low"),
+            Verdict::Wrong => write!(f, "wrong"),
+            Verdict::Wait { seconds } => write!(f, "too early, wait {}s", seconds),
+            Verdict::AlreadySolved => write!(f, "already solved"),
+        }
+    }
+}
+
+/// Posts an answer unless the ledger already knows it to be wrong, and records the verdict.
+pub fn submit(
+    client: &AocClient,
+    ledger: &mut Ledger,
+    year: u16,
+    day: u8,
+    part: Part,
+    answer: &Answer,
+) -> Result<Verdict, AocError> {
+    ledger
+        .check(year, day, part, answer, SystemTime::now())
+        .map_err(AocError::Refused)?;
+
+    let page = client.post_form(
+        &format!("/{}/day/{}/answer", year, day),
+        &[
+            ("level", &part.number().to_string()),
+            ("answer", &answer.to_string()),
+        ],
+    )?;
+    let verdict = parse_verdict(&page).ok_or(AocError::UnknownVerdict)?;
+
+    ledger.record(Attempt::new(
+        year,
+        day,
+        part,
+        answer,
+        verdict,
+        SystemTime::now(),
+    ))?;
+    Ok(verdict)
+}
+
+pub fn parse_verdict(page: &str) -> Option<Verdict> {
+    let text = page.to_lowercase();
+
+    if text.contains("that's the right answer") {
+        Some(Verdict::Correct)
+    } else if text.contains("you gave an answer too recently") {
+        Some(Verdict::Wait {
+            seconds: parse_wait_time(&text).unwrap_or(60),
+        })
+    } else if text.contains("did you already complete it") {
+        Some(Verdict::AlreadySolved)
+    } else if text.contains("that's not the right answer") {
+        if text.contains("your answer is too high") {
+            Some(Verdict::TooHigh)
+        } else if text.contains("your answer is too low") {
+            Some(Verdict::TooLow)
+        } else {
+            Some(Verdict::Wrong)
+        }
+    } else {
+        None
+    }
+}
+
+/// Reads durations like "1m 30s" out of "you have 1m 30s left to wait".
+fn parse_wait_time(text: &str) -> Option<u64> {
+    let start = text.find("you have ")? + "you have ".len();
+    let end = start + text[start..].find(" left to wait")?;
+
+    text[start..end]
+        .split_whitespace()
+        .map(|amount| {
+            let (number, unit) = amount.split_at(amount.len() - 1);
+            let factor = match unit {
+                "h" => 3600,
+                "m" => 60,
+                "s" => 1,
+                _ => return None,
+            };
+            number.parse::<u64>().ok().map(|number| number * factor)
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env::temp_dir;
+    use std::fs::remove_file;
+
+    use rstest::*;
+
+    use crate::aoc::ledger::Refusal;
+    use crate::aoc::stub_server::serve;
+    use crate::aoc::submit::*;
+
+    #[rstest]
+    #[case(
+        "<article><p>That's the right answer!  You are one gold star closer.</p></article>",
+        Verdict::Correct
+    )]
+    #[case("<article><p>That's not the right answer; your answer is too high.  Please wait one minute.</p></article>", Verdict::TooHigh)]
+    #[case(
+        "<article><p>That's not the right answer; your answer is too low.</p></article>",
+        Verdict::TooLow
+    )]
+    #[case(
+        "<article><p>That's not the right answer.  If you're stuck, ...</p></article>",
+        Verdict::Wrong
+    )]
+    #[case("<article><p>You gave an answer too recently.  You have 1m 30s left to wait.</p></article>", Verdict::Wait { seconds: 90 })]
+    #[case("<article><p>You don't seem to be solving the right level.  Did you already complete it?</p></article>", Verdict::AlreadySolved)]
+    fn test_parse_verdict(#[case] page: &str, #[case] expected: Verdict) {
+        assert_eq!(Some(expected), parse_verdict(page));
+    }
+
+    #[test]
+    fn test_parse_unknown_verdict() {
+        assert_eq!(None, parse_verdict("<html>maintenance</html>"));
+    }
+
+    #[test]
+    fn test_submit_posts_answer_and_records_verdict() {
+        let path = temp_dir().join(format!("aoc2021-submit-{}.json", std::process::id()));
+        let _ = remove_file(&path);
+        let mut ledger = Ledger::load(&path).unwrap();
+        let (base_url, requests) = serve(vec![(
+            200,
+            "<article><p>That's not the right answer; your answer is too high.</p></article>",
+        )]);
+        let client = AocClient::new("abc123").with_base_url(base_url);
+
+        let verdict = submit(
+            &client,
+            &mut ledger,
+            2021,
+            1,
+            Part::Two,
+            &Answer::Number(1200),
+        );
+
+        assert_eq!(Verdict::TooHigh, verdict.unwrap());
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("POST /2021/day/1/answer HTTP/1.1"));
+        assert!(request.ends_with("level=2&answer=1200"));
+        assert_eq!(1, Ledger::load(&path).unwrap().attempts().len());
+
+        let result = submit(
+            &client,
+            &mut ledger,
+            2021,
+            1,
+            Part::Two,
+            &Answer::Number(1300),
+        );
+
+        assert!(matches!(result, Err(AocError::Refused(_))));
+        remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_submit_waits_out_the_wait_verdict() {
+        let path = temp_dir().join(format!("aoc2021-submit-wait-{}.json", std::process::id()));
+        let _ = remove_file(&path);
+        let mut ledger = Ledger::load(&path).unwrap();
+        let (base_url, _requests) = serve(vec![(
+            200,
+            "<article><p>You gave an answer too recently.  You have 1m 30s left to wait.</p></article>",
+        )]);
+        let client = AocClient::new("abc123").with_base_url(base_url);
+        let answer = Answer::Number(1200);
+
+        submit(&client, &mut ledger, 2021, 1, Part::One, &answer).unwrap();
+        let result = submit(&client, &mut ledger, 2021, 1, Part::One, &answer);
+
+        assert!(matches!(
+            result,
+            Err(AocError::Refused(Refusal::TooEarly { seconds: 89..=90 }))
+        ));
+        remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,273 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+#[cfg(feature = "json")]
+use std::fs::{read_to_string, write};
+use std::io;
+#[cfg(feature = "json")]
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "json")]
+use serde::{Deserialize, Serialize};
+
+use crate::parse::ParseError;
+use crate::solution::{Part, Solver};
+
+#[derive(Debug)]
+pub enum BenchError {
+    Io { path: PathBuf, source: io::Error },
+    Format(String),
+}
+
+impl Display for BenchError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BenchError::Io { path, source } => write!(f, "{}: {}", path.display(), source),
+            BenchError::Format(message) => write!(f, "invalid baseline: {}", message),
+        }
+    }
+}
+
+impl Error for BenchError {}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(
+    feature = "json",
+    derive(Serialize, Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum Stage {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Display for Stage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Stage::Parse => write!(f, "parse"),
+            Stage::Part1 => write!(f, "part 1"),
+            Stage::Part2 => write!(f, "part 2"),
+        }
+    }
+}
+
+/// Wall times of one stage over several runs, in nanoseconds.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct Measurement {
+    pub day: u8,
+    pub stage: Stage,
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub max_ns: u64,
+}
+
+impl Measurement {
+    fn from_samples(day: u8, stage: Stage, mut samples: Vec<Duration>) -> Measurement {
+        samples.sort();
+        let nanos = |duration: &Duration| duration.as_nanos() as u64;
+
+        Measurement {
+            day,
+            stage,
+            min_ns: samples.first().map(nanos).unwrap_or(0),
+            median_ns: samples.get(samples.len() / 2).map(nanos).unwrap_or(0),
+            max_ns: samples.last().map(nanos).unwrap_or(0),
+        }
+    }
+
+    pub fn min(&self) -> Duration {
+        Duration::from_nanos(self.min_ns)
+    }
+
+    pub fn median(&self) -> Duration {
+        Duration::from_nanos(self.median_ns)
+    }
+
+    pub fn max(&self) -> Duration {
+        Duration::from_nanos(self.max_ns)
+    }
+}
+
+/// A stage whose median got slower than the baseline allows.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Regression {
+    pub day: u8,
+    pub stage: Stage,
+    pub baseline: Duration,
+    pub current: Duration,
+}
+
+/// Runs parse, part 1 and part 2 of a day `runs` times each. The parts include cloning the parsed input.
+pub fn measure(
+    solver: &dyn Solver,
+    input: &str,
+    runs: usize,
+) -> Result<Vec<Measurement>, ParseError> {
+    let mut parse_samples = vec![];
+    let mut parsed = solver.parse(input)?;
+    for _ in 0..runs {
+        let start = Instant::now();
+        parsed = solver.parse(input)?;
+        parse_samples.push(start.elapsed());
+    }
+
+    let mut measurements = vec![Measurement::from_samples(
+        solver.day(),
+        Stage::Parse,
+        parse_samples,
+    )];
+    for (part, stage) in Part::BOTH.into_iter().zip([Stage::Part1, Stage::Part2]) {
+        let samples = (0..runs)
+            .map(|_| {
+                let start = Instant::now();
+                solver.run(&parsed, part);
+                start.elapsed()
+            })
+            .collect();
+        measurements.push(Measurement::from_samples(solver.day(), stage, samples));
+    }
+
+    Ok(measurements)
+}
+
+/// Medians that exceed the baseline median by more than `tolerance`, e.g. 0.2 for 20%.
+pub fn regressions(
+    baseline: &[Measurement],
+    current: &[Measurement],
+    tolerance: f64,
+) -> Vec<Regression> {
+    current
+        .iter()
+        .filter_map(|measurement| {
+            let previous = baseline.iter().find(|previous| {
+                (previous.day, previous.stage) == (measurement.day, measurement.stage)
+            })?;
+
+            let allowed = previous.median_ns as f64 * (1.0 + tolerance);
+            match measurement.median_ns as f64 > allowed {
+                true => Some(Regression {
+                    day: measurement.day,
+                    stage: measurement.stage,
+                    baseline: previous.median(),
+                    current: measurement.median(),
+                }),
+                false => None,
+            }
+        })
+        .collect()
+}
+
+#[cfg(feature = "json")]
+pub fn load_baseline(path: impl AsRef<Path>) -> Result<Vec<Measurement>, BenchError> {
+    let content = read_to_string(path.as_ref()).map_err(|source| BenchError::Io {
+        path: path.as_ref().to_path_buf(),
+        source,
+    })?;
+    serde_json::from_str(&content).map_err(|error| BenchError::Format(error.to_string()))
+}
+
+#[cfg(feature = "json")]
+pub fn save_baseline(
+    path: impl AsRef<Path>,
+    measurements: &[Measurement],
+) -> Result<(), BenchError> {
+    let content = serde_json::to_string_pretty(measurements)
+        .map_err(|error| BenchError::Format(error.to_string()))?;
+    write(path.as_ref(), content).map_err(|source| BenchError::Io {
+        path: path.as_ref().to_path_buf(),
+        source,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "json")]
+    use std::env::temp_dir;
+    #[cfg(feature = "json")]
+    use std::fs::remove_file;
+
+    use crate::bench::*;
+    use crate::day7::Day7;
+    use crate::input::read_input;
+
+    fn measurement(day: u8, stage: Stage, median_ns: u64) -> Measurement {
+        Measurement {
+            day,
+            stage,
+            min_ns: median_ns / 2,
+            median_ns,
+            max_ns: median_ns * 2,
+        }
+    }
+
+    #[test]
+    fn test_from_samples() {
+        let samples = [30, 10, 50, 20, 40].map(Duration::from_nanos).to_vec();
+
+        let result = Measurement::from_samples(1, Stage::Part1, samples);
+
+        assert_eq!(
+            (10, 30, 50),
+            (result.min_ns, result.median_ns, result.max_ns)
+        );
+    }
+
+    #[test]
+    fn test_measure() {
+        let input = read_input("src/day7/test_input.txt").unwrap();
+
+        let result = measure(&Day7, &input, 3).unwrap();
+
+        assert_eq!(
+            vec![(7, Stage::Parse), (7, Stage::Part1), (7, Stage::Part2)],
+            result
+                .iter()
+                .map(|measurement| (measurement.day, measurement.stage))
+                .collect::<Vec<_>>()
+        );
+        assert!(result
+            .iter()
+            .all(|measurement| measurement.min_ns <= measurement.median_ns
+                && measurement.median_ns <= measurement.max_ns));
+    }
+
+    #[test]
+    fn test_regressions() {
+        let baseline = vec![
+            measurement(9, Stage::Part1, 1000),
+            measurement(9, Stage::Part2, 1000),
+        ];
+        let current = vec![
+            measurement(9, Stage::Part1, 1100),
+            measurement(9, Stage::Part2, 1300),
+            measurement(12, Stage::Part2, 5000),
+        ];
+
+        let result = regressions(&baseline, &current, 0.2);
+
+        assert_eq!(
+            vec![Regression {
+                day: 9,
+                stage: Stage::Part2,
+                baseline: Duration::from_nanos(1000),
+                current: Duration::from_nanos(1300),
+            }],
+            result
+        );
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_baseline_round_trip() {
+        let path = temp_dir().join(format!("aoc2021-bench-{}.json", std::process::id()));
+        let measurements = vec![measurement(1, Stage::Parse, 42)];
+
+        save_baseline(&path, &measurements).unwrap();
+
+        assert_eq!(measurements, load_baseline(&path).unwrap());
+        remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,144 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// Reading past the end of a [`BitReader`]. `offset` is the position the read started at.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct BitError {
+    pub offset: usize,
+    pub wanted: usize,
+    pub available: usize,
+}
+
+impl Display for BitError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "wanted {} bits at bit {}, but only {} are left",
+            self.wanted, self.offset, self.available
+        )
+    }
+}
+
+impl Error for BitError {}
+
+/// Reads unsigned numbers of any width up to 64 bits from a bit stream, most significant bit
+/// first, and keeps track of the position for error messages.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct BitReader {
+    bits: Vec<bool>,
+    position: usize,
+}
+
+impl BitReader {
+    pub fn new(bits: Vec<bool>) -> BitReader {
+        BitReader { bits, position: 0 }
+    }
+
+    /// Every digit contributes its lowest `width` bits, so width 4 reads hexadecimal digits and
+    /// width 1 binary ones.
+    pub fn from_digits(digits: &[u32], width: usize) -> BitReader {
+        BitReader::new(
+            digits
+                .iter()
+                .flat_map(|digit| (0..width).rev().map(move |bit| digit >> bit & 1 == 1))
+                .collect(),
+        )
+    }
+
+    /// The number of bits read so far.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.bits.len() - self.position
+    }
+
+    pub fn read(&mut self, width: usize) -> Result<u64, BitError> {
+        assert!(width <= 64, "cannot read {} bits into a u64", width);
+        if width > self.remaining() {
+            return Err(BitError {
+                offset: self.position,
+                wanted: width,
+                available: self.remaining(),
+            });
+        }
+
+        let bits = &self.bits[self.position..self.position + width];
+        self.position += width;
+        Ok(bits
+            .iter()
+            .fold(0, |number, bit| number << 1 | u64::from(*bit)))
+    }
+
+    pub fn read_bool(&mut self) -> Result<bool, BitError> {
+        Ok(self.read(1)? == 1)
+    }
+
+    /// Whether all bits left are zeros, like the padding at the end of a transmission.
+    pub fn is_padding(&self) -> bool {
+        self.bits[self.position..].iter().all(|bit| !bit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use crate::bits::*;
+
+    #[rstest]
+    #[case(&[0xD, 0x2], 4, vec![true, true, false, true, false, false, true, false])]
+    #[case(&[1, 0, 1], 1, vec![true, false, true])]
+    #[case(&[0b110], 2, vec![true, false])]
+    fn test_from_digits(#[case] digits: &[u32], #[case] width: usize, #[case] expected: Vec<bool>) {
+        assert_eq!(
+            BitReader::new(expected),
+            BitReader::from_digits(digits, width)
+        );
+    }
+
+    #[test]
+    fn test_read() {
+        let mut reader = BitReader::from_digits(&[0xD, 0x2, 0xF, 0xE, 0x2, 0x8], 4);
+
+        assert_eq!(Ok(6), reader.read(3));
+        assert_eq!(Ok(true), reader.read_bool());
+        assert_eq!(Ok(0b00101), reader.read(5));
+        assert_eq!(9, reader.position());
+        assert_eq!(15, reader.remaining());
+        assert_eq!(Ok(0), reader.read(0));
+        assert!(!reader.is_padding());
+    }
+
+    #[test]
+    fn test_read_past_the_end() {
+        let mut reader = BitReader::from_digits(&[0xA, 0x0], 4);
+        reader.read(3).unwrap();
+
+        let error = reader.read(6).unwrap_err();
+
+        assert_eq!(
+            BitError {
+                offset: 3,
+                wanted: 6,
+                available: 5
+            },
+            error
+        );
+        assert_eq!(
+            "wanted 6 bits at bit 3, but only 5 are left",
+            error.to_string()
+        );
+        assert_eq!(3, reader.position());
+        assert!(reader.is_padding());
+    }
+
+    #[test]
+    fn test_read_64_bits() {
+        let mut reader = BitReader::new(vec![true; 65]);
+
+        assert_eq!(Ok(u64::MAX), reader.read(64));
+        assert_eq!(Ok(true), reader.read_bool());
+    }
+}
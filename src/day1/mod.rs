@@ -1,21 +1,47 @@
 use std::cmp::Ordering;
-use std::fs::read_to_string;
 
-pub fn get_answer_1() -> usize {
-    get_increase_count(read_file())
+use crate::input::{default_input_path, InputError};
+use crate::parse::{input_lines, ParseError};
+use crate::solution::{Answer, Solution};
+
+const DAY: u8 = 1;
+
+pub fn get_answer_1() -> Result<usize, InputError> {
+    Ok(get_increase_count(Day1::read_file(default_input_path(
+        DAY,
+    ))?))
 }
 
-pub fn get_answer_2() -> usize {
-    get_increase_windowed_count(read_file())
+pub fn get_answer_2() -> Result<usize, InputError> {
+    Ok(get_increase_windowed_count(Day1::read_file(
+        default_input_path(DAY),
+    )?))
 }
 
-fn read_file() -> Vec<i32> {
-    let file_content = read_to_string("src/day1/input.txt").expect("hoppla");
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = DAY;
+
+    type Input = Vec<i32>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: Self::Input) -> Answer {
+        get_increase_count(input).into()
+    }
+
+    fn part2(input: Self::Input) -> Answer {
+        get_increase_windowed_count(input).into()
+    }
+}
 
-    file_content
-        .lines()
-        .into_iter()
-        .filter_map(|line| line.parse::<i32>().ok())
+pub fn parse(input: &str) -> Result<Vec<i32>, ParseError> {
+    input_lines(DAY, input)
+        .filter(|line| !line.text.is_empty())
+        .map(|line| line.parse::<i32>(line.text, "a depth measurement"))
         .collect()
 }
 
@@ -31,7 +57,7 @@ fn compare_with_window_size(input: Vec<i32>, window_size: usize) -> usize {
     input
         .iter()
         .zip(input.iter().skip(window_size))
-        .map(|(first, second)| first.cmp(&second))
+        .map(|(first, second)| first.cmp(second))
         .filter(|cmp| cmp == &Ordering::Less)
         .count()
 }
@@ -44,13 +70,21 @@ mod tests {
 
     #[test]
     fn test_read_file() {
-        let results = read_file();
+        let results = Day1::read_file("src/day1/input.txt").unwrap();
 
         assert_eq!(2000, results.len());
         assert_eq!(Some(&141), results.first());
         assert_eq!(Some(&2682), results.last());
     }
 
+    #[test]
+    fn test_parse_error() {
+        let error = parse("141\n152\n16x4\n").unwrap_err();
+
+        assert_eq!((1, 3, 1), (error.day, error.line, error.column));
+        assert_eq!("16x4", error.text);
+    }
+
     #[rstest]
     #[case(vec ! [1], 0)]
     #[case(vec ! [1, 1], 0)]
@@ -80,12 +114,4 @@ mod tests {
 
         assert_eq!(expected, result);
     }
-
-    #[test]
-    fn result() {
-        println!("{}", get_answer_1());
-        assert_eq!(1184, get_answer_1());
-        println!("{}", get_answer_2());
-        assert_eq!(1158, get_answer_2());
-    }
 }
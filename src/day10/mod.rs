@@ -1,34 +1,94 @@
-use std::fs::read_to_string;
+use crate::input::{default_input_path, InputError};
+use crate::parse::{input_lines, InputLine, ParseError};
+use crate::solution::{Answer, Solution};
 
-pub fn get_answer_1() -> usize {
-    get_error_score(read_file("src/day10/input.txt"))
+const DAY: u8 = 10;
+
+pub fn get_answer_1() -> Result<usize, InputError> {
+    Ok(get_error_score(Day10::read_file(default_input_path(DAY))?))
 }
 
-pub fn get_answer_2() -> usize {
-    find_middle_completion_score(read_file("src/day10/input.txt"))
+pub fn get_answer_2() -> Result<usize, InputError> {
+    Ok(find_middle_completion_score(Day10::read_file(
+        default_input_path(DAY),
+    )?))
 }
 
-fn read_file(path: &str) -> Vec<Vec<char>> {
-    let file_content = read_to_string(path).expect("hoppla");
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = DAY;
+
+    type Input = Vec<Vec<Token>>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: Self::Input) -> Answer {
+        get_error_score(input).into()
+    }
 
-    file_content
-        .lines()
-        .map(|str| str.chars().collect())
+    fn part2(input: Self::Input) -> Answer {
+        find_middle_completion_score(input).into()
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<Vec<Token>>, ParseError> {
+    let lines = input_lines(DAY, input)
+        .map(parse_line)
+        .collect::<Result<Vec<Vec<Token>>, ParseError>>()?;
+
+    // The second part takes the middle score of the incomplete lines.
+    match lines
+        .iter()
+        .any(|line| matches!(compute_line_state(line.clone()), Line::Incomplete(_)))
+    {
+        true => Ok(lines),
+        false => {
+            let last = input_lines(DAY, input)
+                .last()
+                .unwrap_or_else(|| InputLine::new(DAY, 0, ""));
+            Err(last.error(
+                &last.text[last.text.len()..],
+                "expected at least one incomplete line",
+            ))
+        }
+    }
+}
+
+pub(crate) fn parse_line(line: InputLine) -> Result<Vec<Token>, ParseError> {
+    line.text
+        .char_indices()
+        .map(|(index, char)| match char {
+            '(' => Ok(Token::Open(Bracket::Round)),
+            '[' => Ok(Token::Open(Bracket::Square)),
+            '{' => Ok(Token::Open(Bracket::Curly)),
+            '<' => Ok(Token::Open(Bracket::Pointy)),
+            ')' => Ok(Token::Close(Bracket::Round)),
+            ']' => Ok(Token::Close(Bracket::Square)),
+            '}' => Ok(Token::Close(Bracket::Curly)),
+            '>' => Ok(Token::Close(Bracket::Pointy)),
+            _ => Err(line.error(
+                &line.text[index..index + char.len_utf8()],
+                "expected one of ()[]{}<>",
+            )),
+        })
         .collect()
 }
 
-fn get_error_score(input: Vec<Vec<char>>) -> usize {
+fn get_error_score(input: Vec<Vec<Token>>) -> usize {
     input.into_iter().map(compute_line_score).sum()
 }
 
-fn compute_line_score(line: Vec<char>) -> usize {
+fn compute_line_score(line: Vec<Token>) -> usize {
     match compute_line_state(line) {
         Line::Corrupted(score) => score,
         Line::Complete | Line::Incomplete(_) => 0,
     }
 }
 
-fn find_middle_completion_score(input: Vec<Vec<char>>) -> usize {
+fn find_middle_completion_score(input: Vec<Vec<Token>>) -> usize {
     let mut scores = input.into_iter().filter_map(do_it).collect::<Vec<usize>>();
 
     scores.sort();
@@ -36,7 +96,7 @@ fn find_middle_completion_score(input: Vec<Vec<char>>) -> usize {
     scores[scores.len() / 2]
 }
 
-fn do_it(line: Vec<char>) -> Option<usize> {
+fn do_it(line: Vec<Token>) -> Option<usize> {
     match compute_line_state(line) {
         Line::Corrupted(_) | Line::Complete => None,
         Line::Incomplete(missing_brackets) => Some(
@@ -48,32 +108,17 @@ fn do_it(line: Vec<char>) -> Option<usize> {
     }
 }
 
-fn compute_line_state(line: Vec<char>) -> Line {
+pub(crate) fn compute_line_state(line: Vec<Token>) -> Line {
     let mut bracket_stack = vec![];
 
-    for bracket in line {
-        match bracket {
-            '(' => bracket_stack.push(Bracket::Round),
-            '[' => bracket_stack.push(Bracket::Square),
-            '{' => bracket_stack.push(Bracket::Curly),
-            '<' => bracket_stack.push(Bracket::Pointy),
-            ')' => match bracket_stack.pop() {
-                Some(Bracket::Round) => {}
-                _ => return Line::Corrupted(3),
-            },
-            ']' => match bracket_stack.pop() {
-                Some(Bracket::Square) => {}
-                _ => return Line::Corrupted(57),
-            },
-            '}' => match bracket_stack.pop() {
-                Some(Bracket::Curly) => {}
-                _ => return Line::Corrupted(1197),
-            },
-            '>' => match bracket_stack.pop() {
-                Some(Bracket::Pointy) => {}
-                _ => return Line::Corrupted(25137),
-            },
-            unexpected => panic!("{} should not be here", unexpected),
+    for token in line {
+        match token {
+            Token::Open(bracket) => bracket_stack.push(bracket),
+            Token::Close(bracket) => {
+                if bracket_stack.pop() != Some(bracket) {
+                    return Line::Corrupted(bracket.error_score());
+                }
+            }
         }
     }
 
@@ -83,13 +128,21 @@ fn compute_line_state(line: Vec<char>) -> Line {
     }
 }
 
-enum Line {
+#[derive(Debug, Eq, PartialEq)]
+pub(crate) enum Line {
     Corrupted(usize),
     Complete,
     Incomplete(Vec<Bracket>),
 }
 
-enum Bracket {
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Token {
+    Open(Bracket),
+    Close(Bracket),
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Bracket {
     Round,
     Square,
     Curly,
@@ -97,6 +150,15 @@ enum Bracket {
 }
 
 impl Bracket {
+    fn error_score(&self) -> usize {
+        match self {
+            Bracket::Round => 3,
+            Bracket::Square => 57,
+            Bracket::Curly => 1197,
+            Bracket::Pointy => 25137,
+        }
+    }
+
     fn completion_score(&self) -> usize {
         match self {
             Bracket::Round => 1,
@@ -109,19 +171,48 @@ impl Bracket {
 
 #[cfg(test)]
 mod tests {
+    use rstest::*;
+
     use crate::day10::*;
 
     #[test]
     fn test_read_file() {
-        let input = read_file("src/day10/input.txt");
+        let input = Day10::read_file("src/day10/input.txt").unwrap();
 
-        assert_eq!('(', input[0][0]);
-        assert_eq!(&'(', input.last().unwrap().last().unwrap());
+        assert_eq!(Token::Open(Bracket::Round), input[0][0]);
+        assert_eq!(
+            &Token::Open(Bracket::Round),
+            input.last().unwrap().last().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error =
+            parse("[({(<(())[]>[[{[]{<()<>>\n[(()[<>])]({[<{<<[]>>(\n(((({<>}<{<{a>}{[]{[]{}")
+                .unwrap_err();
+
+        assert_eq!((10, 3, 13), (error.day, error.line, error.column));
+        assert_eq!("a", error.text);
+    }
+
+    #[rstest]
+    #[case("", 1, 1)]
+    #[case("()\n[<>]\n{]", 3, 3)]
+    fn test_parse_error_without_incomplete_lines(
+        #[case] input: &str,
+        #[case] line: usize,
+        #[case] column: usize,
+    ) {
+        let error = parse(input).unwrap_err();
+
+        assert_eq!((10, line, column), (error.day, error.line, error.column));
+        assert_eq!("expected at least one incomplete line", error.reason);
     }
 
     #[test]
     fn test_score() {
-        let input = read_file("src/day10/test_input.txt");
+        let input = Day10::read_file("src/day10/test_input.txt").unwrap();
 
         let result = get_error_score(input);
 
@@ -130,18 +221,10 @@ mod tests {
 
     #[test]
     fn test_score_completions() {
-        let input = read_file("src/day10/test_input.txt");
+        let input = Day10::read_file("src/day10/test_input.txt").unwrap();
 
         let result = find_middle_completion_score(input);
 
         assert_eq!(288957, result);
     }
-
-    #[test]
-    fn result() {
-        println!("{}", get_answer_1());
-        assert_eq!(389589, get_answer_1());
-        println!("{}", get_answer_2());
-        assert_eq!(1190420163, get_answer_2());
-    }
 }
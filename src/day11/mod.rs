@@ -1,65 +1,101 @@
-use std::collections::HashMap;
-use std::fs::read_to_string;
-
-pub fn get_answer_1() -> usize {
-    count_flashes(read_file("src/day11/input.txt"))
+use crate::animate::{draw_frame, Observer};
+use crate::geometry::Point2;
+use crate::grid::Grid;
+use crate::input::{default_input_path, InputError};
+use crate::parse::ParseError;
+use crate::render::{Color, Frame};
+use crate::solution::{Answer, Solution};
+
+const DAY: u8 = 11;
+
+pub fn get_answer_1() -> Result<usize, InputError> {
+    Ok(count_flashes(Day11::read_file(default_input_path(DAY))?))
 }
 
-pub fn get_answer_2() -> usize {
-    find_synchronization_step(read_file("src/day11/input.txt"))
+pub fn get_answer_2() -> Result<usize, InputError> {
+    Ok(find_synchronization_step(Day11::read_file(
+        default_input_path(DAY),
+    )?))
 }
 
-fn read_file(path: &str) -> HashMap<Point, usize> {
-    let file_content = read_to_string(path).expect("hoppla");
+pub struct Day11;
 
-    let mut map = HashMap::new();
-    for (j, line) in file_content.lines().enumerate() {
-        for (i, char) in line.chars().enumerate() {
-            map.insert(
-                (i as isize, j as isize),
-                char.to_string().parse::<usize>().unwrap(),
-            );
-        }
+impl Solution for Day11 {
+    const DAY: u8 = DAY;
+
+    type Input = Grid<usize>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: Self::Input) -> Answer {
+        count_flashes(input).into()
+    }
+
+    fn part2(input: Self::Input) -> Answer {
+        find_synchronization_step(input).into()
     }
-    map
 }
 
-type Point = (isize, isize);
+pub fn parse(input: &str) -> Result<Grid<usize>, ParseError> {
+    Grid::parse_digits(DAY, input)
+}
+
+fn count_flashes(input: Grid<usize>) -> usize {
+    count_flashes_observed(input, &mut ())
+}
 
-fn count_flashes(mut input: HashMap<Point, usize>) -> usize {
+pub fn count_flashes_observed(
+    mut input: Grid<usize>,
+    observer: &mut impl Observer<Grid<usize>>,
+) -> usize {
     let mut flashes = 0;
+    observer.observe(0, &input);
 
-    for _ in 0..100 {
+    for step in 1..=100 {
         flashes += do_step_and_get_flashing_points(&mut input).len();
+        observer.observe(step, &input);
     }
 
     flashes
 }
 
-fn find_synchronization_step(mut input: HashMap<Point, usize>) -> usize {
+fn find_synchronization_step(input: Grid<usize>) -> usize {
+    find_synchronization_step_observed(input, &mut ())
+}
+
+pub fn find_synchronization_step_observed(
+    mut input: Grid<usize>,
+    observer: &mut impl Observer<Grid<usize>>,
+) -> usize {
     let mut step = 1;
+    observer.observe(0, &input);
 
-    while do_step_and_get_flashing_points(&mut input).len() != 100 {
+    while do_step_and_get_flashing_points(&mut input).len() != input.len() {
+        observer.observe(step, &input);
         step += 1;
     }
 
+    observer.observe(step, &input);
     step
 }
 
-fn do_step_and_get_flashing_points(mut input: &mut HashMap<Point, usize>) -> Vec<Point> {
-    input.iter_mut().for_each(|(_, energy)| *energy += 1);
+pub(crate) fn do_step_and_get_flashing_points(input: &mut Grid<usize>) -> Vec<Point2> {
+    input.values_mut().for_each(|energy| *energy += 1);
 
-    let mut flashing_points = find_new_flashing_points(&mut input, &vec![]);
+    let mut flashing_points = find_new_flashing_points(input, &[]);
 
     let mut new_flashing_points = flashing_points.clone();
     while !new_flashing_points.is_empty() {
         for point in &new_flashing_points {
-            for neighbour in neighbouring_points_of(*point) {
-                input.entry(neighbour).and_modify(|energy| *energy += 1);
+            let neighbours = input.neighbours_8(*point).collect::<Vec<Point2>>();
+            for neighbour in neighbours {
+                input[neighbour] += 1;
             }
         }
 
-        new_flashing_points = find_new_flashing_points(&input, &flashing_points);
+        new_flashing_points = find_new_flashing_points(input, &flashing_points);
         flashing_points.append(&mut new_flashing_points.clone())
     }
 
@@ -70,45 +106,62 @@ fn do_step_and_get_flashing_points(mut input: &mut HashMap<Point, usize>) -> Vec
     flashing_points
 }
 
-fn find_new_flashing_points(
-    input: &HashMap<Point, usize>,
-    flashing_points: &[Point],
-) -> Vec<Point> {
+/// The energy field before the first and after every step, flashing octopuses in white.
+pub fn render_steps(mut input: Grid<usize>, steps: usize) -> Vec<Frame> {
+    let mut frames = vec![render_energy(&input)];
+
+    for _ in 0..steps {
+        do_step_and_get_flashing_points(&mut input);
+        frames.push(render_energy(&input));
+    }
+
+    frames
+}
+
+pub fn draw_energy(input: &Grid<usize>) -> String {
+    draw_frame(&render_energy(input))
+}
+
+fn render_energy(input: &Grid<usize>) -> Frame {
+    input.map(|energy| match energy {
+        0 => Color::WHITE,
+        energy => Color::BLACK.mix(Color::GOLD, *energy as f64 / 10.0),
+    })
+}
+
+fn find_new_flashing_points(input: &Grid<usize>, flashing_points: &[Point2]) -> Vec<Point2> {
     input
         .iter()
         .filter(|(point, energy)| energy > &&9 && !flashing_points.contains(point))
-        .map(|(point, _)| *point)
-        .collect::<Vec<Point>>()
-}
-
-fn neighbouring_points_of((i, j): Point) -> Vec<Point> {
-    vec![
-        (i + 1, j),
-        (i + 1, j + 1),
-        (i, j + 1),
-        (i - 1, j + 1),
-        (i - 1, j),
-        (i - 1, j - 1),
-        (i, j - 1),
-        (i + 1, j - 1),
-    ]
+        .map(|(point, _)| point)
+        .collect::<Vec<Point2>>()
 }
 
 #[cfg(test)]
 mod tests {
     use crate::day11::*;
+    use crate::render::tests::assert_golden;
+    use crate::render::to_ppm;
 
     #[test]
     fn test_read_file() {
-        let matrix = read_file("src/day11/input.txt");
+        let matrix = Day11::read_file("src/day11/input.txt").unwrap();
 
-        assert_eq!(5, matrix[&(0, 0)]);
-        assert_eq!(3, matrix[&(9, 9)]);
+        assert_eq!(5, matrix[Point2::new(0, 0)]);
+        assert_eq!(3, matrix[Point2::new(9, 9)]);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = parse("5483143223\n27452-5541\n").unwrap_err();
+
+        assert_eq!((11, 2, 6), (error.day, error.line, error.column));
+        assert_eq!("-", error.text);
     }
 
     #[test]
     fn test_count() {
-        let input = read_file("src/day11/test_input.txt");
+        let input = Day11::read_file("src/day11/test_input.txt").unwrap();
 
         let result = count_flashes(input);
 
@@ -117,7 +170,7 @@ mod tests {
 
     #[test]
     fn test_find_synchronization_step() {
-        let input = read_file("src/day11/test_input.txt");
+        let input = Day11::read_file("src/day11/test_input.txt").unwrap();
 
         let result = find_synchronization_step(input);
 
@@ -125,10 +178,12 @@ mod tests {
     }
 
     #[test]
-    fn result() {
-        println!("{}", get_answer_1());
-        assert_eq!(1617, get_answer_1());
-        println!("{}", get_answer_2());
-        assert_eq!(258, get_answer_2());
+    fn test_render_steps() {
+        let input = Day11::read_file("src/day11/test_input.txt").unwrap();
+
+        let frames = render_steps(input, 2);
+
+        assert_eq!(3, frames.len());
+        assert_golden("src/day11/test_step_2.ppm", &to_ppm(&frames[2]));
     }
 }
@@ -1,50 +1,70 @@
 use std::collections::HashMap;
 use std::fmt::{Debug, Formatter};
-use std::fs::read_to_string;
 use std::ops::Deref;
 
-pub fn get_answer_1() -> usize {
-    count_ways(read_file("src/day12/input.txt"))
+use crate::input::{default_input_path, InputError};
+use crate::parse::{input_lines, InputLine, ParseError};
+use crate::solution::{Answer, Solution};
+
+const DAY: u8 = 12;
+
+pub fn get_answer_1() -> Result<usize, InputError> {
+    Ok(count_ways(Day12::read_file(default_input_path(DAY))?))
 }
 
-pub fn get_answer_2() -> usize {
-    count_ways_2(read_file("src/day12/input.txt"))
+pub fn get_answer_2() -> Result<usize, InputError> {
+    Ok(count_ways_2(Day12::read_file(default_input_path(DAY))?))
 }
 
-fn read_file(path: &str) -> CaveGraph {
-    let file_content = read_to_string(path).expect("hoppla");
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = DAY;
+
+    type Input = CaveGraph;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: Self::Input) -> Answer {
+        count_ways(input).into()
+    }
+
+    fn part2(input: Self::Input) -> Answer {
+        count_ways_2(input).into()
+    }
+}
 
-    let edges = file_content
-        .lines()
-        .map(String::from)
-        .map(Edge::from)
-        .collect();
+pub fn parse(input: &str) -> Result<CaveGraph, ParseError> {
+    let edges = input_lines(DAY, input)
+        .map(Edge::parse)
+        .collect::<Result<Vec<Edge>, ParseError>>()?;
 
-    CaveGraph { edges }
+    Ok(CaveGraph { edges })
 }
 
-#[derive(Debug)]
-struct CaveGraph {
+#[derive(Debug, Clone)]
+pub struct CaveGraph {
     edges: Vec<Edge>,
 }
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Eq, PartialEq, Clone)]
 struct Edge {
     node1: Box<Node>,
     node2: Box<Node>,
 }
 
-impl From<String> for Edge {
-    fn from(str: String) -> Self {
-        let parts: Vec<&str> = str.split("-").collect();
-        Edge {
-            node1: Box::from(Node::from(parts[0])),
-            node2: Box::from(Node::from(parts[1])),
-        }
+impl Edge {
+    fn parse(line: InputLine) -> Result<Edge, ParseError> {
+        let (node1, node2) = line.split_once("-")?;
+
+        Ok(Edge {
+            node1: Box::from(parse_node(&line, node1)?),
+            node2: Box::from(parse_node(&line, node2)?),
+        })
     }
-}
 
-impl Edge {
     fn get_target_from(&self, node: &Node) -> Option<&Node> {
         match node {
             Node::End => None,
@@ -68,7 +88,7 @@ impl Debug for Node {
         match self {
             Node::Start => write!(f, "start"),
             Node::End => write!(f, "end"),
-            Node::Small(str) | Node::Large(str) => write!(f, "{}", str.to_string()),
+            Node::Small(str) | Node::Large(str) => write!(f, "{}", str),
         }
     }
 }
@@ -84,6 +104,13 @@ impl From<&str> for Node {
     }
 }
 
+fn parse_node(line: &InputLine, name: &str) -> Result<Node, ParseError> {
+    match !name.is_empty() && name.chars().all(|c| c.is_ascii_alphabetic()) {
+        true => Ok(Node::from(name)),
+        false => Err(line.error(name, "expected a cave name")),
+    }
+}
+
 fn count_ways(graph: CaveGraph) -> usize {
     count_ways_with_filter(&graph, Box::from(allow_multiple_large_caves))
 }
@@ -104,7 +131,7 @@ where
     while !unfinished_ways.is_empty() {
         let next_ways = unfinished_ways
             .into_iter()
-            .flat_map(|way| add_next_possible_caves(&graph, way, cave_filter.clone()))
+            .flat_map(|way| add_next_possible_caves(graph, way, cave_filter.clone()))
             .collect::<Vec<Way>>();
 
         unfinished_ways = vec![];
@@ -119,11 +146,9 @@ where
     ways.len()
 }
 
-fn add_next_possible_caves(
-    graph: &CaveGraph,
-    way: Way,
-    cave_filter: Box<dyn Fn(&Way, &Node) -> bool>,
-) -> Vec<Way> {
+type CaveFilter = Box<dyn Fn(&Way, &Node) -> bool>;
+
+fn add_next_possible_caves(graph: &CaveGraph, way: Way, cave_filter: CaveFilter) -> Vec<Way> {
     let last_cave = way.caves.last().unwrap().clone();
     let way2 = way.clone();
 
@@ -131,7 +156,7 @@ fn add_next_possible_caves(
         .edges
         .iter()
         .filter_map(move |edge| edge.get_target_from(&last_cave))
-        .filter(move |next_cave| cave_filter(&way2, *next_cave))
+        .filter(move |next_cave| cave_filter(&way2, next_cave))
         .map(move |next_cave| way.clone().with(next_cave.clone()))
         .collect::<Vec<Way>>()
 }
@@ -183,7 +208,7 @@ mod tests {
 
     #[test]
     fn test_read_file() {
-        let graph = read_file("src/day12/test_input2.txt");
+        let graph = Day12::read_file("src/day12/test_input2.txt").unwrap();
 
         let expected_first = Edge {
             node1: Box::new(Node::Small("fs".to_string())),
@@ -197,12 +222,30 @@ mod tests {
         assert_eq!(Some(&expected_last), graph.edges.last());
     }
 
+    #[rstest]
+    #[case("start-A\nA-b\nA-end\nb", 4, 1, "b")]
+    #[case("start-A\nA-b\nA-\n", 3, 3, "")]
+    #[case("start-A\nA-b2", 2, 3, "b2")]
+    fn test_parse_error(
+        #[case] input: &str,
+        #[case] line: usize,
+        #[case] column: usize,
+        #[case] text: &str,
+    ) {
+        let error = parse(input).unwrap_err();
+
+        assert_eq!(
+            (12, line, column, text),
+            (error.day, error.line, error.column, error.text.as_str())
+        );
+    }
+
     #[rstest]
     #[case("src/day12/test_input.txt", 10)]
     #[case("src/day12/test_input1.txt", 19)]
     #[case("src/day12/test_input2.txt", 226)]
     fn test_count(#[case] file: &str, #[case] expected: usize) {
-        let input = read_file(file);
+        let input = Day12::read_file(file).unwrap();
 
         let result = count_ways(input);
 
@@ -214,18 +257,10 @@ mod tests {
     #[case("src/day12/test_input1.txt", 103)]
     #[case("src/day12/test_input2.txt", 3509)]
     fn test_count_2(#[case] file: &str, #[case] expected: usize) {
-        let input = read_file(file);
+        let input = Day12::read_file(file).unwrap();
 
         let result = count_ways_2(input);
 
         assert_eq!(expected, result);
     }
-
-    #[test]
-    fn result() {
-        println!("{}", get_answer_1());
-        assert_eq!(3708, get_answer_1());
-        println!("{}", get_answer_2());
-        assert_eq!(93858, get_answer_2());
-    }
 }
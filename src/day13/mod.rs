@@ -1,99 +1,171 @@
-use std::cmp::Ordering;
 use std::collections::HashSet;
-use std::fs::read_to_string;
 
-pub fn get_answer_1() -> usize {
-    fold_once(read_file("src/day13/input.txt"))
+use crate::animate::{draw_frame, Observer};
+use crate::geometry::Point2;
+use crate::grid::Grid;
+use crate::input::{default_input_path, InputError};
+use crate::parse::{input_lines, InputLine, ParseError};
+use crate::render::{Color, Frame};
+use crate::solution::{Answer, Solution};
+
+const DAY: u8 = 13;
+
+pub fn get_answer_1() -> Result<usize, InputError> {
+    Ok(fold_once(Day13::read_file(default_input_path(DAY))?))
+}
+
+pub fn get_answer_2() -> Result<String, InputError> {
+    Ok(fold_all(Day13::read_file(default_input_path(DAY))?))
 }
 
-pub fn get_answer_2() -> String {
-    fold_all(read_file("src/day13/input.txt"))
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = DAY;
+
+    type Input = (HashSet<Point2>, Vec<Fold>);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: Self::Input) -> Answer {
+        fold_once(input).into()
+    }
+
+    fn part2(input: Self::Input) -> Answer {
+        fold_all(input).into()
+    }
 }
 
-fn read_file(path: &str) -> (HashSet<Point>, Vec<Fold>) {
-    let file_content = read_to_string(path).expect("hoppla");
+pub fn parse(input: &str) -> Result<(HashSet<Point2>, Vec<Fold>), ParseError> {
+    let mut points = HashSet::new();
+    let mut folds = vec![];
+    let mut paper = HashSet::new();
+    let mut last_line = InputLine::new(DAY, 0, "");
 
-    file_content
-        .lines()
-        .fold((HashSet::new(), vec![]), |(mut points, mut folds), line| {
-            match line.starts_with("fold along") {
-                true => folds.push(Fold::from(line)),
-                false if line.len() > 0 => {
-                    points.insert(Point::from(line));
+    for line in input_lines(DAY, input) {
+        match line.text.starts_with("fold along") {
+            true if points.is_empty() => return Err(line.error(line.text, "expected dots")),
+            true => {
+                let fold = Fold::parse(line)?;
+                if !paper.iter().all(|point| fold.keeps(*point)) {
+                    let (_, n) = line.text.split_once('=').unwrap_or_default();
+                    return Err(line.error(n, "expected a fold that keeps the dots on the paper"));
                 }
-                _ => {}
+                paper = paper
+                    .into_iter()
+                    .map(|point| fold.transform(point))
+                    .collect();
+                folds.push(fold);
             }
-            (points, folds)
-        })
+            false if line.text.is_empty() => {}
+            false if !folds.is_empty() => return Err(line.error(line.text, "expected a fold")),
+            false => {
+                let point = parse_point(line)?;
+                points.insert(point);
+                paper.insert(point);
+            }
+        }
+        last_line = line;
+    }
+
+    match (points.is_empty(), folds.is_empty()) {
+        (false, false) => Ok((points, folds)),
+        (true, _) => Err(last_line.error("", "expected dots")),
+        (false, true) => Err(last_line.error("", "expected folds after the dots")),
+    }
 }
 
-#[derive(Debug, Eq, PartialEq, Hash)]
-struct Point(usize, usize);
+/// Dots lie on the paper, so unlike [`Point2::parse`] negative coordinates are rejected.
+fn parse_point(line: InputLine) -> Result<Point2, ParseError> {
+    let (x, y) = line.split_once(",")?;
+    let coordinate = |part| {
+        isize::try_from(line.parse::<usize>(part, "a number")?)
+            .map_err(|_| line.error(part, "expected a smaller number"))
+    };
 
-impl From<&str> for Point {
-    fn from(str: &str) -> Self {
-        let parts = str.split(",").collect::<Vec<&str>>();
-        Point(
-            parts[0].parse::<usize>().unwrap(),
-            parts[1].parse::<usize>().unwrap(),
-        )
-    }
+    Ok(Point2::new(coordinate(x)?, coordinate(y)?))
 }
 
-#[derive(Debug, Eq, PartialEq)]
-enum Fold {
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum Fold {
     X(usize),
     Y(usize),
 }
 
-impl From<&str> for Fold {
-    fn from(str: &str) -> Self {
-        let parts = str.split("=").collect::<Vec<&str>>();
-        let n = parts[1].parse::<usize>().unwrap();
-        match parts[0].contains("x") {
-            true => Fold::X(n),
-            false => Fold::Y(n),
+impl Fold {
+    pub(crate) fn parse(line: InputLine) -> Result<Fold, ParseError> {
+        let (axis, n) = line.split_once("=")?;
+        let n = line.parse::<usize>(n, "a number")?;
+
+        match axis {
+            "fold along x" => Ok(Fold::X(n)),
+            "fold along y" => Ok(Fold::Y(n)),
+            _ => Err(line.error(axis, "expected fold along x or y")),
         }
     }
-}
 
-impl Fold {
-    fn transform(&self, point: Point) -> Point {
-        match self {
-            Fold::X(n) => match point.0.cmp(n) {
-                Ordering::Less => point,
-                _ => Point(2 * n - point.0, point.1),
-            },
-            Fold::Y(n) => match point.1.cmp(n) {
-                Ordering::Less => point,
-                _ => Point(point.0, 2 * n - point.1),
-            },
+    /// Whether folding leaves `point` on the paper, rather than reflecting it past 0.
+    pub(crate) fn keeps(&self, point: Point2) -> bool {
+        let edge = |n: usize| isize::try_from(n).ok().and_then(|n| n.checked_mul(2));
+
+        match *self {
+            Fold::X(n) => edge(n).is_some_and(|edge| point.x <= edge),
+            Fold::Y(n) => edge(n).is_some_and(|edge| point.y <= edge),
+        }
+    }
+
+    pub(crate) fn transform(&self, point: Point2) -> Point2 {
+        match *self {
+            Fold::X(n) if point.x >= n as isize => point.reflect_x(n as isize),
+            Fold::Y(n) if point.y >= n as isize => point.reflect_y(n as isize),
+            _ => point,
         }
     }
 }
 
-fn fold_once((points, folds): (HashSet<Point>, Vec<Fold>)) -> usize {
-    points
+fn fold_once(input: (HashSet<Point2>, Vec<Fold>)) -> usize {
+    fold_once_observed(input, &mut ())
+}
+
+pub fn fold_once_observed(
+    (points, folds): (HashSet<Point2>, Vec<Fold>),
+    observer: &mut impl Observer<HashSet<Point2>>,
+) -> usize {
+    observer.observe(0, &points);
+    let points = points
         .into_iter()
         .map(|point| folds[0].transform(point))
-        .collect::<HashSet<Point>>()
-        .len()
+        .collect::<HashSet<Point2>>();
+    observer.observe(1, &points);
+
+    points.len()
 }
 
-fn fold_all((mut points, folds): (HashSet<Point>, Vec<Fold>)) -> String {
-    for fold in folds {
+fn fold_all(input: (HashSet<Point2>, Vec<Fold>)) -> String {
+    fold_all_observed(input, &mut ())
+}
+
+pub fn fold_all_observed(
+    (mut points, folds): (HashSet<Point2>, Vec<Fold>),
+    observer: &mut impl Observer<HashSet<Point2>>,
+) -> String {
+    observer.observe(0, &points);
+    for (step, fold) in folds.iter().enumerate() {
         points = points
             .into_iter()
             .map(|point| fold.transform(point))
-            .collect::<HashSet<Point>>();
+            .collect::<HashSet<Point2>>();
+        observer.observe(step + 1, &points);
     }
 
-    let size_x = points.iter().map(|point| point.0).max().unwrap() + 1;
-    let size_y = points.iter().map(|point| point.1).max().unwrap() + 1;
+    let size_x = points.iter().map(|point| point.x).max().unwrap() as usize + 1;
+    let size_y = points.iter().map(|point| point.y).max().unwrap() as usize + 1;
 
     let mut result = vec![vec![" "; size_x]; size_y];
     for point in points {
-        result[point.1][point.0] = "*";
+        result[point.y as usize][point.x as usize] = "*";
     }
 
     result
@@ -103,21 +175,82 @@ fn fold_all((mut points, folds): (HashSet<Point>, Vec<Fold>)) -> String {
         .join("\n")
 }
 
+/// The paper before the first and after every fold.
+pub fn render_folds(input: (HashSet<Point2>, Vec<Fold>)) -> Vec<Frame> {
+    let mut frames = vec![];
+    fold_all_observed(input, &mut |_, points: &HashSet<Point2>| {
+        frames.push(render_paper(points))
+    });
+    frames
+}
+
+pub fn draw_paper(points: &HashSet<Point2>) -> String {
+    draw_frame(&render_paper(points))
+}
+
+fn render_paper(points: &HashSet<Point2>) -> Frame {
+    let width = points.iter().map(|point| point.x + 1).max().unwrap_or(0);
+    let height = points.iter().map(|point| point.y + 1).max().unwrap_or(0);
+
+    Grid::from_fn(width as usize, height as usize, |point| {
+        match points.contains(&point) {
+            true => Color::WHITE,
+            false => Color::NAVY,
+        }
+    })
+}
+
 #[cfg(test)]
 mod tests {
+    use rstest::*;
+
     use crate::day13::*;
+    use crate::render::tests::assert_golden;
+    use crate::render::{to_ppm, to_svg};
 
     #[test]
     fn test_read_file() {
-        let (points, folds) = read_file("src/day13/input.txt");
+        let (points, folds) = Day13::read_file("src/day13/input.txt").unwrap();
 
-        assert_eq!(Some(&Point(802, 891)), points.get(&Point(802, 891)));
+        assert!(points.contains(&Point2::new(802, 891)));
         assert_eq!(Some(&Fold::X(655)), folds.first());
     }
 
+    #[rstest]
+    #[case("6,10\n0,14\n9\n", 3, 1, "9")]
+    #[case("6,10\n0,-14\n", 2, 3, "-14")]
+    #[case("6,10\n\nfold along y=\n", 3, 14, "")]
+    #[case("6,10\n\nfold along z=7\n", 3, 1, "fold along z")]
+    #[case("6,10\n0,14\n", 2, 1, "")]
+    #[case("fold along y=7\n", 1, 1, "fold along y=7")]
+    #[case("", 1, 1, "")]
+    #[case("6,10\n\nfold along y=7\n0,14\n", 4, 1, "0,14")]
+    #[case("10,0\n\nfold along x=2\n", 3, 14, "2")]
+    #[case("9,0\n\nfold along x=6\nfold along x=1\n", 4, 14, "1")]
+    #[case(
+        "6,10\n\nfold along y=4611686018427387904\n",
+        3,
+        14,
+        "4611686018427387904"
+    )]
+    #[case("6,9223372036854775808\n", 1, 3, "9223372036854775808")]
+    fn test_parse_error(
+        #[case] input: &str,
+        #[case] line: usize,
+        #[case] column: usize,
+        #[case] text: &str,
+    ) {
+        let error = parse(input).unwrap_err();
+
+        assert_eq!(
+            (13, line, column, text),
+            (error.day, error.line, error.column, error.text.as_str())
+        );
+    }
+
     #[test]
     fn test_count() {
-        let input = read_file("src/day13/test_input.txt");
+        let input = Day13::read_file("src/day13/test_input.txt").unwrap();
 
         let result = fold_once(input);
 
@@ -125,9 +258,13 @@ mod tests {
     }
 
     #[test]
-    fn result() {
-        println!("{}", get_answer_1());
-        assert_eq!(693, get_answer_1());
-        println!("{}", get_answer_2());
+    fn test_render_folds() {
+        let input = Day13::read_file("src/day13/test_input.txt").unwrap();
+
+        let frames = render_folds(input);
+
+        assert_eq!(3, frames.len());
+        assert_golden("src/day13/test_fold_1.svg", &to_svg(&frames[1]));
+        assert_golden("src/day13/test_fold_2.ppm", &to_ppm(&frames[2]));
     }
 }
@@ -0,0 +1,254 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::animate::Observer;
+use crate::input::{default_input_path, InputError};
+use crate::parse::{input_lines, InputLine, ParseError};
+use crate::solution::{Answer, Solution};
+
+const DAY: u8 = 14;
+
+/// Pairs at most double with every step, so for templates up to this length the counts of the
+/// 40 steps of the second part fit into 64 bits.
+pub const MAX_TEMPLATE: usize = 1 << 24;
+
+pub fn get_answer_1() -> Result<usize, InputError> {
+    Ok(polymerize(Day14::read_file(default_input_path(DAY))?, 10))
+}
+
+pub fn get_answer_2() -> Result<usize, InputError> {
+    Ok(polymerize(Day14::read_file(default_input_path(DAY))?, 40))
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = DAY;
+
+    type Input = Polymer;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: Self::Input) -> Answer {
+        polymerize(input, 10).into()
+    }
+
+    fn part2(input: Self::Input) -> Answer {
+        polymerize(input, 40).into()
+    }
+}
+
+pub type Pair = (char, char);
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Polymer {
+    pub template: Vec<char>,
+    pub rules: HashMap<Pair, char>,
+}
+
+pub fn parse(input: &str) -> Result<Polymer, ParseError> {
+    let mut lines = input_lines(DAY, input).filter(|line| !line.text.is_empty());
+    let template = match lines.next() {
+        Some(line) => parse_elements(&line, line.text)?,
+        None => return Err(InputLine::new(DAY, 0, "").error("", "expected a template")),
+    };
+    if template.len() > MAX_TEMPLATE {
+        let line = input_lines(DAY, input)
+            .find(|line| !line.text.is_empty())
+            .unwrap();
+        return Err(line.error(
+            line.text,
+            format!("expected at most {} elements", MAX_TEMPLATE),
+        ));
+    }
+
+    let rules = lines
+        .map(|line| {
+            let (pair, element) = line.split_once(" -> ")?;
+            match (
+                parse_elements(&line, pair)?.as_slice(),
+                parse_elements(&line, element)?.as_slice(),
+            ) {
+                ([first, second], [element]) => Ok(((*first, *second), *element)),
+                ([_, _], _) => Err(line.error(element, "expected one element")),
+                _ => Err(line.error(pair, "expected a pair of elements")),
+            }
+        })
+        .collect::<Result<HashMap<Pair, char>, ParseError>>()?;
+
+    Ok(Polymer { template, rules })
+}
+
+fn parse_elements(line: &InputLine, elements: &str) -> Result<Vec<char>, ParseError> {
+    match elements.chars().all(|char| char.is_ascii_uppercase()) && !elements.is_empty() {
+        true => Ok(elements.chars().collect()),
+        false => Err(line.error(elements, "expected elements A to Z")),
+    }
+}
+
+/// The most common minus the least common element after `steps` insertions.
+fn polymerize(polymer: Polymer, steps: usize) -> usize {
+    polymerize_observed(polymer, steps, &mut ()).expect("parse checks that 40 steps fit")
+}
+
+/// Like `polymerize`, showing the number of every element before the first and after every step.
+/// Only pairs are counted, so the polymer itself is never built. `None` if the counts overflow,
+/// which takes more than 40 steps for the templates that `parse` accepts.
+pub fn polymerize_observed(
+    polymer: Polymer,
+    steps: usize,
+    observer: &mut impl Observer<BTreeMap<char, usize>>,
+) -> Option<usize> {
+    let last = *polymer.template.last().unwrap();
+    let mut pairs = count_pairs(&polymer.template);
+    observer.observe(0, &count_elements(&pairs, last)?);
+
+    for step in 0..steps {
+        pairs = insert(&pairs, &polymer.rules)?;
+        observer.observe(step + 1, &count_elements(&pairs, last)?);
+    }
+
+    let elements = count_elements(&pairs, last)?;
+    Some(elements.values().max().unwrap() - elements.values().min().unwrap())
+}
+
+/// The element frequencies before the first and after every one of `steps` steps, ending early
+/// at the first step whose counts overflow.
+pub fn frequencies(polymer: Polymer, steps: usize) -> Vec<BTreeMap<char, usize>> {
+    let mut snapshots = vec![];
+    polymerize_observed(
+        polymer,
+        steps,
+        &mut |_, elements: &BTreeMap<char, usize>| snapshots.push(elements.clone()),
+    );
+    snapshots
+}
+
+fn count_pairs(template: &[char]) -> HashMap<Pair, usize> {
+    template
+        .windows(2)
+        .fold(HashMap::new(), |mut pairs, window| {
+            *pairs.entry((window[0], window[1])).or_insert(0) += 1;
+            pairs
+        })
+}
+
+/// Every pair with a rule becomes two pairs around the inserted element, all others stay.
+/// `None` if a count overflows.
+fn insert(
+    pairs: &HashMap<Pair, usize>,
+    rules: &HashMap<Pair, char>,
+) -> Option<HashMap<Pair, usize>> {
+    let mut next_pairs = HashMap::new();
+    let mut add = |pair: Pair, count: usize| {
+        let total = next_pairs.entry(pair).or_insert(0_usize);
+        *total = total.checked_add(count)?;
+        Some(())
+    };
+    for (&(first, second), &count) in pairs {
+        match rules.get(&(first, second)) {
+            Some(&element) => {
+                add((first, element), count)?;
+                add((element, second), count)?;
+            }
+            None => add((first, second), count)?,
+        }
+    }
+    Some(next_pairs)
+}
+
+/// Pairs overlap, so every element is counted as the first of its pair, except the last one of
+/// the polymer, which never changes. `None` if a count overflows.
+fn count_elements(pairs: &HashMap<Pair, usize>, last: char) -> Option<BTreeMap<char, usize>> {
+    pairs.iter().try_fold(
+        BTreeMap::from([(last, 1)]),
+        |mut elements, ((first, _), count)| {
+            let total = elements.entry(*first).or_insert(0_usize);
+            *total = total.checked_add(*count)?;
+            Some(elements)
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use crate::day14::*;
+
+    #[test]
+    fn test_read_file() {
+        let polymer = Day14::read_file("src/day14/test_input.txt").unwrap();
+
+        assert_eq!(vec!['N', 'N', 'C', 'B'], polymer.template);
+        assert_eq!(16, polymer.rules.len());
+        assert_eq!(Some(&'B'), polymer.rules.get(&('C', 'H')));
+    }
+
+    #[rstest]
+    #[case("NNCB\n\nCH -> B\nHH - N\n", 4, 1, "HH - N")]
+    #[case("NNCB\n\nCH -> B\nHHH -> N\n", 4, 1, "HHH")]
+    #[case("NNCB\n\nCH -> b\n", 3, 7, "b")]
+    #[case("\n", 1, 1, "")]
+    fn test_parse_error(
+        #[case] input: &str,
+        #[case] line: usize,
+        #[case] column: usize,
+        #[case] text: &str,
+    ) {
+        let error = parse(input).unwrap_err();
+
+        assert_eq!((14, line, column), (error.day, error.line, error.column));
+        assert_eq!(text, error.text);
+    }
+
+    #[rstest]
+    #[case(10, 1588)]
+    #[case(40, 2188189693529)]
+    fn test_polymerize(#[case] steps: usize, #[case] expected: usize) {
+        let polymer = Day14::read_file("src/day14/test_input.txt").unwrap();
+
+        assert_eq!(expected, polymerize(polymer, steps));
+    }
+
+    #[test]
+    fn test_parse_long_template() {
+        let input = format!("{}\n\nAB -> A\n", "A".repeat(MAX_TEMPLATE + 1));
+
+        let error = parse(&input).unwrap_err();
+
+        assert_eq!((14, 1, 1), (error.day, error.line, error.column));
+        assert!(parse(&input[1..]).is_ok());
+    }
+
+    #[test]
+    fn test_polymerize_overflow() {
+        let polymer = parse("AB\n\nAA -> A\nAB -> A\n").unwrap();
+
+        assert_eq!(
+            Some((1 << 63) - 1),
+            polymerize_observed(polymer.clone(), 63, &mut ())
+        );
+        assert_eq!(None, polymerize_observed(polymer.clone(), 64, &mut ()));
+        assert_eq!(64, frequencies(polymer, 100).len());
+    }
+
+    #[test]
+    fn test_frequencies() {
+        let polymer = Day14::read_file("src/day14/test_input.txt").unwrap();
+
+        let snapshots = frequencies(polymer, 10);
+
+        assert_eq!(11, snapshots.len());
+        assert_eq!(BTreeMap::from([('B', 1), ('C', 1), ('N', 2)]), snapshots[0]);
+        // NCNBCHB
+        assert_eq!(
+            BTreeMap::from([('B', 2), ('C', 2), ('H', 1), ('N', 2)]),
+            snapshots[1]
+        );
+        assert_eq!(3073, snapshots[10].values().sum::<usize>());
+        assert_eq!(Some(&1749), snapshots[10].get(&'B'));
+        assert_eq!(Some(&161), snapshots[10].get(&'H'));
+    }
+}
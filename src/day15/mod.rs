@@ -0,0 +1,239 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use crate::geometry::Point2;
+use crate::grid::Grid;
+use crate::input::{default_input_path, InputError};
+use crate::parse::{InputLine, ParseError};
+use crate::render::{Color, Frame};
+use crate::solution::{Answer, Solution};
+
+const DAY: u8 = 15;
+
+pub fn get_answer_1() -> Result<usize, InputError> {
+    Ok(lowest_total_risk(Day15::read_file(default_input_path(
+        DAY,
+    ))?))
+}
+
+pub fn get_answer_2() -> Result<usize, InputError> {
+    Ok(lowest_total_risk_of_full_map(Day15::read_file(
+        default_input_path(DAY),
+    )?))
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = DAY;
+
+    type Input = Grid<usize>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: Self::Input) -> Answer {
+        lowest_total_risk(input).into()
+    }
+
+    fn part2(input: Self::Input) -> Answer {
+        lowest_total_risk_of_full_map(input).into()
+    }
+}
+
+pub fn parse(input: &str) -> Result<Grid<usize>, ParseError> {
+    let grid = Grid::parse(DAY, input, "a risk level from 1 to 9", |char| {
+        char.to_digit(10)
+            .filter(|digit| *digit > 0)
+            .map(|digit| digit as usize)
+    })?;
+
+    match grid.width() > 0 && grid.height() > 0 {
+        true => Ok(grid),
+        false => Err(InputLine::new(DAY, 0, "").error("", "expected a risk map")),
+    }
+}
+
+fn lowest_total_risk(input: Grid<usize>) -> usize {
+    find_route(&input).risk
+}
+
+fn lowest_total_risk_of_full_map(input: Grid<usize>) -> usize {
+    find_route(&expand(&input, 5)).risk
+}
+
+/// The safest way from the top left to the bottom right corner. The risk of the start is not
+/// counted, because it is never entered.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Route {
+    pub risk: usize,
+    pub path: Vec<Point2>,
+}
+
+/// Dijkstra with a binary heap, so the expanded 500×500 map takes a fraction of a second.
+pub fn find_route(input: &Grid<usize>) -> Route {
+    let start = Point2::ORIGIN;
+    let end = Point2::new(input.width() as isize - 1, input.height() as isize - 1);
+    let mut risks = input.map(|_| usize::MAX);
+    let mut previous = input.map(|_| None);
+    let mut queue = BinaryHeap::from([Reverse((0, start))]);
+    risks[start] = 0;
+
+    while let Some(Reverse((risk, position))) = queue.pop() {
+        if position == end {
+            break;
+        }
+        if risk > risks[position] {
+            continue;
+        }
+        for neighbour in input.neighbours_4(position) {
+            let neighbour_risk = risk + input[neighbour];
+            if neighbour_risk < risks[neighbour] {
+                risks[neighbour] = neighbour_risk;
+                previous[neighbour] = Some(position);
+                queue.push(Reverse((neighbour_risk, neighbour)));
+            }
+        }
+    }
+
+    let mut path = vec![end];
+    while let Some(position) = previous[*path.last().unwrap()] {
+        path.push(position);
+    }
+    path.reverse();
+
+    Route {
+        risk: risks[end],
+        path,
+    }
+}
+
+/// Tiles the map `times` times in both directions. Every tile to the right or below adds one to
+/// the risks, wrapping from 9 back to 1.
+pub fn expand(input: &Grid<usize>, times: usize) -> Grid<usize> {
+    let (width, height) = (input.width(), input.height());
+
+    Grid::from_fn(width * times, height * times, |Point2 { x, y }| {
+        let (x, y) = (x as usize, y as usize);
+        let risk = input[Point2::new((x % width) as isize, (y % height) as isize)];
+        (risk - 1 + x / width + y / height) % 9 + 1
+    })
+}
+
+/// Risks from dark to gold, the safest route in white.
+pub fn render_route(input: &Grid<usize>) -> Frame {
+    let mut frame = input.map(|risk| Color::BLACK.mix(Color::GOLD, *risk as f64 / 9.0));
+
+    for position in find_route(input).path {
+        frame[position] = Color::WHITE;
+    }
+
+    frame
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use crate::day15::*;
+    use crate::render::tests::assert_golden;
+    use crate::render::to_ppm;
+
+    #[test]
+    fn test_read_file() {
+        let input = Day15::read_file("src/day15/test_input.txt").unwrap();
+
+        assert_eq!((10, 10), (input.width(), input.height()));
+        assert_eq!(1, input[Point2::new(0, 0)]);
+        assert_eq!(1, input[Point2::new(9, 9)]);
+    }
+
+    #[rstest]
+    #[case(
+        "1163751742\n13813x3672\n",
+        2,
+        6,
+        "x",
+        "expected a risk level from 1 to 9"
+    )]
+    #[case(
+        "1163751742\n1381303672\n",
+        2,
+        6,
+        "0",
+        "expected a risk level from 1 to 9"
+    )]
+    #[case("", 1, 1, "", "expected a risk map")]
+    fn test_parse_error(
+        #[case] input: &str,
+        #[case] line: usize,
+        #[case] column: usize,
+        #[case] text: &str,
+        #[case] reason: &str,
+    ) {
+        let error = parse(input).unwrap_err();
+
+        assert_eq!((15, line, column), (error.day, error.line, error.column));
+        assert_eq!((text, reason), (error.text.as_str(), error.reason.as_str()));
+    }
+
+    #[rstest]
+    #[case(1, 40)]
+    #[case(5, 315)]
+    fn test_find_route(#[case] times: usize, #[case] expected: usize) {
+        let input = expand(
+            &Day15::read_file("src/day15/test_input.txt").unwrap(),
+            times,
+        );
+
+        let route = find_route(&input);
+
+        assert_eq!(expected, route.risk);
+        assert_eq!(Some(&Point2::ORIGIN), route.path.first());
+        assert_eq!(
+            Some(&Point2::new(
+                10 * times as isize - 1,
+                10 * times as isize - 1
+            )),
+            route.path.last()
+        );
+        assert!(route
+            .path
+            .windows(2)
+            .all(|step| step[0].manhattan_distance(step[1]) == 1));
+        assert_eq!(
+            expected,
+            route.path[1..]
+                .iter()
+                .map(|position| input[*position])
+                .sum::<usize>()
+        );
+    }
+
+    #[test]
+    fn test_expand_wraps_around() {
+        let input = Grid::from_rows(vec![vec![8]]).unwrap();
+
+        let expanded = expand(&input, 3);
+
+        assert_eq!("891\n912\n123", expanded.to_string());
+    }
+
+    #[test]
+    fn test_single_cell() {
+        let route = find_route(&Grid::from_rows(vec![vec![5]]).unwrap());
+
+        assert_eq!(0, route.risk);
+        assert_eq!(vec![Point2::ORIGIN], route.path);
+    }
+
+    #[test]
+    fn test_render_route() {
+        let input = Day15::read_file("src/day15/test_input.txt").unwrap();
+
+        let frame = render_route(&input);
+
+        assert_golden("src/day15/test_route.ppm", &to_ppm(&frame));
+    }
+}
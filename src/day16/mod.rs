@@ -0,0 +1,476 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use crate::bits::{BitError, BitReader};
+use crate::input::{default_input_path, InputError};
+use crate::parse::{input_lines, InputLine, ParseError};
+use crate::solution::{Answer, Solution};
+
+const DAY: u8 = 16;
+
+pub fn get_answer_1() -> Result<usize, InputError> {
+    Ok(version_sum(&Day16::read_file(default_input_path(DAY))?))
+}
+
+pub fn get_answer_2() -> Result<usize, InputError> {
+    Ok(value(&Day16::read_file(default_input_path(DAY))?))
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u8 = DAY;
+
+    type Input = Packet;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: Self::Input) -> Answer {
+        version_sum(&input).into()
+    }
+
+    fn part2(input: Self::Input) -> Answer {
+        value(&input).into()
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Packet {
+    Literal {
+        version: u8,
+        value: u64,
+    },
+    Operator {
+        version: u8,
+        operator: Operator,
+        packets: Vec<Packet>,
+    },
+}
+
+/// What an operator packet computes from the values of its sub-packets. The comparisons take
+/// exactly two and give 1 if they hold and 0 otherwise.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Operator {
+    Sum,
+    Product,
+    Minimum,
+    Maximum,
+    GreaterThan,
+    LessThan,
+    EqualTo,
+}
+
+/// The type ID of literal packets, all others are operators.
+pub const LITERAL: u64 = 4;
+
+/// How deep packets may nest, so that a hostile transmission cannot overflow the stack.
+pub const MAX_DEPTH: usize = 256;
+
+impl Operator {
+    pub fn from_type_id(type_id: u64) -> Option<Operator> {
+        match type_id {
+            0 => Some(Operator::Sum),
+            1 => Some(Operator::Product),
+            2 => Some(Operator::Minimum),
+            3 => Some(Operator::Maximum),
+            5 => Some(Operator::GreaterThan),
+            6 => Some(Operator::LessThan),
+            7 => Some(Operator::EqualTo),
+            _ => None,
+        }
+    }
+
+    pub fn type_id(&self) -> u64 {
+        match self {
+            Operator::Sum => 0,
+            Operator::Product => 1,
+            Operator::Minimum => 2,
+            Operator::Maximum => 3,
+            Operator::GreaterThan => 5,
+            Operator::LessThan => 6,
+            Operator::EqualTo => 7,
+        }
+    }
+
+    pub fn is_comparison(&self) -> bool {
+        matches!(
+            self,
+            Operator::GreaterThan | Operator::LessThan | Operator::EqualTo
+        )
+    }
+}
+
+/// A malformed transmission. `offset` is the bit where the offending part starts.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct DecodeError {
+    pub offset: usize,
+    pub reason: String,
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "bit {}: {}", self.offset, self.reason)
+    }
+}
+
+impl Error for DecodeError {}
+
+impl From<BitError> for DecodeError {
+    fn from(error: BitError) -> Self {
+        DecodeError {
+            offset: error.offset,
+            reason: format!(
+                "expected {} more bits, but only {} are left",
+                error.wanted, error.available
+            ),
+        }
+    }
+}
+
+pub fn parse(input: &str) -> Result<Packet, ParseError> {
+    let mut lines = input_lines(DAY, input).filter(|line| !line.text.is_empty());
+    let line = match lines.next() {
+        Some(line) => line,
+        None => return Err(InputLine::new(DAY, 0, "").error("", "expected a transmission")),
+    };
+    if let Some(line) = lines.next() {
+        return Err(line.error(line.text, "expected a single line"));
+    }
+
+    let digits = line
+        .text
+        .char_indices()
+        .map(|(index, char)| {
+            char.to_digit(16).ok_or_else(|| {
+                line.error(
+                    &line.text[index..index + char.len_utf8()],
+                    "expected a hexadecimal digit",
+                )
+            })
+        })
+        .collect::<Result<Vec<u32>, ParseError>>()?;
+
+    let packet = decode(&mut BitReader::from_digits(&digits, 4)).map_err(|error| {
+        let index = (error.offset / 4).min(line.text.len() - 1);
+        line.error(
+            &line.text[index..index + 1],
+            format!("{} at bit {}", error.reason, error.offset),
+        )
+    })?;
+
+    // The value is the answer of part 2, so it has to fit into one
+    match evaluate(&packet) {
+        Some(value) if value <= i64::MAX as u64 => Ok(packet),
+        _ => Err(line.error(
+            &line.text[..1],
+            "expected the value of the transmission to fit in 63 bits",
+        )),
+    }
+}
+
+/// Decodes the outermost packet. Anything after it must be zero padding.
+pub fn decode(reader: &mut BitReader) -> Result<Packet, DecodeError> {
+    let packet = decode_packet(reader, 1)?;
+
+    match reader.is_padding() {
+        true => Ok(packet),
+        false => Err(DecodeError {
+            offset: reader.position(),
+            reason: "expected only zeros after the packet".to_string(),
+        }),
+    }
+}
+
+/// `depth` is 1 for the outermost packet.
+fn decode_packet(reader: &mut BitReader, depth: usize) -> Result<Packet, DecodeError> {
+    let start = reader.position();
+    if depth > MAX_DEPTH {
+        return Err(DecodeError {
+            offset: start,
+            reason: format!("expected packets to nest at most {} deep", MAX_DEPTH),
+        });
+    }
+    let version = reader.read(3)? as u8;
+    let type_id = reader.read(3)?;
+    let operator = match Operator::from_type_id(type_id) {
+        Some(operator) => operator,
+        None => {
+            return Ok(Packet::Literal {
+                version,
+                value: decode_literal(reader)?,
+            })
+        }
+    };
+
+    let packets = match reader.read_bool()? {
+        false => {
+            let length = reader.read(15)? as usize;
+            let end = reader.position() + length;
+            let mut packets = vec![];
+            while reader.position() < end {
+                packets.push(decode_packet(reader, depth + 1)?);
+            }
+            if reader.position() > end {
+                return Err(DecodeError {
+                    offset: end,
+                    reason: format!("the sub-packets overrun their length of {} bits", length),
+                });
+            }
+            packets
+        }
+        true => (0..reader.read(11)?)
+            .map(|_| decode_packet(reader, depth + 1))
+            .collect::<Result<Vec<Packet>, DecodeError>>()?,
+    };
+
+    let error = |reason: String| {
+        Err(DecodeError {
+            offset: start,
+            reason,
+        })
+    };
+    match (operator.is_comparison(), packets.len()) {
+        (_, 0) => error("expected at least one sub-packet".to_string()),
+        (true, count) if count != 2 => error(format!(
+            "expected two sub-packets to compare, found {}",
+            count
+        )),
+        _ => Ok(Packet::Operator {
+            version,
+            operator,
+            packets,
+        }),
+    }
+}
+
+/// Groups of four bits, each after a bit that tells whether another one follows.
+fn decode_literal(reader: &mut BitReader) -> Result<u64, DecodeError> {
+    let mut value = 0_u64;
+    loop {
+        let offset = reader.position();
+        let more = reader.read_bool()?;
+        let group = reader.read(4)?;
+        if value.leading_zeros() < 4 {
+            return Err(DecodeError {
+                offset,
+                reason: "the literal does not fit in 64 bits".to_string(),
+            });
+        }
+        value = value << 4 | group;
+        if !more {
+            return Ok(value);
+        }
+    }
+}
+
+pub fn version_sum(packet: &Packet) -> usize {
+    match packet {
+        Packet::Literal { version, .. } => *version as usize,
+        Packet::Operator {
+            version, packets, ..
+        } => *version as usize + packets.iter().map(version_sum).sum::<usize>(),
+    }
+}
+
+/// How many packets deep the innermost literal is.
+pub fn depth(packet: &Packet) -> usize {
+    match packet {
+        Packet::Literal { .. } => 1,
+        Packet::Operator { packets, .. } => 1 + packets.iter().map(depth).max().unwrap_or(0),
+    }
+}
+
+/// The value of the expression, `None` if it overflows 64 bits on the way.
+pub fn evaluate(packet: &Packet) -> Option<u64> {
+    let (operator, packets) = match packet {
+        Packet::Literal { value, .. } => return Some(*value),
+        Packet::Operator {
+            operator, packets, ..
+        } => (operator, packets),
+    };
+    let values = packets.iter().map(evaluate).collect::<Option<Vec<u64>>>()?;
+
+    match operator {
+        Operator::Sum => values
+            .iter()
+            .try_fold(0_u64, |sum, value| sum.checked_add(*value)),
+        Operator::Product => values
+            .iter()
+            .try_fold(1_u64, |product, value| product.checked_mul(*value)),
+        Operator::Minimum => values.iter().min().copied(),
+        Operator::Maximum => values.iter().max().copied(),
+        Operator::GreaterThan => Some((values[0] > values[1]) as u64),
+        Operator::LessThan => Some((values[0] < values[1]) as u64),
+        Operator::EqualTo => Some((values[0] == values[1]) as u64),
+    }
+}
+
+fn value(packet: &Packet) -> usize {
+    evaluate(packet).expect("parse checks that the value fits") as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use crate::day16::*;
+
+    fn literal(version: u8, value: u64) -> Packet {
+        Packet::Literal { version, value }
+    }
+
+    fn bits(text: &str) -> BitReader {
+        BitReader::new(text.chars().map(|char| char == '1').collect())
+    }
+
+    #[test]
+    fn test_read_file() {
+        let packet = Day16::read_file("src/day16/test_input.txt").unwrap();
+
+        assert!(matches!(
+            packet,
+            Packet::Operator {
+                version: 5,
+                operator: Operator::Sum,
+                ..
+            }
+        ));
+    }
+
+    #[rstest]
+    #[case("D2FE28", literal(6, 2021))]
+    #[case("38006F45291200", Packet::Operator {
+        version: 1,
+        operator: Operator::LessThan,
+        packets: vec![literal(6, 10), literal(2, 20)],
+    })]
+    #[case("EE00D40C823060", Packet::Operator {
+        version: 7,
+        operator: Operator::Maximum,
+        packets: vec![literal(2, 1), literal(4, 2), literal(1, 3)],
+    })]
+    fn test_parse(#[case] input: &str, #[case] expected: Packet) {
+        assert_eq!(Ok(expected), parse(input));
+    }
+
+    #[rstest]
+    #[case("D2FG28", 4, "G", "expected a hexadecimal digit")]
+    #[case("D2FE2", 5, "2", "expected 4 more bits, but only 3 are left at bit 17")]
+    #[case("D2FE29", 6, "9", "expected only zeros after the packet at bit 21")]
+    #[case(
+        "16004408",
+        1,
+        "1",
+        "expected two sub-packets to compare, found 1 at bit 0"
+    )]
+    #[case("", 1, "", "expected a transmission")]
+    // a literal of 2^63
+    #[case(
+        "1310842108421084210800",
+        1,
+        "1",
+        "expected the value of the transmission to fit in 63 bits"
+    )]
+    // the product of 2^62 and 2
+    #[case(
+        "060084A42108421084210842001040",
+        1,
+        "0",
+        "expected the value of the transmission to fit in 63 bits"
+    )]
+    fn test_parse_error(
+        #[case] input: &str,
+        #[case] column: usize,
+        #[case] text: &str,
+        #[case] reason: &str,
+    ) {
+        let error = parse(input).unwrap_err();
+
+        assert_eq!((16, 1, column), (error.day, error.line, error.column));
+        assert_eq!((text, reason), (error.text.as_str(), error.reason.as_str()));
+    }
+
+    #[rstest]
+    // an operator with an empty sub-packet length
+    #[case("0000000000000000000000", 0, "expected at least one sub-packet")]
+    // a literal of 17 groups of 1111
+    #[case(
+        &format!("000100{}01111", "11111".repeat(16)),
+        86,
+        "the literal does not fit in 64 bits"
+    )]
+    // 11 bits of sub-packets in 10
+    #[case(
+        "000000000000000000101000010000001",
+        32,
+        "the sub-packets overrun their length of 10 bits"
+    )]
+    fn test_decode_error(#[case] input: &str, #[case] offset: usize, #[case] reason: &str) {
+        let error = decode(&mut bits(input)).unwrap_err();
+
+        assert_eq!(
+            DecodeError {
+                offset,
+                reason: reason.to_string()
+            },
+            error
+        );
+    }
+
+    #[rstest]
+    #[case(MAX_DEPTH - 1, None)]
+    #[case(MAX_DEPTH, Some(MAX_DEPTH * 18))]
+    #[case(200_000, Some(MAX_DEPTH * 18))]
+    fn test_decode_depth(#[case] operators: usize, #[case] offset: Option<usize>) {
+        // sums of one sub-packet each around a literal 1
+        let input = format!("{}00010000001", "000000100000000001".repeat(operators));
+
+        let result = decode(&mut bits(&input));
+
+        match offset {
+            None => assert_eq!(Ok(operators + 1), result.map(|packet| depth(&packet))),
+            Some(offset) => assert_eq!(
+                Err(DecodeError {
+                    offset,
+                    reason: format!("expected packets to nest at most {} deep", MAX_DEPTH)
+                }),
+                result
+            ),
+        }
+    }
+
+    #[rstest]
+    #[case("8A004A801A8002F478", 16)]
+    #[case("620080001611562C8802118E34", 12)]
+    #[case("C0015000016115A2E0802F182340", 23)]
+    #[case("A0016C880162017C3686B18A3D4780", 31)]
+    fn test_version_sum(#[case] input: &str, #[case] expected: usize) {
+        assert_eq!(expected, version_sum(&parse(input).unwrap()));
+    }
+
+    #[rstest]
+    #[case("C200B40A82", 3)]
+    #[case("04005AC33890", 54)]
+    #[case("880086C3E88112", 7)]
+    #[case("CE00C43D881120", 9)]
+    #[case("D8005AC2A8F0", 1)]
+    #[case("F600BC2D8F", 0)]
+    #[case("9C005AC2F8F0", 0)]
+    #[case("9C0141080250320F1802104A08", 1)]
+    fn test_evaluate(#[case] input: &str, #[case] expected: u64) {
+        assert_eq!(Some(expected), evaluate(&parse(input).unwrap()));
+    }
+
+    #[test]
+    fn test_evaluate_overflow() {
+        let packet = Packet::Operator {
+            version: 0,
+            operator: Operator::Product,
+            packets: vec![literal(0, u64::MAX), literal(0, 2)],
+        };
+
+        assert_eq!(None, evaluate(&packet));
+    }
+}
@@ -0,0 +1,289 @@
+use std::ops::RangeInclusive;
+
+use crate::geometry::{Point2, Vec2};
+use crate::input::{default_input_path, InputError};
+use crate::parse::{input_lines, InputLine, ParseError};
+use crate::solution::{Answer, Solution};
+
+const DAY: u8 = 17;
+
+/// How far the target may reach from the probe, so that the apex `vy * (vy + 1) / 2` and the
+/// positions along the way stay well inside 64 bits.
+pub const MAX_DISTANCE: isize = 1 << 30;
+
+pub fn get_answer_1() -> Result<usize, InputError> {
+    Ok(highest_apex(Day17::read_file(default_input_path(DAY))?))
+}
+
+pub fn get_answer_2() -> Result<usize, InputError> {
+    Ok(count_hits(Day17::read_file(default_input_path(DAY))?))
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u8 = DAY;
+
+    type Input = Target;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: Self::Input) -> Answer {
+        highest_apex(input).into()
+    }
+
+    fn part2(input: Self::Input) -> Answer {
+        count_hits(input).into()
+    }
+}
+
+/// The target area. The probe starts at the origin, so it has to be to the right and below.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Target {
+    pub x: RangeInclusive<isize>,
+    pub y: RangeInclusive<isize>,
+}
+
+pub fn parse(input: &str) -> Result<Target, ParseError> {
+    let line = match input_lines(DAY, input).find(|line| !line.text.is_empty()) {
+        Some(line) => line,
+        None => return Err(InputLine::new(DAY, 0, "").error("", "expected a target area")),
+    };
+    let ranges = line
+        .text
+        .strip_prefix("target area: ")
+        .ok_or_else(|| line.error(line.text, "expected \"target area: \""))?;
+    let (x, y) = ranges
+        .split_once(", ")
+        .ok_or_else(|| line.error(ranges, "expected x=..., y=..."))?;
+
+    let target = Target {
+        x: parse_range(&line, x, "x=")?,
+        y: parse_range(&line, y, "y=")?,
+    };
+    if *target.x.start() <= 0 {
+        return Err(line.error(x, "expected x right of the probe"));
+    }
+    if *target.y.end() >= 0 {
+        return Err(line.error(y, "expected y below the probe"));
+    }
+    if *target.x.end() > MAX_DISTANCE {
+        return Err(line.error(x, format!("expected x up to {}", MAX_DISTANCE)));
+    }
+    if *target.y.start() < -MAX_DISTANCE {
+        return Err(line.error(y, format!("expected y down to {}", -MAX_DISTANCE)));
+    }
+
+    Ok(target)
+}
+
+fn parse_range(
+    line: &InputLine,
+    range: &str,
+    prefix: &str,
+) -> Result<RangeInclusive<isize>, ParseError> {
+    let (from, to) = range
+        .strip_prefix(prefix)
+        .and_then(|range| range.split_once(".."))
+        .ok_or_else(|| line.error(range, format!("expected {}from..to", prefix)))?;
+    let (from, to) = (
+        line.parse::<isize>(from, "a number")?,
+        line.parse::<isize>(to, "a number")?,
+    );
+
+    match from <= to {
+        true => Ok(from..=to),
+        false => Err(line.error(range, "expected from not to exceed to")),
+    }
+}
+
+impl Target {
+    pub fn contains(&self, position: Point2) -> bool {
+        self.x.contains(&position.x) && self.y.contains(&position.y)
+    }
+
+    /// Once the probe is below or right of the target, it can never come back.
+    fn is_passed(&self, position: Point2) -> bool {
+        position.y < *self.y.start() || position.x > *self.x.end()
+    }
+
+    pub fn trajectory(&self, velocity: Vec2) -> Trajectory {
+        Trajectory {
+            target: self.clone(),
+            position: Point2::ORIGIN,
+            velocity,
+            finished: false,
+        }
+    }
+
+    pub fn is_hit(&self, velocity: Vec2) -> bool {
+        self.trajectory(velocity)
+            .last()
+            .is_some_and(|position| self.contains(position))
+    }
+
+    /// All initial velocities that can possibly hit. Slower than the smallest `vx` with
+    /// `vx * (vx + 1) / 2 >= x.start`, the probe stops short of the target, faster than `x.end`
+    /// it overshoots in the first step. Faster down than `y.start`, it falls through in the first
+    /// step. Launched upwards with `vy`, it comes back to `y = 0` with `-vy - 1`, so faster up than
+    /// `-y.start - 1` falls through in the step after.
+    pub fn candidates(&self) -> impl Iterator<Item = Vec2> {
+        let x_start = *self.x.start() as f64;
+        let slowest = ((((8.0 * x_start + 1.0).sqrt() - 1.0) / 2.0).ceil()) as isize;
+        let (x_end, y_start) = (*self.x.end(), *self.y.start());
+
+        (slowest..=x_end).flat_map(move |x| (y_start..-y_start).map(move |y| Vec2::new(x, y)))
+    }
+
+    pub fn hits(&self) -> Vec<Vec2> {
+        self.candidates()
+            .filter(|velocity| self.is_hit(*velocity))
+            .collect()
+    }
+}
+
+/// The positions of a probe from the origin until it is in the target or the last one before it
+/// passes the target.
+#[derive(Debug)]
+pub struct Trajectory {
+    target: Target,
+    position: Point2,
+    velocity: Vec2,
+    finished: bool,
+}
+
+impl Iterator for Trajectory {
+    type Item = Point2;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished || self.target.is_passed(self.position) {
+            None
+        } else {
+            let next = self.position;
+            self.finished = self.target.contains(next);
+            self.position += self.velocity;
+            self.velocity = Vec2::new(
+                self.velocity.x - self.velocity.x.signum(),
+                self.velocity.y - 1,
+            );
+            Some(next)
+        }
+    }
+}
+
+/// The highest point of a trajectory with the initial vertical velocity `vy`.
+fn apex(vy: isize) -> usize {
+    match vy > 0 {
+        true => (vy * (vy + 1) / 2) as usize,
+        false => 0,
+    }
+}
+
+fn highest_apex(target: Target) -> usize {
+    target
+        .hits()
+        .into_iter()
+        .map(|velocity| apex(velocity.y))
+        .max()
+        .unwrap()
+}
+
+fn count_hits(target: Target) -> usize {
+    target.hits().len()
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use crate::day17::*;
+
+    #[test]
+    fn test_read_file() {
+        let target = Day17::read_file("src/day17/test_input.txt").unwrap();
+
+        assert_eq!(
+            Target {
+                x: 20..=30,
+                y: -10..=-5
+            },
+            target
+        );
+    }
+
+    #[rstest]
+    #[case("target area: x=20..30, y=-10..-5,", 31, "-5,")]
+    #[case("target area: x=20..30 y=-10..-5", 14, "x=20..30 y=-10..-5")]
+    #[case("target area: x=30..20, y=-10..-5", 14, "x=30..20")]
+    #[case("target area: x=-5..20, y=-10..-5", 14, "x=-5..20")]
+    #[case("target area: x=20..30, y=-10..5", 24, "y=-10..5")]
+    #[case("target: x=20..30, y=-10..-5", 1, "target: x=20..30, y=-10..-5")]
+    #[case(
+        "the target area: x=20..30, y=-10..-5",
+        1,
+        "the target area: x=20..30, y=-10..-5"
+    )]
+    #[case("target area: x=20..1073741825, y=-10..-5", 14, "x=20..1073741825")]
+    #[case(
+        "target area: x=20..30, y=-9223372036854775808..-5",
+        24,
+        "y=-9223372036854775808..-5"
+    )]
+    fn test_parse_error(#[case] input: &str, #[case] column: usize, #[case] text: &str) {
+        let error = parse(input).unwrap_err();
+
+        assert_eq!((17, 1, column), (error.day, error.line, error.column));
+        assert_eq!(text, error.text);
+    }
+
+    #[rstest]
+    #[case(
+        Vec2::new(7, 2),
+        vec![(0, 0), (7, 2), (13, 3), (18, 3), (22, 2), (25, 0), (27, -3), (28, -7)]
+    )]
+    #[case(Vec2::new(17, -4), vec![(0, 0), (17, -4)])]
+    #[case(
+        Vec2::new(6, 3),
+        vec![(0, 0), (6, 3), (11, 5), (15, 6), (18, 6), (20, 5), (21, 3), (21, 0), (21, -4), (21, -9)]
+    )]
+    fn test_trajectory(#[case] velocity: Vec2, #[case] expected: Vec<(isize, isize)>) {
+        let target = Day17::read_file("src/day17/test_input.txt").unwrap();
+
+        assert_eq!(
+            expected
+                .into_iter()
+                .map(|(x, y)| Point2::new(x, y))
+                .collect::<Vec<Point2>>(),
+            target.trajectory(velocity).collect::<Vec<Point2>>()
+        );
+    }
+
+    #[test]
+    fn test_candidates_are_bounded() {
+        let target = Day17::read_file("src/day17/test_input.txt").unwrap();
+
+        let candidates = target.candidates().collect::<Vec<Vec2>>();
+
+        assert_eq!(Some(&Vec2::new(6, -10)), candidates.first());
+        assert_eq!(Some(&Vec2::new(30, 9)), candidates.last());
+        assert_eq!(25 * 20, candidates.len());
+    }
+
+    #[rstest]
+    #[case("src/day17/test_input.txt", 45)]
+    fn test_highest_apex(#[case] path: &str, #[case] expected: usize) {
+        let result = highest_apex(Day17::read_file(path).unwrap());
+
+        assert_eq!(expected, result);
+    }
+
+    #[rstest]
+    #[case("src/day17/test_input.txt", 112)]
+    fn test_count_hits(#[case] path: &str, #[case] expected: usize) {
+        let result = count_hits(Day17::read_file(path).unwrap());
+
+        assert_eq!(expected, result);
+    }
+}
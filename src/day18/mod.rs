@@ -0,0 +1,398 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::ops::Add;
+use std::str::FromStr;
+
+use crate::input::{default_input_path, InputError};
+use crate::parse::{input_lines, InputLine, ParseError};
+use crate::solution::{Answer, Solution};
+
+const DAY: u8 = 18;
+
+pub fn get_answer_1() -> Result<usize, InputError> {
+    Ok(magnitude_of_sum(Day18::read_file(default_input_path(DAY))?))
+}
+
+pub fn get_answer_2() -> Result<usize, InputError> {
+    Ok(largest_magnitude_of_two(Day18::read_file(
+        default_input_path(DAY),
+    )?))
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u8 = DAY;
+
+    type Input = Vec<Number>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: Self::Input) -> Answer {
+        magnitude_of_sum(input).into()
+    }
+
+    fn part2(input: Self::Input) -> Answer {
+        largest_magnitude_of_two(input).into()
+    }
+}
+
+/// A snailfish number, a pair whose elements are regular numbers or pairs again.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Number {
+    Regular(u32),
+    Pair(Box<Number>, Box<Number>),
+}
+
+/// Pairs nested inside this many pairs explode.
+const MAX_DEPTH: usize = 4;
+
+/// Regular numbers from this on split.
+const MAX_REGULAR: u32 = 9;
+
+pub fn parse(input: &str) -> Result<Vec<Number>, ParseError> {
+    let numbers = input_lines(DAY, input)
+        .filter(|line| !line.text.is_empty())
+        .map(|line| {
+            let number = Number::parse(&line, line.text)?;
+            match number.is_reduced() {
+                true => Ok(number),
+                false => Err(line.error(line.text, "expected a reduced snailfish number")),
+            }
+        })
+        .collect::<Result<Vec<Number>, ParseError>>()?;
+
+    match numbers.is_empty() {
+        true => Err(InputLine::new(DAY, 0, "").error("", "expected a snailfish number")),
+        false => Ok(numbers),
+    }
+}
+
+impl Number {
+    fn pair(left: Number, right: Number) -> Number {
+        Number::Pair(Box::new(left), Box::new(right))
+    }
+
+    /// Parses a pair like `[[1,2],3]` as part of an input line. Regular numbers may have any
+    /// number of digits, so numbers that still need reducing parse, too.
+    pub fn parse(line: &InputLine, str: &str) -> Result<Number, ParseError> {
+        if !str.starts_with('[') {
+            return Err(line.error(str, "expected a pair"));
+        }
+
+        let (number, rest) = parse_element(line, str, 0)?;
+        match rest.is_empty() {
+            true => Ok(number),
+            false => Err(line.error(rest, "expected the end of the number")),
+        }
+    }
+
+    pub fn magnitude(&self) -> usize {
+        match self {
+            Number::Regular(value) => *value as usize,
+            Number::Pair(left, right) => 3 * left.magnitude() + 2 * right.magnitude(),
+        }
+    }
+
+    /// Whether no pair is nested too deep and no regular number is too large.
+    pub fn is_reduced(&self) -> bool {
+        self.is_reduced_at(0)
+    }
+
+    fn is_reduced_at(&self, depth: usize) -> bool {
+        match self {
+            Number::Regular(value) => *value <= MAX_REGULAR,
+            Number::Pair(left, right) => {
+                depth < MAX_DEPTH && left.is_reduced_at(depth + 1) && right.is_reduced_at(depth + 1)
+            }
+        }
+    }
+
+    /// Explodes the leftmost pair that is nested too deep until there is none, then splits the
+    /// leftmost regular number that is too large, and starts over.
+    pub fn reduce(&mut self) {
+        loop {
+            if self.explode(0).is_some() {
+                continue;
+            }
+            if !self.split() {
+                return;
+            }
+        }
+    }
+
+    /// Replaces the leftmost pair of two regular numbers nested in `MAX_DEPTH` pairs with 0.
+    /// Returns the values that still have to be added to the regular numbers to its left and right.
+    fn explode(&mut self, depth: usize) -> Option<(Option<u32>, Option<u32>)> {
+        let Number::Pair(left, right) = self else {
+            return None;
+        };
+
+        if let (true, Number::Regular(left), Number::Regular(right)) =
+            (depth >= MAX_DEPTH, left.as_ref(), right.as_ref())
+        {
+            let carry = (Some(*left), Some(*right));
+            *self = Number::Regular(0);
+            return Some(carry);
+        }
+
+        if let Some((to_left, to_right)) = left.explode(depth + 1) {
+            if let Some(value) = to_right {
+                right.add_to_edge(value, true);
+            }
+            return Some((to_left, None));
+        }
+        if let Some((to_left, to_right)) = right.explode(depth + 1) {
+            if let Some(value) = to_left {
+                left.add_to_edge(value, false);
+            }
+            return Some((None, to_right));
+        }
+        None
+    }
+
+    /// Adds `value` to the leftmost or the rightmost regular number.
+    fn add_to_edge(&mut self, value: u32, leftmost: bool) {
+        match self {
+            Number::Regular(regular) => *regular += value,
+            Number::Pair(left, _) if leftmost => left.add_to_edge(value, leftmost),
+            Number::Pair(_, right) => right.add_to_edge(value, leftmost),
+        }
+    }
+
+    /// Replaces the leftmost regular number that is too large with a pair of its halves.
+    fn split(&mut self) -> bool {
+        match self {
+            Number::Regular(value) if *value > MAX_REGULAR => {
+                *self = Number::pair(
+                    Number::Regular(*value / 2),
+                    Number::Regular((*value).div_ceil(2)),
+                );
+                true
+            }
+            Number::Regular(_) => false,
+            Number::Pair(left, right) => left.split() || right.split(),
+        }
+    }
+}
+
+/// Reads one element from the start of `str` and returns it with the rest. `depth` is the number
+/// of pairs around it. One level more than reduced numbers have is allowed, which is what a sum
+/// has before it explodes, anything deeper is refused before it can overflow the stack.
+fn parse_element<'a>(
+    line: &InputLine,
+    str: &'a str,
+    depth: usize,
+) -> Result<(Number, &'a str), ParseError> {
+    let expect = |str: &'a str, expected: char| match str.strip_prefix(expected) {
+        Some(rest) => Ok(rest),
+        None => Err(line.error(next_char(str), format!("expected {:?}", expected))),
+    };
+
+    match str.strip_prefix('[') {
+        Some(_) if depth > MAX_DEPTH => Err(line.error(
+            next_char(str),
+            format!("expected pairs nested at most {} deep", MAX_DEPTH + 1),
+        )),
+        Some(rest) => {
+            let (left, rest) = parse_element(line, rest, depth + 1)?;
+            let (right, rest) = parse_element(line, expect(rest, ',')?, depth + 1)?;
+            Ok((Number::pair(left, right), expect(rest, ']')?))
+        }
+        None => {
+            let end = str
+                .find(|char: char| !char.is_ascii_digit())
+                .unwrap_or(str.len());
+            match end {
+                0 => Err(line.error(next_char(str), "expected a regular number or a pair")),
+                _ => Ok((
+                    Number::Regular(line.parse(&str[..end], "a regular number")?),
+                    &str[end..],
+                )),
+            }
+        }
+    }
+}
+
+/// The first character of `str`, or the empty end of it.
+fn next_char(str: &str) -> &str {
+    &str[..str.chars().next().map_or(0, char::len_utf8)]
+}
+
+impl Add for Number {
+    type Output = Number;
+
+    fn add(self, other: Number) -> Number {
+        let mut sum = Number::pair(self, other);
+        sum.reduce();
+        sum
+    }
+}
+
+impl Display for Number {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Number::Regular(value) => write!(f, "{}", value),
+            Number::Pair(left, right) => write!(f, "[{},{}]", left, right),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseNumberError(String);
+
+impl Display for ParseNumberError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "expected a snailfish number, found {:?}", self.0)
+    }
+}
+
+impl Error for ParseNumberError {}
+
+impl FromStr for Number {
+    type Err = ParseNumberError;
+
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        Number::parse(&InputLine::new(DAY, 0, str), str)
+            .map_err(|_| ParseNumberError(str.to_string()))
+    }
+}
+
+fn magnitude_of_sum(input: Vec<Number>) -> usize {
+    input
+        .into_iter()
+        .reduce(|sum, number| sum + number)
+        .unwrap()
+        .magnitude()
+}
+
+/// The largest magnitude of the sum of two different numbers, in either order.
+fn largest_magnitude_of_two(input: Vec<Number>) -> usize {
+    (0..input.len())
+        .flat_map(|first| (0..input.len()).map(move |second| (first, second)))
+        .filter(|(first, second)| first != second)
+        .map(|(first, second)| (input[first].clone() + input[second].clone()).magnitude())
+        .max()
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use crate::day18::*;
+
+    fn number(str: &str) -> Number {
+        str.parse().unwrap()
+    }
+
+    #[test]
+    fn test_read_file() {
+        let input = Day18::read_file("src/day18/test_input.txt").unwrap();
+
+        assert_eq!(10, input.len());
+        assert_eq!(
+            "[[[0,[5,8]],[[1,7],[9,6]]],[[4,[1,2]],[[1,4],2]]]",
+            input[0].to_string()
+        );
+    }
+
+    #[rstest]
+    #[case("[1,2]", Number::pair(Number::Regular(1), Number::Regular(2)))]
+    #[case(
+        "[[1,22],3]",
+        Number::pair(
+            Number::pair(Number::Regular(1), Number::Regular(22)),
+            Number::Regular(3)
+        )
+    )]
+    fn test_from_str(#[case] str: &str, #[case] expected: Number) {
+        assert_eq!(Ok(expected), str.parse::<Number>());
+    }
+
+    #[rstest]
+    #[case("[9,[8,7]]")]
+    #[case("[[[[0,7],4],[15,[0,13]]],[1,1]]")]
+    #[case("[[[[[9,8],1],2],3],4]")]
+    fn test_display_round_trips(#[case] str: &str) {
+        assert_eq!(str, number(str).to_string());
+    }
+
+    #[rstest]
+    #[case("[1,2]\n[[3,4],5\n", 2, 9, "")]
+    #[case("[1,2]\n[[3,4]5]\n", 2, 7, "5")]
+    #[case("[1,2]\n[[3,x],5]\n", 2, 5, "x")]
+    #[case("[1,2]\n[3,4]]\n", 2, 6, "]")]
+    #[case("7\n", 1, 1, "7")]
+    #[case("[[[[[9,8],1],2],3],4]\n", 1, 1, "[[[[[9,8],1],2],3],4]")]
+    #[case("[10,1]\n", 1, 1, "[10,1]")]
+    #[case("\n", 1, 1, "")]
+    #[case("[[[[[[1,2],3],4],5],6],7]\n", 1, 6, "[")]
+    #[case(&"[".repeat(200_000), 1, 6, "[")]
+    fn test_parse_error(
+        #[case] input: &str,
+        #[case] line: usize,
+        #[case] column: usize,
+        #[case] text: &str,
+    ) {
+        let error = parse(input).unwrap_err();
+
+        assert_eq!((18, line, column), (error.day, error.line, error.column));
+        assert_eq!(text, error.text);
+    }
+
+    #[rstest]
+    #[case("[[[[[9,8],1],2],3],4]", "[[[[0,9],2],3],4]")]
+    #[case("[7,[6,[5,[4,[3,2]]]]]", "[7,[6,[5,[7,0]]]]")]
+    #[case("[[6,[5,[4,[3,2]]]],1]", "[[6,[5,[7,0]]],3]")]
+    #[case(
+        "[[3,[2,[1,[7,3]]]],[6,[5,[4,[3,2]]]]]",
+        "[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]"
+    )]
+    fn test_explode(#[case] before: &str, #[case] after: &str) {
+        let mut number = number(before);
+
+        assert!(number.explode(0).is_some());
+        assert_eq!(after, number.to_string());
+    }
+
+    #[rstest]
+    #[case(
+        "[[[[4,3],4],4],[7,[[8,4],9]]]",
+        "[1,1]",
+        "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]"
+    )]
+    #[case(
+        "[[[0,[4,5]],[0,0]],[[[4,5],[2,6]],[9,5]]]",
+        "[7,[[[3,7],[4,3]],[[6,3],[8,8]]]]",
+        "[[[[4,0],[5,4]],[[7,7],[6,0]]],[[8,[7,7]],[[7,9],[5,0]]]]"
+    )]
+    fn test_add(#[case] left: &str, #[case] right: &str, #[case] expected: &str) {
+        assert_eq!(expected, (number(left) + number(right)).to_string());
+    }
+
+    #[rstest]
+    #[case("[[1,2],[[3,4],5]]", 143)]
+    #[case("[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]", 3488)]
+    fn test_magnitude(#[case] str: &str, #[case] expected: usize) {
+        assert_eq!(expected, number(str).magnitude());
+    }
+
+    #[rstest]
+    #[case("src/day18/test_input.txt", 4140)]
+    fn test_magnitude_of_sum(#[case] path: &str, #[case] expected: usize) {
+        let result = magnitude_of_sum(Day18::read_file(path).unwrap());
+
+        assert_eq!(expected, result);
+    }
+
+    #[rstest]
+    #[case("src/day18/test_input.txt", 3993)]
+    fn test_largest_magnitude_of_two(#[case] path: &str, #[case] expected: usize) {
+        let result = largest_magnitude_of_two(Day18::read_file(path).unwrap());
+
+        assert_eq!(expected, result);
+    }
+}
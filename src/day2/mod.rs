@@ -1,9 +1,10 @@
-use std::fs::read_to_string;
-
 use crate::day2::Direction::Forward;
+use crate::input::{default_input_path, InputError};
+use crate::parse::{input_lines, InputLine, ParseError};
+use crate::solution::{Answer, Solution};
 
-#[derive(Debug, Eq, PartialEq)]
-enum Direction {
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum Direction {
     Forward,
     Down,
     Up,
@@ -34,37 +35,57 @@ impl Direction {
     fn apply_better(&self, value: i32, (x, depth, aim): (isize, isize, isize)) -> (isize, isize, isize) {
         let value = value as isize;
         match self {
-            Forward => (x + value as isize, depth + aim * value, aim),
+            Forward => (x + value, depth + aim * value, aim),
             Direction::Down => (x, depth, aim + value),
             Direction::Up => (x, depth, aim - value),
         }
     }
 }
 
-pub fn get_answer_1() -> isize {
-    do_it(read_file())
+const DAY: u8 = 2;
+
+pub fn get_answer_1() -> Result<isize, InputError> {
+    Ok(do_it(Day2::read_file(default_input_path(DAY))?))
 }
 
-pub fn get_answer_2() -> isize {
-    do_it_better(read_file())
+pub fn get_answer_2() -> Result<isize, InputError> {
+    Ok(do_it_better(Day2::read_file(default_input_path(DAY))?))
 }
 
-fn read_file() -> Vec<(Direction, i32)> {
-    let file_content = read_to_string("src/day2/input.txt").expect("hoppla");
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = DAY;
+
+    type Input = Vec<(Direction, i32)>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: Self::Input) -> Answer {
+        do_it(input).into()
+    }
+
+    fn part2(input: Self::Input) -> Answer {
+        do_it_better(input).into()
+    }
+}
 
-    file_content.lines()
-        .into_iter()
+pub fn parse(input: &str) -> Result<Vec<(Direction, i32)>, ParseError> {
+    input_lines(DAY, input)
         .map(parse_line)
         .collect()
 }
 
-fn parse_line(line: &str) -> (Direction, i32) {
-    let mut parts: Vec<&str> = line.split(" ").collect();
+fn parse_line(line: InputLine) -> Result<(Direction, i32), ParseError> {
+    let (direction, value) = line.split_once(" ")?;
 
-    let value = parts.pop().unwrap().parse::<i32>().unwrap();
-    let direction = parts.pop().unwrap().try_into().unwrap();
+    let direction = Direction::try_from(direction)
+        .map_err(|_| line.error(direction, "expected forward, down or up"))?;
+    let value = line.parse::<i32>(value, "a number")?;
 
-    (direction, value)
+    Ok((direction, value))
 }
 
 fn do_it(input: Vec<(Direction, i32)>) -> isize {
@@ -93,7 +114,7 @@ mod tests {
 
     #[test]
     fn test_read_file() {
-        let results = read_file();
+        let results = Day2::read_file("src/day2/input.txt").unwrap();
 
         assert_eq!(1000, results.len());
         assert_eq!(Some(&(Direction::Forward, 2)), results.first());
@@ -102,6 +123,16 @@ mod tests {
         assert_eq!(Some(&(Direction::Forward, 4)), results.last());
     }
 
+    #[rstest]
+    #[case("forward 5\nsideways 3", 2, 1, "sideways")]
+    #[case("forward 5\ndown x", 2, 6, "x")]
+    #[case("forward", 1, 1, "forward")]
+    fn test_parse_error(#[case] input: &str, #[case] line: usize, #[case] column: usize, #[case] text: &str) {
+        let error = parse(input).unwrap_err();
+
+        assert_eq!((2, line, column, text), (error.day, error.line, error.column, error.text.as_str()));
+    }
+
     #[rstest]
     #[case(vec ! [], 0)]
     #[case(vec ! [(Direction::Forward, 1), (Direction::Down, 1)], 1)]
@@ -139,10 +170,4 @@ mod tests {
 
         assert_eq!(expected, result);
     }
-
-    #[test]
-    fn result() {
-        println!("{}", get_answer_1());
-        println!("{}", get_answer_2());
-    }
 }
\ No newline at end of file
@@ -12,14 +12,14 @@ fn reduce_to_most_common_value_at_position(mut input: Vec<Vec<isize>>, position:
         return input.pop().unwrap();
     }
 
-    let bit_sum_at_position = sum_bits_at_position(&mut input, position);
+    let bit_sum_at_position = sum_bits_at_position(&input, position);
 
     let most_common_bit = match 2 * bit_sum_at_position >= input.len() as isize {
         true => 1,
         false => 0,
     };
 
-    let next = filter_by_bit(&mut input, position, most_common_bit);
+    let next = filter_by_bit(&input, position, most_common_bit);
 
     reduce_to_most_common_value_at_position(next, position + 1)
 }
@@ -29,19 +29,22 @@ fn reduce_to_least_common_value_at_position(mut input: Vec<Vec<isize>>, position
         return input.pop().unwrap();
     }
 
-    let bit_sum_at_position = sum_bits_at_position(&mut input, position);
+    let bit_sum_at_position = sum_bits_at_position(&input, position);
 
-    let least_common_bit = match 2 * bit_sum_at_position < input.len() as isize {
-        true => 1,
-        false => 0,
+    // A bit every remaining value shares is the least common one too, or nothing would be left
+    let least_common_bit = match bit_sum_at_position {
+        0 => 0,
+        sum if sum == input.len() as isize => 1,
+        sum if 2 * sum < input.len() as isize => 1,
+        _ => 0,
     };
 
-    let next = filter_by_bit(&mut input, position, least_common_bit);
+    let next = filter_by_bit(&input, position, least_common_bit);
 
     reduce_to_least_common_value_at_position(next, position + 1)
 }
 
-fn all_values_are_equal(input: &Vec<Vec<isize>>) -> bool {
+fn all_values_are_equal(input: &[Vec<isize>]) -> bool {
     input.iter()
         .zip(input.iter().skip(1))
         .fold(
@@ -50,14 +53,14 @@ fn all_values_are_equal(input: &Vec<Vec<isize>>) -> bool {
         )
 }
 
-fn sum_bits_at_position(input: &mut Vec<Vec<isize>>, position: usize) -> isize {
+fn sum_bits_at_position(input: &[Vec<isize>], position: usize) -> isize {
     input.iter().fold(
         0,
         |sum, bits| sum + bits.get(position).unwrap(),
     )
 }
 
-fn filter_by_bit(input: &mut Vec<Vec<isize>>, position: usize, most_common_bit: isize) -> Vec<Vec<isize>> {
+fn filter_by_bit(input: &[Vec<isize>], position: usize, most_common_bit: isize) -> Vec<Vec<isize>> {
     input.iter()
         .filter(|bits| *bits.get(position).unwrap() == most_common_bit)
         .cloned()
@@ -1,26 +1,65 @@
 mod life_support_rating;
 
-use std::fs::read_to_string;
 use crate::day3::life_support_rating::*;
+use crate::input::{default_input_path, InputError};
+use crate::parse::{input_lines, InputLine, ParseError};
+use crate::solution::{Answer, Solution};
 
-pub fn get_answer_1() -> isize {
-    do_it(read_file())
+const DAY: u8 = 3;
+
+pub fn get_answer_1() -> Result<isize, InputError> {
+    Ok(do_it(Day3::read_file(default_input_path(DAY))?))
 }
 
-pub fn get_answer_2() -> isize {
-    find_life_support_rating(read_file())
+pub fn get_answer_2() -> Result<isize, InputError> {
+    Ok(find_life_support_rating(Day3::read_file(default_input_path(DAY))?))
 }
 
-fn read_file() -> Vec<Vec<isize>> {
-    let file_content = read_to_string("src/day3/input.txt").expect("hoppla");
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = DAY;
+
+    type Input = Vec<Vec<isize>>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: Self::Input) -> Answer {
+        do_it(input).into()
+    }
+
+    fn part2(input: Self::Input) -> Answer {
+        find_life_support_rating(input).into()
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<Vec<isize>>, ParseError> {
+    let lines = input_lines(DAY, input).collect::<Vec<InputLine>>();
+    let width = lines.first().map(|line| line.text.len()).unwrap_or(0);
+
+    if width == 0 {
+        let line = lines.into_iter().next().unwrap_or_else(|| InputLine::new(DAY, 0, ""));
+        return Err(line.error(line.text, "expected a line of bits"));
+    }
 
-    file_content.lines()
-        .into_iter()
-        .map(|str|
-            str.chars().map(|char|
-                char.to_string().parse::<isize>().unwrap()
-            ).collect()
-        )
+    lines.into_iter()
+        .map(|line| parse_line(line, width))
+        .collect()
+}
+
+fn parse_line(line: InputLine, width: usize) -> Result<Vec<isize>, ParseError> {
+    if line.text.len() != width {
+        return Err(line.error(line.text, format!("expected {} bits", width)));
+    }
+
+    line.text.char_indices()
+        .map(|(index, char)| match char {
+            '0' => Ok(0),
+            '1' => Ok(1),
+            _ => Err(line.error(&line.text[index..index + char.len_utf8()], "expected 0 or 1")),
+        })
         .collect()
 }
 
@@ -48,7 +87,7 @@ fn do_it(input: Vec<Vec<isize>>) -> isize {
     bits_to_decimal(gamma_rate_bits) * bits_to_decimal(epsilon_rate_bits)
 }
 
-fn increase_bit_counts(mut gamma_rate_bit_counts: Vec<isize>, line: &Vec<isize>) -> Vec<isize> {
+fn increase_bit_counts(mut gamma_rate_bit_counts: Vec<isize>, line: &[isize]) -> Vec<isize> {
     for (bit_position, bit) in line.iter().enumerate() {
         *gamma_rate_bit_counts.get_mut(bit_position).unwrap() += bit;
     }
@@ -72,7 +111,7 @@ mod tests {
 
     #[test]
     fn test_read_file() {
-        let results = read_file();
+        let results = Day3::read_file("src/day3/input.txt").unwrap();
 
         let expected_first = vec![0, 0, 0, 0, 0, 1, 0, 0, 0, 1, 0, 1];
         let expected_last = vec![0, 0, 1, 0, 0, 0, 0, 1, 1, 0, 0, 1];
@@ -82,6 +121,17 @@ mod tests {
         assert_eq!(Some(&expected_last), results.last());
     }
 
+    #[rstest]
+    #[case("0101\n0121", 2, 3, "2")]
+    #[case("0101\n01011", 2, 1, "01011")]
+    #[case("", 1, 1, "")]
+    #[case("\n0101", 1, 1, "")]
+    fn test_parse_error(#[case] input: &str, #[case] line: usize, #[case] column: usize, #[case] text: &str) {
+        let error = parse(input).unwrap_err();
+
+        assert_eq!((3, line, column, text), (error.day, error.line, error.column, error.text.as_str()));
+    }
+
     #[rstest]
     #[case(vec ! [vec![1, 0], vec![1, 0], vec![0, 1]], 2)]
     #[case(vec ! [
@@ -107,6 +157,7 @@ mod tests {
     #[rstest]
     #[case(vec ! [vec![1, 0], vec![1, 0], vec![0, 1]], 2)]
     #[case(vec ! [vec![1, 1, 0], vec![1, 0, 0], vec![0, 1, 1]], 18)]
+    #[case(vec ! [vec![1, 0], vec![1, 1]], 6)]
     #[case(vec ! [
     vec![0,0,1,0,0],
     vec![1,1,1,1,0],
@@ -126,12 +177,4 @@ mod tests {
 
         assert_eq!(expected, result);
     }
-
-    #[test]
-    fn result() {
-        println!("{}", get_answer_1());
-        assert_eq!(738234, get_answer_1());
-        println!("{}", get_answer_2());
-        assert_eq!(3969126, get_answer_2());
-    }
 }
\ No newline at end of file
@@ -1,24 +1,28 @@
-use nalgebra::{
-    ArrayStorage, Const, MatrixSlice, MatrixSlice5x1, RowVector5, SquareMatrix, U1, U5,
-};
+use crate::day4::DAY;
+use crate::geometry::Point2;
+use crate::grid::Grid;
+use crate::parse::{InputLine, ParseError};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct BingoBoard {
-    matrix: SquareMatrix<(isize, bool), U5, ArrayStorage<(isize, bool), 5, 5>>,
+    matrix: Grid<(isize, bool)>,
 }
 
 impl BingoBoard {
-    pub fn builder() -> BoardBuilder {
-        BoardBuilder { rows: vec![] }
+    pub fn builder<'a>() -> BoardBuilder<'a> {
+        BoardBuilder {
+            rows: vec![],
+            last_line: None,
+        }
     }
 
     pub fn get(&self, row: usize, column: usize) -> (isize, bool) {
-        self.matrix[(row, column)]
+        self.matrix[Point2::new(column as isize, row as isize)]
     }
 
     pub fn compute_unmarked_score(&self) -> isize {
         self.matrix
-            .iter()
+            .values()
             .filter(|(_, marked)| !*marked)
             .map(|(value, _)| value)
             .sum()
@@ -26,74 +30,81 @@ impl BingoBoard {
 
     pub fn mark_value(&mut self, value: isize) {
         self.matrix
-            .iter_mut()
+            .values_mut()
             .filter(|(stored_value, _)| stored_value == &value)
             .for_each(|(_, marked)| *marked = true);
     }
 
     pub fn bingo(&self) -> bool {
-        let has_completed_column = self.matrix.column_iter().fold(false, |completed, column| {
-            completed || is_column_completed(column)
-        });
+        let has_completed_column = self
+            .matrix
+            .columns()
+            .any(|mut column| column.all(|(_, marked)| *marked));
         let has_completed_row = self
             .matrix
-            .row_iter()
-            .fold(false, |completed, row| completed || is_row_completed(row));
+            .rows()
+            .any(|row| row.iter().all(|(_, marked)| *marked));
 
         has_completed_row || has_completed_column
     }
 }
 
-fn is_column_completed(column: MatrixSlice5x1<(isize, bool)>) -> bool {
-    column.iter().fold(true, |column_completed, (_, marked)| {
-        column_completed && *marked
-    })
+pub struct BoardBuilder<'a> {
+    rows: Vec<Vec<(isize, bool)>>,
+    last_line: Option<InputLine<'a>>,
 }
 
-fn is_row_completed(row: MatrixSlice<(isize, bool), U1, U5, Const<1>, Const<5>>) -> bool {
-    row.iter()
-        .fold(true, |row_completed, (_, marked)| row_completed && *marked)
-}
+impl<'a> BoardBuilder<'a> {
+    pub fn add_row(&mut self, line: InputLine<'a>) -> Result<(), ParseError> {
+        let row = line
+            .text
+            .split_whitespace()
+            .map(|entry| {
+                line.parse::<isize>(entry, "a number")
+                    .map(|value| (value, false))
+            })
+            .collect::<Result<Vec<(isize, bool)>, ParseError>>()?;
+
+        if row.len() != 5 {
+            return Err(line.error(
+                line.text,
+                format!("expected 5 numbers per row, found {}", row.len()),
+            ));
+        }
 
-pub struct BoardBuilder {
-    rows: Vec<RowVector5<(isize, bool)>>,
-}
+        self.rows.push(row);
+        self.last_line = Some(line);
+        Ok(())
+    }
 
-impl BoardBuilder {
-    pub fn add_row(&mut self, row_str: &str) {
-        let row = row_str
-            .split_whitespace()
-            .map(|entry| (entry.parse::<isize>().unwrap(), false));
-        self.rows.push(RowVector5::from_iterator(row));
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
     }
 
-    pub fn build(self) -> BingoBoard {
+    pub fn build(self) -> Result<BingoBoard, ParseError> {
         if self.rows.len() != 5 {
-            panic!("Länge war {}", self.rows.len());
+            let line = self.last_line.unwrap_or_else(|| InputLine::new(DAY, 0, ""));
+            return Err(line.error(
+                line.text,
+                format!("expected 5 rows per board, found {}", self.rows.len()),
+            ));
         };
 
-        BingoBoard {
-            matrix: SquareMatrix::from_rows(&self.rows),
-        }
+        Ok(BingoBoard {
+            matrix: Grid::from_rows(self.rows).unwrap(),
+        })
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use nalgebra::{RowVector5, SquareMatrix, U5};
-
     use crate::day4::board::BingoBoard;
+    use crate::grid::Grid;
 
     #[test]
     fn board_score() {
         let under_test = BingoBoard {
-            matrix: SquareMatrix::from_rows(&vec![
-                RowVector5::from_element((1, false)),
-                RowVector5::from_element((2, false)),
-                RowVector5::from_element((3, false)),
-                RowVector5::from_element((4, false)),
-                RowVector5::from_element((5, false)),
-            ]),
+            matrix: Grid::from_fn(5, 5, |point| (point.y + 1, false)),
         };
 
         assert_eq!(75, under_test.compute_unmarked_score());
@@ -102,14 +113,12 @@ mod tests {
     #[test]
     fn tick_value() {
         let mut under_test = BingoBoard {
-            matrix: SquareMatrix::<_, U5, _>::from_fn(|i, j| ((i * 10 + j) as isize, false)),
+            matrix: Grid::from_fn(5, 5, |point| (point.y * 10 + point.x, false)),
         };
 
         under_test.mark_value(6);
 
-        let is_value_ticked = under_test
-            .matrix
-            .fold(false, |result, (_, ticked)| result || ticked);
+        let is_value_ticked = under_test.matrix.values().any(|(_, ticked)| *ticked);
         assert!(!is_value_ticked);
 
         under_test.mark_value(4);
@@ -120,7 +129,7 @@ mod tests {
     #[test]
     fn bingo_vertical() {
         let mut under_test = BingoBoard {
-            matrix: SquareMatrix::<_, U5, _>::from_fn(|i, j| ((i * 10 + j) as isize, false)),
+            matrix: Grid::from_fn(5, 5, |point| (point.y * 10 + point.x, false)),
         };
 
         assert!(!under_test.bingo());
@@ -149,7 +158,7 @@ mod tests {
     #[test]
     fn bingo_horizontal() {
         let mut under_test = BingoBoard {
-            matrix: SquareMatrix::<_, U5, _>::from_fn(|i, j| ((i * 10 + j) as isize, false)),
+            matrix: Grid::from_fn(5, 5, |point| (point.y * 10 + point.x, false)),
         };
 
         assert!(!under_test.bingo());
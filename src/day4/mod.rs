@@ -1,56 +1,106 @@
-use std::fs::read_to_string;
-
-use crate::day4::board::{BingoBoard, BoardBuilder};
+use crate::day4::board::BoardBuilder;
+use crate::input::{default_input_path, InputError};
+use crate::parse::{input_lines, InputLine, ParseError};
+use crate::solution::{Answer, Solution};
 
 mod board;
 
-pub fn get_answer_1() -> isize {
-    play(read_file("src/day4/input.txt"))
+pub use board::BingoBoard;
+
+const DAY: u8 = 4;
+
+pub fn get_answer_1() -> Result<isize, InputError> {
+    Ok(play(Day4::read_file(default_input_path(DAY))?))
 }
 
-pub fn get_answer_2() -> isize {
-    play_badly(read_file("src/day4/input.txt"))
+pub fn get_answer_2() -> Result<isize, InputError> {
+    Ok(play_badly(Day4::read_file(default_input_path(DAY))?))
 }
 
-fn read_file(path: &str) -> (Vec<isize>, Vec<BingoBoard>) {
-    let file_content = read_to_string(path).expect("hoppla");
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = DAY;
+
+    type Input = (Vec<isize>, Vec<BingoBoard>);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: Self::Input) -> Answer {
+        play(input).into()
+    }
+
+    fn part2(input: Self::Input) -> Answer {
+        play_badly(input).into()
+    }
+}
 
-    let lines: Vec<&str> = file_content.lines().collect();
+pub fn parse(input: &str) -> Result<(Vec<isize>, Vec<BingoBoard>), ParseError> {
+    let mut lines = input_lines(DAY, input);
 
-    let numbers = lines
-        .first()
-        .unwrap()
-        .split(",")
-        .map(|number| number.parse::<isize>().unwrap())
-        .collect();
+    let numbers_line = lines
+        .next()
+        .ok_or_else(|| InputLine::new(DAY, 0, input).error(input, "expected the drawn numbers"))?;
+    let numbers = numbers_line
+        .text
+        .split(',')
+        .map(|number| numbers_line.parse::<isize>(number, "a number"))
+        .collect::<Result<Vec<isize>, ParseError>>()?;
 
-    let (mut bingo_boards, board_builder) = lines.iter().skip(2).fold(
+    let (mut bingo_boards, board_builder) = lines.try_fold(
         (vec![], BingoBoard::builder()),
         |(bingo_boards, board_builder), line| add_line_to_boards(bingo_boards, board_builder, line),
-    );
+    )?;
 
-    bingo_boards.push(board_builder.build());
+    if !board_builder.is_empty() {
+        bingo_boards.push(board_builder.build()?);
+    }
+
+    if bingo_boards.is_empty() {
+        return Err(numbers_line.error(
+            &numbers_line.text[numbers_line.text.len()..],
+            "expected bingo boards after the drawn numbers",
+        ));
+    }
+    // The second part plays until the last board wins, so every board has to win eventually.
+    if let Some(index) = bingo_boards
+        .iter()
+        .position(|board| !wins_with(board.clone(), &numbers))
+    {
+        return Err(numbers_line.error(
+            numbers_line.text,
+            format!("board {} never wins with these numbers", index + 1),
+        ));
+    }
 
-    (numbers, bingo_boards)
+    Ok((numbers, bingo_boards))
 }
 
-fn add_line_to_boards(
+fn add_line_to_boards<'a>(
     mut bingo_boards: Vec<BingoBoard>,
-    mut board_builder: BoardBuilder,
-    line: &str,
-) -> (Vec<BingoBoard>, BoardBuilder) {
-    match line.len() {
-        0 => {
-            bingo_boards.push(board_builder.build());
-            (bingo_boards, BingoBoard::builder())
+    mut board_builder: BoardBuilder<'a>,
+    line: InputLine<'a>,
+) -> Result<(Vec<BingoBoard>, BoardBuilder<'a>), ParseError> {
+    match (line.text.trim().is_empty(), board_builder.is_empty()) {
+        (true, true) => Ok((bingo_boards, board_builder)),
+        (true, false) => {
+            bingo_boards.push(board_builder.build()?);
+            Ok((bingo_boards, BingoBoard::builder()))
         }
-        _ => {
-            board_builder.add_row(line);
-            (bingo_boards, board_builder)
+        (false, _) => {
+            board_builder.add_row(line)?;
+            Ok((bingo_boards, board_builder))
         }
     }
 }
 
+fn wins_with(mut board: BingoBoard, numbers: &[isize]) -> bool {
+    numbers.iter().for_each(|number| board.mark_value(*number));
+    board.bingo()
+}
+
 fn play((numbers, mut boards): (Vec<isize>, Vec<BingoBoard>)) -> isize {
     for number in numbers {
         for board in &mut boards {
@@ -88,11 +138,13 @@ fn play_badly((numbers, mut boards): (Vec<isize>, Vec<BingoBoard>)) -> isize {
 
 #[cfg(test)]
 mod tests {
+    use rstest::*;
+
     use crate::day4::*;
 
     #[test]
     fn test_read_file() {
-        let (numbers, bingo_boards) = read_file("src/day4/input.txt");
+        let (numbers, bingo_boards) = Day4::read_file("src/day4/input.txt").unwrap();
 
         assert_eq!(Some(&90), numbers.first());
         assert_eq!(Some(&65), numbers.last());
@@ -101,25 +153,48 @@ mod tests {
         assert_eq!((60, false), bingo_boards.last().unwrap().get(4, 4));
     }
 
+    #[rstest]
+    #[case("1,2,x\n", 1, 5, "x")]
+    #[case(
+        "1,2\n\n1 2 3 4 5\n1 2 3 4 5\n1 2 3 4 5\n1 2 3 4 5\n",
+        6,
+        1,
+        "1 2 3 4 5"
+    )]
+    #[case("1,2\n\n1 2 3 4 5\n1 2 3 4 5\n1 2 3 4\n", 5, 1, "1 2 3 4")]
+    #[case("1,2\n\n1 2 3 4 5\n1 2 a 4 5\n", 4, 5, "a")]
+    #[case("1,2\n", 1, 4, "")]
+    #[case(
+        "1,2\n\n1 2 3 4 5\n6 7 8 9 10\n11 12 13 14 15\n16 17 18 19 20\n21 22 23 24 25\n",
+        1,
+        1,
+        "1,2"
+    )]
+    fn test_parse_error(
+        #[case] input: &str,
+        #[case] line: usize,
+        #[case] column: usize,
+        #[case] text: &str,
+    ) {
+        let error = parse(input).unwrap_err();
+
+        assert_eq!(
+            (4, line, column, text),
+            (error.day, error.line, error.column, error.text.as_str())
+        );
+    }
+
     #[test]
     fn test_input() {
-        let result = play(read_file("src/day4/test_input.txt"));
+        let result = play(Day4::read_file("src/day4/test_input.txt").unwrap());
 
         assert_eq!(4512, result);
     }
 
     #[test]
     fn test_input_play_badly() {
-        let result = play_badly(read_file("src/day4/test_input.txt"));
+        let result = play_badly(Day4::read_file("src/day4/test_input.txt").unwrap());
 
         assert_eq!(1924, result);
     }
-
-    #[test]
-    fn result() {
-        println!("{}", get_answer_1());
-        assert_eq!(8136, get_answer_1());
-        println!("{}", get_answer_2());
-        assert_eq!(12738, get_answer_2());
-    }
 }
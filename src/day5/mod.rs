@@ -1,90 +1,107 @@
 use std::collections::HashMap;
-use std::fs::read_to_string;
-use std::ops::AddAssign;
 
-pub fn get_answer_1() -> usize {
-    count_non_diagonal_points_higher_than_2(read_file("src/day5/input.txt"))
+use crate::geometry::{Point2, Vec2};
+use crate::grid::Grid;
+use crate::input::{default_input_path, InputError};
+use crate::parse::{input_lines, InputLine, ParseError};
+use crate::render::{Color, Frame};
+use crate::solution::{Answer, Solution};
+
+const DAY: u8 = 5;
+
+pub fn get_answer_1() -> Result<usize, InputError> {
+    Ok(count_non_diagonal_points_higher_than_2(Day5::read_file(
+        default_input_path(DAY),
+    )?))
 }
 
-pub fn get_answer_2() -> usize {
-    count_all_points_higher_than_2(read_file("src/day5/input.txt"))
+pub fn get_answer_2() -> Result<usize, InputError> {
+    Ok(count_all_points_higher_than_2(Day5::read_file(
+        default_input_path(DAY),
+    )?))
 }
 
-fn read_file(path: &str) -> Vec<Line> {
-    let file_content = read_to_string(path).expect("hoppla");
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = DAY;
+
+    type Input = Vec<Line>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: Self::Input) -> Answer {
+        count_non_diagonal_points_higher_than_2(input).into()
+    }
+
+    fn part2(input: Self::Input) -> Answer {
+        count_all_points_higher_than_2(input).into()
+    }
+}
 
-    file_content
-        .lines()
-        .into_iter()
-        .map(Line::from_str)
-        .collect()
+pub fn parse(input: &str) -> Result<Vec<Line>, ParseError> {
+    input_lines(DAY, input).map(Line::parse).collect()
 }
 
 fn count_non_diagonal_points_higher_than_2(input: Vec<Line>) -> usize {
-    let map = input
-        .into_iter()
-        .filter(Line::is_not_diagonal)
-        .flat_map(|line| line.into_iter())
-        .fold(HashMap::<Point, usize>::new(), |mut map, point| {
-            map.entry(point)
-                .and_modify(|value| *value += 1)
-                .or_insert(1);
-            map
-        });
+    let map = count_overlaps(input.into_iter().filter(Line::is_not_diagonal));
 
     map.iter().filter(|(_, value)| value > &&1).count()
 }
 
 fn count_all_points_higher_than_2(input: Vec<Line>) -> usize {
-    let map = input.into_iter().flat_map(|line| line.into_iter()).fold(
-        HashMap::<Point, usize>::new(),
-        |mut map, point| {
-            map.entry(point)
-                .and_modify(|value| *value += 1)
-                .or_insert(1);
-            map
-        },
-    );
+    let map = count_overlaps(input.into_iter());
 
     map.iter().filter(|(_, value)| value > &&1).count()
 }
 
-#[derive(Eq, PartialEq, Debug, Hash, Copy, Clone)]
-struct Point {
-    x: isize,
-    y: isize,
-}
-
-impl Point {
-    fn from_str(str: &str) -> Point {
-        let parts: Vec<&str> = str.split(",").collect();
-        Point {
-            x: parts[0].parse::<isize>().unwrap(),
-            y: parts[1].parse::<isize>().unwrap(),
-        }
-    }
+fn count_overlaps(lines: impl Iterator<Item = Line>) -> HashMap<Point2, usize> {
+    lines
+        .flat_map(|line| line.into_iter())
+        .fold(HashMap::new(), |mut map, point| {
+            map.entry(point)
+                .and_modify(|value| *value += 1)
+                .or_insert(1);
+            map
+        })
 }
 
-impl AddAssign for Point {
-    fn add_assign(&mut self, rhs: Self) {
-        self.x += rhs.x;
-        self.y += rhs.y;
-    }
+/// How many vents overlap at each point, from dark (none) to bright (the most).
+pub fn render_heatmap(input: &[Line]) -> Frame {
+    let map = count_overlaps(input.iter().copied());
+    let width = map.keys().map(|point| point.x + 1).max().unwrap_or(0);
+    let height = map.keys().map(|point| point.y + 1).max().unwrap_or(0);
+    let maximum = map.values().copied().max().unwrap_or(1);
+
+    Grid::from_fn(width as usize, height as usize, |point| {
+        let overlaps = map.get(&point).copied().unwrap_or(0);
+        Color::NAVY.mix(Color::GOLD, overlaps as f64 / maximum as f64)
+    })
 }
 
 #[derive(Eq, PartialEq, Debug, Copy, Clone)]
-struct Line {
-    from: Point,
-    to: Point,
+pub struct Line {
+    from: Point2,
+    to: Point2,
 }
 
 impl Line {
-    fn from_str(str_line: &str) -> Line {
-        let parts: Vec<&str> = str_line.split(" -> ").collect();
-        let from = Point::from_str(parts[0]);
-        let to = Point::from_str(parts[1]);
+    fn parse(line: InputLine) -> Result<Line, ParseError> {
+        let (from, to) = line.split_once(" -> ")?;
+        let from = Point2::parse(&line, from)?;
+        let to = Point2::parse(&line, to)?;
+
+        let delta = to - from;
+        if delta.x != 0 && delta.y != 0 && delta.x.abs() != delta.y.abs() {
+            return Err(line.error(
+                line.text,
+                "expected a horizontal, vertical or diagonal line",
+            ));
+        }
 
-        Line { from, to }
+        Ok(Line { from, to })
     }
 
     fn is_not_diagonal(&self) -> bool {
@@ -93,23 +110,15 @@ impl Line {
 }
 
 impl IntoIterator for Line {
-    type Item = Point;
+    type Item = Point2;
     type IntoIter = LineIterator;
 
     fn into_iter(self) -> Self::IntoIter {
-        let delta_x = self.to.x - self.from.x;
-        let delta_y = self.to.y - self.from.y;
-
-        if delta_x != 0 && delta_y != 0 && delta_x.abs() != delta_y.abs() {
-            panic!("das sieht komisch aus: {:?}", self);
-        }
-
+        // Line::parse only accepts horizontal, vertical and diagonal lines, so the direction
+        // always reaches `to`.
         LineIterator {
+            direction: (self.to - self.from).signum(),
             line: self,
-            direction: Point {
-                x: delta_x.signum(),
-                y: delta_y.signum(),
-            },
             finished: false,
             counter: 0,
         }
@@ -117,21 +126,21 @@ impl IntoIterator for Line {
 }
 
 #[derive(Debug)]
-struct LineIterator {
+pub struct LineIterator {
     line: Line,
-    direction: Point,
+    direction: Vec2,
     finished: bool,
     counter: usize,
 }
 
 impl Iterator for LineIterator {
-    type Item = Point;
+    type Item = Point2;
 
     fn next(&mut self) -> Option<Self::Item> {
         if self.finished || self.counter > 1000 {
             None
         } else {
-            let next = self.line.from.clone();
+            let next = self.line.from;
             self.counter += 1;
             if next == self.line.to {
                 self.finished = true;
@@ -145,20 +154,24 @@ impl Iterator for LineIterator {
 
 #[cfg(test)]
 mod tests {
+    use rstest::*;
+
     use crate::day5::*;
+    use crate::render::tests::assert_golden;
+    use crate::render::{to_ppm, to_svg};
 
     #[test]
     fn test_read_file() {
-        let lines = read_file("src/day5/input.txt");
+        let lines = Day5::read_file("src/day5/input.txt").unwrap();
 
         let expected_first = Line {
-            from: Point { x: 284, y: 294 },
-            to: Point { x: 733, y: 743 },
+            from: Point2::new(284, 294),
+            to: Point2::new(733, 743),
         };
 
         let expected_last = Line {
-            from: Point { x: 595, y: 393 },
-            to: Point { x: 941, y: 393 },
+            from: Point2::new(595, 393),
+            to: Point2::new(941, 393),
         };
 
         assert_eq!(500, lines.len());
@@ -166,25 +179,49 @@ mod tests {
         assert_eq!(Some(&expected_last), lines.last());
     }
 
+    #[rstest]
+    #[case("0,9 -> 5,9\n8,0 -> 0,8\n9,4 - 3,4", 3, 1, "9,4 - 3,4")]
+    #[case("0,9 -> 5,9\n8,0 -> 0,y", 2, 10, "y")]
+    #[case("0,9 -> 59", 1, 8, "59")]
+    #[case("0,9 -> 5,7", 1, 1, "0,9 -> 5,7")]
+    fn test_parse_error(
+        #[case] input: &str,
+        #[case] line: usize,
+        #[case] column: usize,
+        #[case] text: &str,
+    ) {
+        let error = parse(input).unwrap_err();
+
+        assert_eq!(
+            (5, line, column, text),
+            (error.day, error.line, error.column, error.text.as_str())
+        );
+    }
+
     #[test]
     fn test_input_non_diagonal_only() {
-        let result = count_non_diagonal_points_higher_than_2(read_file("src/day5/test_input.txt"));
+        let result = count_non_diagonal_points_higher_than_2(
+            Day5::read_file("src/day5/test_input.txt").unwrap(),
+        );
 
         assert_eq!(5, result);
     }
 
     #[test]
     fn test_input_all() {
-        let result = count_all_points_higher_than_2(read_file("src/day5/test_input.txt"));
+        let result =
+            count_all_points_higher_than_2(Day5::read_file("src/day5/test_input.txt").unwrap());
 
         assert_eq!(12, result);
     }
 
     #[test]
-    fn result() {
-        println!("{}", get_answer_1());
-        assert_eq!(6572, get_answer_1());
-        println!("{}", get_answer_2());
-        assert_eq!(21466, get_answer_2());
+    fn test_render_heatmap() {
+        let input = Day5::read_file("src/day5/test_input.txt").unwrap();
+
+        let frame = render_heatmap(&input);
+
+        assert_golden("src/day5/test_heatmap.ppm", &to_ppm(&frame));
+        assert_golden("src/day5/test_heatmap.svg", &to_svg(&frame));
     }
 }
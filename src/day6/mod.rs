@@ -1,46 +1,103 @@
 use std::collections::HashMap;
-use std::fs::read_to_string;
 
-pub fn get_answer_1() -> usize {
-    grow(read_file("src/day6/input.txt"), 80)
+use crate::animate::{paint, Observer};
+use crate::input::{default_input_path, InputError};
+use crate::parse::{InputLine, ParseError};
+use crate::render::Color;
+use crate::solution::{Answer, Solution};
+
+const DAY: u8 = 6;
+
+pub fn get_answer_1() -> Result<usize, InputError> {
+    Ok(grow(Day6::read_file(default_input_path(DAY))?, 80))
 }
 
-pub fn get_answer_2() -> usize {
-    grow(read_file("src/day6/input.txt"), 256)
+pub fn get_answer_2() -> Result<usize, InputError> {
+    Ok(grow(Day6::read_file(default_input_path(DAY))?, 256))
 }
 
-fn read_file(path: &str) -> Vec<u8> {
-    let file_content = read_to_string(path).expect("hoppla");
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = DAY;
+
+    type Input = Vec<u8>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: Self::Input) -> Answer {
+        grow(input, 80).into()
+    }
 
-    file_content
-        .split(",")
-        .map(|str| str.parse::<u8>().unwrap())
+    fn part2(input: Self::Input) -> Answer {
+        grow(input, 256).into()
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<u8>, ParseError> {
+    let line = InputLine::new(DAY, 0, input.trim_end());
+
+    line.text
+        .split(',')
+        .map(|str| line.parse::<u8>(str, "a timer value"))
         .collect()
 }
 
 fn grow(input: Vec<u8>, days: i32) -> usize {
-    let mut fishies_by_days = group_values(input);
-
-    for _ in 0..days {
-        let zeros = fishies_by_days.get(&0).cloned().unwrap_or(0);
-        let sevens = fishies_by_days.get(&7).cloned().unwrap_or(0);
+    grow_observed(input, days, &mut ())
+}
 
-        fishies_by_days = fishies_by_days
-            .iter()
-            .map(|(n, number_of_fish)| match n {
-                &0 => (6, *number_of_fish + sevens),
-                &7 => (6, *number_of_fish + zeros),
-                n => (*n - 1, *number_of_fish),
-            })
-            .collect();
+/// Like `grow`, showing the number of fish per timer value after every day.
+pub fn grow_observed(
+    input: Vec<u8>,
+    days: i32,
+    observer: &mut impl Observer<HashMap<u8, usize>>,
+) -> usize {
+    let mut fishies_by_days = group_values(input);
+    observer.observe(0, &fishies_by_days);
 
-        fishies_by_days.insert(8, zeros);
+    for day in 0..days {
+        fishies_by_days = next_day(&fishies_by_days);
+        observer.observe(day as usize + 1, &fishies_by_days);
     }
 
     fishies_by_days.values().sum()
 }
 
-fn group_values(input: Vec<u8>) -> HashMap<u8, usize> {
+pub(crate) fn next_day(fishies_by_days: &HashMap<u8, usize>) -> HashMap<u8, usize> {
+    let zeros = fishies_by_days.get(&0).cloned().unwrap_or(0);
+    let sevens = fishies_by_days.get(&7).cloned().unwrap_or(0);
+
+    let mut next_day = fishies_by_days
+        .iter()
+        .map(|(n, number_of_fish)| match n {
+            &0 => (6, *number_of_fish + sevens),
+            &7 => (6, *number_of_fish + zeros),
+            n => (*n - 1, *number_of_fish),
+        })
+        .collect::<HashMap<u8, usize>>();
+
+    next_day.insert(8, zeros);
+    next_day
+}
+
+/// One bar per timer value, scaled to the largest group.
+pub fn draw_fish(fishies_by_days: &HashMap<u8, usize>) -> String {
+    let maximum = fishies_by_days.values().copied().max().unwrap_or(0).max(1);
+
+    (0..=8)
+        .map(|timer| {
+            let count = fishies_by_days.get(&timer).copied().unwrap_or(0);
+            let bar = "\u{2588}".repeat((count * 60).div_ceil(maximum));
+            format!("{} {} {}", timer, paint(&bar, Color::GOLD), count)
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+pub(crate) fn group_values(input: Vec<u8>) -> HashMap<u8, usize> {
     input.iter().fold(HashMap::new(), |mut map, n| {
         map.entry(*n).and_modify(|v| *v += 1).or_insert(1);
         map
@@ -53,15 +110,23 @@ mod tests {
 
     #[test]
     fn test_read_file() {
-        let lines = read_file("src/day6/input.txt");
+        let lines = Day6::read_file("src/day6/input.txt").unwrap();
 
         assert_eq!(Some(&3), lines.first());
         assert_eq!(Some(&4), lines.last());
     }
 
+    #[test]
+    fn test_parse_error() {
+        let error = parse("3,4,3,1,-2\n").unwrap_err();
+
+        assert_eq!((6, 1, 9), (error.day, error.line, error.column));
+        assert_eq!("-2", error.text);
+    }
+
     #[test]
     fn test_grow() {
-        let input = read_file("src/day6/test_input.txt");
+        let input = Day6::read_file("src/day6/test_input.txt").unwrap();
 
         assert_eq!(5, grow(input.clone(), 1));
         assert_eq!(26, grow(input.clone(), 18));
@@ -70,10 +135,27 @@ mod tests {
     }
 
     #[test]
-    fn result() {
-        println!("{}", get_answer_1());
-        assert_eq!(351188, get_answer_1());
-        println!("{}", get_answer_2());
-        assert_eq!(1595779846729, get_answer_2());
+    fn test_grow_observed() {
+        let input = Day6::read_file("src/day6/test_input.txt").unwrap();
+        let mut totals = vec![];
+
+        let result = grow_observed(input, 3, &mut |day, fishies: &HashMap<u8, usize>| {
+            totals.push((day, fishies.values().sum::<usize>()))
+        });
+
+        assert_eq!(7, result);
+        assert_eq!(vec![(0, 5), (1, 5), (2, 6), (3, 7)], totals);
+    }
+
+    #[test]
+    fn test_draw_fish() {
+        let drawing = draw_fish(&HashMap::from([(1, 2), (3, 1)]));
+
+        assert_eq!(9, drawing.lines().count());
+        assert!(drawing.lines().nth(1).unwrap().ends_with(" 2"));
+        assert_eq!(
+            2,
+            drawing.lines().nth(1).unwrap().matches('\u{2588}').count() / 30
+        );
     }
 }
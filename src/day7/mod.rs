@@ -1,19 +1,47 @@
-use std::fs::read_to_string;
+use crate::input::{default_input_path, InputError};
+use crate::parse::{InputLine, ParseError};
+use crate::solution::{Answer, Solution};
 
-pub fn get_answer_1() -> usize {
-    compute_least_fuel_linear_consumption(read_file("src/day7/input.txt"))
+const DAY: u8 = 7;
+
+pub fn get_answer_1() -> Result<usize, InputError> {
+    Ok(compute_least_fuel_linear_consumption(Day7::read_file(
+        default_input_path(DAY),
+    )?))
 }
 
-pub fn get_answer_2() -> usize {
-    compute_least_fuel_non_linear_consumption(read_file("src/day7/input.txt"))
+pub fn get_answer_2() -> Result<usize, InputError> {
+    Ok(compute_least_fuel_non_linear_consumption(Day7::read_file(
+        default_input_path(DAY),
+    )?))
 }
 
-fn read_file(path: &str) -> Vec<i32> {
-    let file_content = read_to_string(path).expect("hoppla");
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u8 = DAY;
+
+    type Input = Vec<i32>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: Self::Input) -> Answer {
+        compute_least_fuel_linear_consumption(input).into()
+    }
+
+    fn part2(input: Self::Input) -> Answer {
+        compute_least_fuel_non_linear_consumption(input).into()
+    }
+}
 
-    file_content
-        .split(",")
-        .map(|str| str.parse::<i32>().unwrap())
+pub fn parse(input: &str) -> Result<Vec<i32>, ParseError> {
+    let line = InputLine::new(DAY, 0, input.trim_end());
+
+    line.text
+        .split(',')
+        .map(|str| line.parse::<i32>(str, "a position"))
         .collect()
 }
 
@@ -25,22 +53,20 @@ fn compute_least_fuel_non_linear_consumption(input: Vec<i32>) -> usize {
     compute_least_fuel_consumption(input, Box::from(compute_fuel_to_position_non_linear))
 }
 
-fn compute_least_fuel_consumption(
-    input: Vec<i32>,
-    compute_fn: Box<dyn Fn(&Vec<i32>, i32) -> i32>,
-) -> usize {
+type FuelFunction = Box<dyn Fn(&[i32], i32) -> i32>;
+
+fn compute_least_fuel_consumption(input: Vec<i32>, compute_fn: FuelFunction) -> usize {
     (0..=*input.iter().max().unwrap())
-        .into_iter()
         .map(|n| compute_fn(&input, n))
         .min()
         .unwrap() as usize
 }
 
-fn compute_fuel_to_position_linear(input: &Vec<i32>, position: i32) -> i32 {
+fn compute_fuel_to_position_linear(input: &[i32], position: i32) -> i32 {
     input.iter().map(|x| (x - position).abs()).sum()
 }
 
-fn compute_fuel_to_position_non_linear(input: &Vec<i32>, position: i32) -> i32 {
+fn compute_fuel_to_position_non_linear(input: &[i32], position: i32) -> i32 {
     input
         .iter()
         .map(|x| sum_of_1_to((x - position).abs()))
@@ -57,15 +83,23 @@ mod tests {
 
     #[test]
     fn test_read_file() {
-        let lines = read_file("src/day7/input.txt");
+        let lines = Day7::read_file("src/day7/input.txt").unwrap();
 
         assert_eq!(Some(&1101), lines.first());
         assert_eq!(Some(&82), lines.last());
     }
 
+    #[test]
+    fn test_parse_error() {
+        let error = parse("16,1,,0").unwrap_err();
+
+        assert_eq!((7, 1, 6), (error.day, error.line, error.column));
+        assert_eq!("expected a position", error.reason);
+    }
+
     #[test]
     fn test_linear() {
-        let input = read_file("src/day7/test_input.txt");
+        let input = Day7::read_file("src/day7/test_input.txt").unwrap();
 
         let result = compute_least_fuel_linear_consumption(input);
 
@@ -74,18 +108,10 @@ mod tests {
 
     #[test]
     fn test_non_linear() {
-        let input = read_file("src/day7/test_input.txt");
+        let input = Day7::read_file("src/day7/test_input.txt").unwrap();
 
         let result = compute_least_fuel_non_linear_consumption(input);
 
         assert_eq!(168, result);
     }
-
-    #[test]
-    fn result() {
-        println!("{}", get_answer_1());
-        assert_eq!(339321, get_answer_1());
-        println!("{}", get_answer_2());
-        assert_eq!(95476244, get_answer_2());
-    }
 }
@@ -1,31 +1,86 @@
 use std::collections::HashMap;
-use std::fs::read_to_string;
 
-pub fn get_answer_1() -> usize {
-    count_1_4_7_8(read_file("src/day8/input.txt"))
+use crate::input::{default_input_path, InputError};
+use crate::parse::{input_lines, InputLine, ParseError};
+use crate::solution::{Answer, Solution};
+
+const DAY: u8 = 8;
+
+pub fn get_answer_1() -> Result<usize, InputError> {
+    Ok(count_1_4_7_8(Day8::read_file(default_input_path(DAY))?))
 }
 
-pub fn get_answer_2() -> usize {
-    sum_display_values(read_file("src/day8/input.txt"))
+pub fn get_answer_2() -> Result<usize, InputError> {
+    Ok(sum_display_values(Day8::read_file(default_input_path(
+        DAY,
+    ))?))
 }
 
-fn read_file(path: &str) -> Vec<(Vec<String>, Vec<String>)> {
-    let file_content = read_to_string(path).expect("hoppla");
+/// The ten scrambled patterns of one display together with its four scrambled output digits.
+pub type Note = (Vec<String>, Vec<String>);
+
+pub struct Day8;
 
-    file_content.lines().map(parse_line).collect()
+impl Solution for Day8 {
+    const DAY: u8 = DAY;
+
+    type Input = Vec<Note>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: Self::Input) -> Answer {
+        count_1_4_7_8(input).into()
+    }
+
+    fn part2(input: Self::Input) -> Answer {
+        sum_display_values(input).into()
+    }
 }
 
-fn parse_line(line: &str) -> (Vec<String>, Vec<String>) {
-    match &line.split(" | ").collect::<Vec<&str>>()[..] {
-        [code, display] => (
-            code.split_whitespace().map(String::from).collect(),
-            display.split_whitespace().map(String::from).collect(),
-        ),
-        _ => panic!("unexpected input {:?}", line),
+pub fn parse(input: &str) -> Result<Vec<Note>, ParseError> {
+    input_lines(DAY, input).map(parse_line).collect()
+}
+
+fn parse_line(line: InputLine) -> Result<Note, ParseError> {
+    let (code, display) = line.split_once(" | ")?;
+
+    Ok((
+        parse_patterns(&line, code, 10)?,
+        parse_patterns(&line, display, 4)?,
+    ))
+}
+
+fn parse_patterns(
+    line: &InputLine,
+    patterns: &str,
+    expected_count: usize,
+) -> Result<Vec<String>, ParseError> {
+    let patterns_found = patterns
+        .split_whitespace()
+        .map(
+            |pattern| match pattern.chars().all(|c| ('a'..='g').contains(&c)) {
+                true => Ok(pattern.to_string()),
+                false => Err(line.error(pattern, "expected segments a to g")),
+            },
+        )
+        .collect::<Result<Vec<String>, ParseError>>()?;
+
+    match patterns_found.len() == expected_count {
+        true => Ok(patterns_found),
+        false => Err(line.error(
+            patterns,
+            format!(
+                "expected {} patterns, found {}",
+                expected_count,
+                patterns_found.len()
+            ),
+        )),
     }
 }
 
-fn count_1_4_7_8(input: Vec<(Vec<String>, Vec<String>)>) -> usize {
+fn count_1_4_7_8(input: Vec<Note>) -> usize {
     input
         .iter()
         .flat_map(|(_, display)| display.iter())
@@ -33,11 +88,11 @@ fn count_1_4_7_8(input: Vec<(Vec<String>, Vec<String>)>) -> usize {
         .count()
 }
 
-fn sum_display_values(input: Vec<(Vec<String>, Vec<String>)>) -> usize {
+fn sum_display_values(input: Vec<Note>) -> usize {
     input.into_iter().map(solve_line).sum()
 }
 
-fn solve_line((code, display): (Vec<String>, Vec<String>)) -> usize {
+pub(crate) fn solve_line((code, display): Note) -> usize {
     let mut code = Code::new(code);
 
     String::from_iter(display.into_iter().map(|digit| code.determine(digit)))
@@ -116,7 +171,7 @@ fn split_off_code_containing(input_codes: &mut Vec<String>, code: &str) -> Strin
         .iter()
         .cloned()
         .enumerate()
-        .find(|(_, c)| c.contains_code(&code))
+        .find(|(_, c)| c.contains_code(code))
         .unwrap();
     input_codes.remove(index);
     found
@@ -127,7 +182,7 @@ fn split_off_code_that_contains(input_codes: &mut Vec<String>, code: &str) -> St
         .iter()
         .cloned()
         .enumerate()
-        .find(|(_, c)| code.contains_code(&c))
+        .find(|(_, c)| code.contains_code(c))
         .unwrap();
     input_codes.remove(index);
     found
@@ -145,7 +200,7 @@ impl ContainsCode for String {
                 return false;
             }
         }
-        return true;
+        true
     }
 }
 
@@ -157,17 +212,19 @@ impl ContainsCode for &str {
                 return false;
             }
         }
-        return true;
+        true
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use rstest::*;
+
     use crate::day8::*;
 
     #[test]
     fn test_read_file() {
-        let lines = read_file("src/day8/input.txt");
+        let lines = Day8::read_file("src/day8/input.txt").unwrap();
 
         let (first_code, first_display) = lines.first().unwrap();
         assert_eq!(Some(&"bgcfda".to_string()), first_code.first());
@@ -178,9 +235,31 @@ mod tests {
         assert_eq!(Some(&"bg".to_string()), last_display.last());
     }
 
+    #[rstest]
+    #[case(
+        "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab cdfeb fcadb cdfeb cdbaf",
+        1,
+        1
+    )]
+    #[case(
+        "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb",
+        1,
+        62
+    )]
+    #[case(
+        "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadh cdfeb cdbaf",
+        1,
+        68
+    )]
+    fn test_parse_error(#[case] input: &str, #[case] line: usize, #[case] column: usize) {
+        let error = parse(input).unwrap_err();
+
+        assert_eq!((8, line, column), (error.day, error.line, error.column));
+    }
+
     #[test]
     fn test_count() {
-        let input = read_file("src/day8/test_input.txt");
+        let input = Day8::read_file("src/day8/test_input.txt").unwrap();
 
         let result = count_1_4_7_8(input);
 
@@ -189,7 +268,7 @@ mod tests {
 
     #[test]
     fn test_sum_everything() {
-        let input = read_file("src/day8/test_input.txt");
+        let input = Day8::read_file("src/day8/test_input.txt").unwrap();
 
         let expected = vec![8394, 9781, 1197, 9361, 4873, 8418, 4548, 1625, 8717, 4315];
 
@@ -204,12 +283,4 @@ mod tests {
 
         assert_eq!(61229, result);
     }
-
-    #[test]
-    fn result() {
-        println!("{}", get_answer_1());
-        assert_eq!(369, get_answer_1());
-        println!("{}", get_answer_2());
-        assert_eq!(1031553, get_answer_2());
-    }
 }
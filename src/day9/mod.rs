@@ -1,139 +1,149 @@
-use std::collections::HashMap;
-use std::fs::read_to_string;
+use crate::geometry::Point2;
+use crate::grid::Grid;
+use crate::input::{default_input_path, InputError};
+use crate::parse::ParseError;
+use crate::render::{Color, Frame};
+use crate::solution::{Answer, Solution};
+
+const DAY: u8 = 9;
+
+pub fn get_answer_1() -> Result<usize, InputError> {
+    Ok(sum_low_point_risk(Day9::read_file(default_input_path(
+        DAY,
+    ))?))
+}
 
-use nalgebra::{DMatrix, RowDVector};
+pub fn get_answer_2() -> Result<usize, InputError> {
+    Ok(find_3_largest_basins(Day9::read_file(default_input_path(
+        DAY,
+    ))?))
+}
 
-use crate::day9::Direction::{Down, Left, Right, Up};
+pub struct Day9;
 
-pub fn get_answer_1() -> usize {
-    sum_low_point_risk(read_file("src/day9/input.txt"))
-}
+impl Solution for Day9 {
+    const DAY: u8 = DAY;
 
-pub fn get_answer_2() -> usize {
-    find_3_largest_basins(read_file("src/day9/input.txt"))
-}
+    type Input = Grid<usize>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
 
-fn read_file(path: &str) -> DMatrix<usize> {
-    let file_content = read_to_string(path).expect("hoppla");
+    fn part1(input: Self::Input) -> Answer {
+        sum_low_point_risk(input).into()
+    }
 
-    DMatrix::from_rows(
-        &file_content
-            .lines()
-            .map(parse_line)
-            .collect::<Vec<RowDVector<usize>>>(),
-    )
+    fn part2(input: Self::Input) -> Answer {
+        find_3_largest_basins(input).into()
+    }
 }
 
-fn parse_line(line: &str) -> RowDVector<usize> {
-    RowDVector::from_iterator(
-        line.len(),
-        line.chars()
-            .map(|c| c.to_string().parse::<usize>().unwrap()),
-    )
+pub fn parse(input: &str) -> Result<Grid<usize>, ParseError> {
+    Grid::parse_digits(DAY, input)
 }
 
-fn sum_low_point_risk(input: DMatrix<usize>) -> usize {
-    let matrix = to_hash_map(input);
-
-    matrix
+fn sum_low_point_risk(input: Grid<usize>) -> usize {
+    input
         .iter()
-        .filter(|((i, j), height)| is_low_point(&matrix, *i, *j, height))
+        .filter(|(position, height)| is_low_point(&input, *position, height))
         .map(|(_, height)| height + 1)
         .sum()
 }
 
-fn to_hash_map(input: DMatrix<usize>) -> HashMap<Point, usize> {
-    let (rows, columns) = input.shape();
-
-    let mut matrix = HashMap::new();
-    for j in 0..rows {
-        for i in 0..columns {
-            matrix.insert((i as isize, j as isize), input[(j, i)]);
-        }
-    }
-    matrix
+fn is_low_point(input: &Grid<usize>, position: Point2, height: &usize) -> bool {
+    input
+        .neighbours_4(position)
+        .all(|neighbour| height < &input[neighbour])
 }
 
-fn is_low_point(input: &HashMap<Point, usize>, row: isize, column: isize, height: &usize) -> bool {
-    let left = input.get(&(row - 1, column)).unwrap_or(&10);
-    let right = input.get(&(row + 1, column)).unwrap_or(&10);
-    let top = input.get(&(row, column + 1)).unwrap_or(&10);
-    let bottom = input.get(&(row, column - 1)).unwrap_or(&10);
+fn find_3_largest_basins(input: Grid<usize>) -> usize {
+    let mut basins = find_basins(&input)
+        .iter()
+        .map(Vec::len)
+        .collect::<Vec<usize>>();
 
-    height < &left && height < right && height < top && height < &bottom
+    basins.sort();
+    basins.iter().rev().take(3).product()
 }
 
-fn find_3_largest_basins(input: DMatrix<usize>) -> usize {
-    let matrix = to_hash_map(input);
-
-    let low_points: HashMap<Point, usize> = matrix
+fn find_basins(input: &Grid<usize>) -> Vec<Vec<Point2>> {
+    let low_points: Vec<Point2> = input
         .iter()
-        .filter(|((i, j), height)| is_low_point(&matrix, *i, *j, height))
-        .map(|(p, height)| (*p, *height))
+        .filter(|(position, height)| is_low_point(input, *position, height))
+        .map(|(position, _)| position)
         .collect();
 
     let mut covered_points = vec![];
     let mut basins = vec![];
-    for (low_point, _) in low_points {
+    for low_point in low_points {
         if covered_points.contains(&low_point) {
             continue;
         }
-        let basin_points =
-            BasinIterator::from_point(low_point, matrix.clone()).collect::<Vec<Point>>();
+        let basin_points = BasinIterator::from_point(low_point, input).collect::<Vec<Point2>>();
 
         covered_points.append(&mut basin_points.clone());
-        basins.push(basin_points.len());
+        basins.push(basin_points);
     }
 
-    basins.sort();
-    basins.iter().rev().take(3).product()
+    basins
 }
 
-type Point = (isize, isize);
+/// Every basin in its own color, its low point in white and the ridges of height 9 in black.
+pub fn render_basins(input: &Grid<usize>) -> Frame {
+    let mut frame = input.map(|_| Color::BLACK);
+
+    for (index, basin) in find_basins(input).iter().enumerate() {
+        for point in basin {
+            frame[*point] = Color::palette(index);
+        }
+        frame[basin[0]] = Color::WHITE;
+    }
+
+    frame
+}
 
 #[derive(Debug)]
-struct BasinIterator {
-    matrix: HashMap<Point, usize>,
-    origin: Point,
-    directions_to_cover: Vec<Direction>,
-    inner: InnerState,
-    already_covered: Vec<Point>,
+struct BasinIterator<'a> {
+    matrix: &'a Grid<usize>,
+    origin: Point2,
+    neighbours_to_cover: Vec<Point2>,
+    inner: InnerState<'a>,
+    already_covered: Vec<Point2>,
 }
 
-impl BasinIterator {
-    fn from_point(origin: Point, matrix: HashMap<Point, usize>) -> BasinIterator {
+impl<'a> BasinIterator<'a> {
+    fn from_point(origin: Point2, matrix: &'a Grid<usize>) -> BasinIterator<'a> {
         BasinIterator {
             origin,
             matrix,
-            directions_to_cover: vec![Up, Left, Down, Right],
+            neighbours_to_cover: matrix.neighbours_4(origin).collect(),
             inner: InnerState::NoneYet,
             already_covered: vec![],
         }
     }
 
-    fn should_skip_point(&self, point: &Point) -> bool {
-        matches!(self.matrix.get(point), None | Some(9)) || self.already_covered.contains(point)
+    fn should_skip_point(&self, point: &Point2) -> bool {
+        matches!(self.matrix.get(*point), None | Some(9)) || self.already_covered.contains(point)
     }
 
-    fn get_next_inner_iterator(&self, next: Point) -> InnerState {
+    fn get_next_inner_iterator(&self, next: Point2) -> InnerState<'a> {
         InnerState::Inner(Box::from(BasinIterator {
-            matrix: self.matrix.clone(),
+            matrix: self.matrix,
             origin: next,
-            directions_to_cover: vec![Up, Left, Down, Right],
+            neighbours_to_cover: self.matrix.neighbours_4(next).collect(),
             inner: InnerState::NoneYet,
             already_covered: self.already_covered.clone(),
         }))
     }
 
-    fn handle_origin(&mut self) -> Option<Point> {
-        match self.directions_to_cover.pop() {
+    fn handle_origin(&mut self) -> Option<Point2> {
+        match self.neighbours_to_cover.pop() {
             None => {
                 self.already_covered.push(self.origin);
                 self.inner = InnerState::NoneLeft;
             }
-            Some(next_direction) => {
-                let next = next_direction.get_moved_point(self.origin);
-
+            Some(next) => {
                 if self.should_skip_point(&next) {
                     return self.next();
                 }
@@ -145,14 +155,12 @@ impl BasinIterator {
         Some(self.origin)
     }
 
-    fn handle_inner_iter_next(&mut self, next: Option<Point>) -> Option<Point> {
+    fn handle_inner_iter_next(&mut self, next: Option<Point2>) -> Option<Point2> {
         match next {
             Some(_) => next,
-            None => match self.directions_to_cover.pop() {
+            None => match self.neighbours_to_cover.pop() {
                 None => None,
-                Some(next_direction) => {
-                    let next = next_direction.get_moved_point(self.origin);
-
+                Some(next) => {
                     if self.should_skip_point(&next) {
                         return self.next();
                     }
@@ -165,8 +173,8 @@ impl BasinIterator {
     }
 }
 
-impl Iterator for BasinIterator {
-    type Item = Point;
+impl Iterator for BasinIterator<'_> {
+    type Item = Point2;
 
     fn next(&mut self) -> Option<Self::Item> {
         match &mut self.inner {
@@ -176,8 +184,8 @@ impl Iterator for BasinIterator {
                 let next = inner.next();
 
                 for point in &inner.already_covered {
-                    if !self.already_covered.contains(&point) {
-                        self.already_covered.push(point.clone());
+                    if !self.already_covered.contains(point) {
+                        self.already_covered.push(*point);
                     }
                 }
 
@@ -188,46 +196,49 @@ impl Iterator for BasinIterator {
 }
 
 #[derive(Debug)]
-enum InnerState {
+enum InnerState<'a> {
     NoneYet,
     NoneLeft,
-    Inner(Box<BasinIterator>),
-}
-
-#[derive(Debug)]
-enum Direction {
-    Up,
-    Left,
-    Down,
-    Right,
-}
-
-impl Direction {
-    fn get_moved_point(&self, (i, j): Point) -> Point {
-        match self {
-            Up => (i, j + 1),
-            Left => (i - 1, j),
-            Down => (i, j - 1),
-            Right => (i + 1, j),
-        }
-    }
+    Inner(Box<BasinIterator<'a>>),
 }
 
 #[cfg(test)]
 mod tests {
+    use rstest::*;
+
     use crate::day9::*;
+    use crate::render::tests::assert_golden;
+    use crate::render::to_ppm;
 
     #[test]
     fn test_read_file() {
-        let matrix = read_file("src/day9/input.txt");
+        let matrix = Day9::read_file("src/day9/input.txt").unwrap();
 
-        assert_eq!(6, matrix[(0, 0)]);
-        assert_eq!(7, matrix[(99, 99)]);
+        assert_eq!(6, matrix[Point2::new(0, 0)]);
+        assert_eq!(7, matrix[Point2::new(99, 99)]);
+        assert_eq!((100, 100), (matrix.width(), matrix.height()));
+    }
+
+    #[rstest]
+    #[case("2199943210\n398789921\n", 2, 1, "398789921")]
+    #[case("2199943210\n39878992x1\n", 2, 9, "x")]
+    fn test_parse_error(
+        #[case] input: &str,
+        #[case] line: usize,
+        #[case] column: usize,
+        #[case] text: &str,
+    ) {
+        let error = parse(input).unwrap_err();
+
+        assert_eq!(
+            (9, line, column, text),
+            (error.day, error.line, error.column, error.text.as_str())
+        );
     }
 
     #[test]
     fn test_count() {
-        let input = read_file("src/day9/test_input.txt");
+        let input = Day9::read_file("src/day9/test_input.txt").unwrap();
 
         let result = sum_low_point_risk(input);
 
@@ -236,7 +247,7 @@ mod tests {
 
     #[test]
     fn test_sum_everything() {
-        let input = read_file("src/day9/test_input.txt");
+        let input = Day9::read_file("src/day9/test_input.txt").unwrap();
 
         let result = find_3_largest_basins(input);
 
@@ -244,10 +255,11 @@ mod tests {
     }
 
     #[test]
-    fn result() {
-        println!("{}", get_answer_1());
-        assert_eq!(508, get_answer_1());
-        println!("{}", get_answer_2());
-        assert_eq!(1564640, get_answer_2());
+    fn test_render_basins() {
+        let input = Day9::read_file("src/day9/test_input.txt").unwrap();
+
+        let frame = render_basins(&input);
+
+        assert_golden("src/day9/test_basins.ppm", &to_ppm(&frame));
     }
 }
@@ -0,0 +1,342 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::fs::{read_to_string, write};
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::answers::{load, AnswersError, Expectation};
+use crate::registry;
+use crate::solution::{Answer, Part};
+
+/// The input name of extracted examples, so they live in `src/dayN/example.txt`.
+pub const EXAMPLE: &str = "example";
+
+const MANIFEST_HEADER: &str =
+    "# Example answers extracted from saved puzzle descriptions by the extract command, in the
+# format of answers.toml. The inputs are in src/dayN/example.txt.
+";
+
+#[derive(Debug)]
+pub enum ExtractError {
+    NoDay,
+    NoExample,
+    Io { path: PathBuf, source: io::Error },
+    Manifest(AnswersError),
+}
+
+impl Display for ExtractError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExtractError::NoDay => write!(f, "found no \"--- Day N: ... ---\" heading"),
+            ExtractError::NoExample => {
+                write!(f, "found no <pre><code> example the solver can parse")
+            }
+            ExtractError::Io { path, source } => write!(f, "{}: {}", path.display(), source),
+            ExtractError::Manifest(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl Error for ExtractError {}
+
+/// The example input of a puzzle and the answers the description gives for it.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Example {
+    pub day: u8,
+    pub input: String,
+    pub answers: Vec<(Part, Answer)>,
+}
+
+/// The checked-in `examples.toml` in the crate root.
+pub fn default_examples_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("examples.toml")
+}
+
+/// Reads the example of a saved puzzle page. Every part is an `<article>`. The example is the
+/// longest `<pre><code>` block of the first one that the solver of the day can parse, which skips
+/// diagrams and intermediate states and prefers the largest of several examples. The answer of a
+/// part is the last emphasised value in a `<code>`, which the description gives for that example.
+pub fn extract(html: &str) -> Result<Example, ExtractError> {
+    let day = between(html, "--- Day ", ":")
+        .and_then(|day| day.trim().parse().ok())
+        .ok_or(ExtractError::NoDay)?;
+    let articles = elements(html, "<article", "</article>");
+    let blocks = articles
+        .first()
+        .map(|article| elements(article, "<pre><code>", "</code></pre>"))
+        .unwrap_or_default()
+        .into_iter()
+        .map(text)
+        .collect::<Vec<String>>();
+
+    let input = match registry::solver(day) {
+        Some(solver) => blocks
+            .into_iter()
+            .filter(|block| solver.parse(block).is_ok())
+            .rev()
+            .max_by_key(|block| block.len()),
+        None => blocks.into_iter().next(),
+    }
+    .ok_or(ExtractError::NoExample)?;
+    let answers = articles
+        .iter()
+        .zip(Part::BOTH)
+        .filter_map(|(article, part)| Some((part, emphasised_answer(article)?)))
+        .collect();
+
+    Ok(Example {
+        day,
+        input,
+        answers,
+    })
+}
+
+fn emphasised_answer(article: &str) -> Option<Answer> {
+    let prose = elements(article, "<pre><code>", "</code></pre>")
+        .into_iter()
+        .fold(article.to_string(), |prose, block| prose.replace(block, ""));
+
+    let answer = elements(&prose, "<code>", "</code>")
+        .into_iter()
+        .rev()
+        .find_map(|code| elements(code, "<em>", "</em>").last().copied())
+        .or_else(|| {
+            elements(&prose, "<em>", "</em>")
+                .into_iter()
+                .rev()
+                .find(|em| text(em).parse::<i64>().is_ok())
+        })?;
+
+    Some(text(answer).parse().unwrap())
+}
+
+/// The contents of all non-nested elements from `open` to `close`.
+fn elements<'a>(html: &'a str, open: &str, close: &str) -> Vec<&'a str> {
+    let mut elements = vec![];
+    let mut rest = html;
+    while let Some(start) = rest.find(open) {
+        let content = &rest[start + open.len()..];
+        let content = match open.ends_with('>') {
+            true => content,
+            false => &content[content.find('>').map_or(0, |end| end + 1)..],
+        };
+        match content.find(close) {
+            Some(end) => {
+                elements.push(&content[..end]);
+                rest = &content[end + close.len()..];
+            }
+            None => break,
+        }
+    }
+    elements
+}
+
+fn between<'a>(html: &'a str, start: &str, end: &str) -> Option<&'a str> {
+    let rest = &html[html.find(start)? + start.len()..];
+    Some(&rest[..rest.find(end)?])
+}
+
+/// Drops the tags and decodes the entities AoC uses.
+fn text(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for char in html.chars() {
+        match char {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(char),
+            _ => {}
+        }
+    }
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Writes the example input to `src/dayN/example.txt` under the crate `root` and its answers to
+/// `manifest`, replacing earlier answers of that day. Returns the written files.
+pub fn save(root: &Path, manifest: &Path, example: &Example) -> Result<Vec<PathBuf>, ExtractError> {
+    let mut expectations = match manifest.exists() {
+        true => load(manifest).map_err(ExtractError::Manifest)?,
+        false => vec![],
+    };
+    expectations.retain(|expectation| expectation.day != example.day);
+    expectations.extend(example.answers.iter().map(|(part, answer)| Expectation {
+        input: EXAMPLE.to_string(),
+        day: example.day,
+        part: *part,
+        answer: answer.clone(),
+    }));
+    expectations.sort_by_key(|expectation| (expectation.day, expectation.part.number()));
+
+    let input_path = root.join(format!("src/day{}/{}.txt", example.day, EXAMPLE));
+    let mut input = example.input.clone();
+    if !input.ends_with('\n') {
+        input.push('\n');
+    }
+    for (path, content) in [
+        (&input_path, input),
+        (&manifest.to_path_buf(), render_manifest(&expectations)),
+    ] {
+        write(path, content).map_err(|source| ExtractError::Io {
+            path: path.clone(),
+            source,
+        })?;
+    }
+
+    Ok(vec![input_path, manifest.to_path_buf()])
+}
+
+fn render_manifest(expectations: &[Expectation]) -> String {
+    let mut manifest = MANIFEST_HEADER.to_string();
+    let mut previous = None;
+    for expectation in expectations {
+        if previous != Some((&expectation.input, expectation.day)) {
+            manifest.push_str(&format!(
+                "\n[{}.day{}]\n",
+                expectation.input, expectation.day
+            ));
+            previous = Some((&expectation.input, expectation.day));
+        }
+        match &expectation.answer {
+            Answer::Number(number) => {
+                manifest.push_str(&format!("part{} = {}\n", expectation.part, number))
+            }
+            Answer::Text(text) => {
+                manifest.push_str(&format!("part{} = '''\n{}'''\n", expectation.part, text))
+            }
+        }
+    }
+    manifest
+}
+
+pub fn read_html(path: &Path) -> Result<String, ExtractError> {
+    read_to_string(path).map_err(|source| ExtractError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env::temp_dir;
+    use std::fs::{create_dir_all, remove_dir_all};
+
+    use crate::answers::{verify, Outcome};
+    use crate::examples::*;
+    use crate::runner::solve;
+
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 7: The Treachery of Whales ---</h2>
+<p>For example, consider the following positions:</p>
+<pre><code>16,1,2,0,4,2,7,1,2,14
+</code></pre>
+<p>Moving to <code>2</code> costs:</p>
+<pre><code>- Move from <em>16</em> to 2: 14 fuel
+</code></pre>
+<p>This costs a total of <code><em>37</em></code> fuel.</p>
+</article>
+<p>Your puzzle answer was <code>339321</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>This costs a total of <code><em>168</em></code> fuel, &lt;much&gt; more.</p>
+</article>
+</main>"#;
+
+    #[test]
+    fn test_extract() {
+        let example = extract(PAGE).unwrap();
+
+        assert_eq!(
+            Example {
+                day: 7,
+                input: "16,1,2,0,4,2,7,1,2,14\n".to_string(),
+                answers: vec![
+                    (Part::One, Answer::Number(37)),
+                    (Part::Two, Answer::Number(168))
+                ],
+            },
+            example
+        );
+        assert_eq!(
+            Ok(Answer::Number(37)),
+            solve(7, Part::One, &example.input).map_err(|error| error.to_string())
+        );
+    }
+
+    #[test]
+    fn test_extract_longest_block_and_entities() {
+        let page = "<h2>--- Day 10: Syntax Scoring ---</h2><article>\
+            <pre><code>[({(&lt;</code></pre>\
+            <pre><code>[({(&lt;(())[]&gt;[[{[]{&lt;()&lt;&gt;&gt;\n[(()[&lt;&gt;])]({[&lt;{&lt;&lt;[]&gt;&gt;(\n</code></pre>\
+            <p>The score is <em>26397</em> points, <em>only</em> once.</p></article>";
+
+        let example = extract(page).unwrap();
+
+        assert_eq!(10, example.day);
+        assert_eq!(
+            "[({(<(())[]>[[{[]{<()<>>\n[(()[<>])]({[<{<<[]>>(\n",
+            example.input
+        );
+        assert_eq!(vec![(Part::One, Answer::Number(26397))], example.answers);
+    }
+
+    #[test]
+    fn test_extract_without_example() {
+        assert!(matches!(extract("<p>hello</p>"), Err(ExtractError::NoDay)));
+        assert!(matches!(
+            extract("<article><h2>--- Day 3: Binary Diagnostic ---</h2></article>"),
+            Err(ExtractError::NoExample)
+        ));
+    }
+
+    #[test]
+    fn test_saved_examples_are_verified() {
+        let root = temp_dir().join(format!("aoc2021-examples-{}", std::process::id()));
+        create_dir_all(root.join("src/day7")).unwrap();
+        let manifest = root.join("examples.toml");
+        write(
+            &manifest,
+            "[example.day7]\npart1 = 1\n\n[example.day13]\npart1 = 17\n",
+        )
+        .unwrap();
+
+        let files = save(&root, &manifest, &extract(PAGE).unwrap()).unwrap();
+
+        let expectations = load(&manifest).unwrap();
+        assert_eq!(2, files.len());
+        assert_eq!(
+            vec![
+                (7, Answer::Number(37)),
+                (7, Answer::Number(168)),
+                (13, Answer::Number(17))
+            ],
+            expectations
+                .iter()
+                .map(|expectation| (expectation.day, expectation.answer.clone()))
+                .collect::<Vec<(u8, Answer)>>()
+        );
+        assert_eq!(
+            "16,1,2,0,4,2,7,1,2,14\n",
+            read_to_string(root.join("src/day7/example.txt")).unwrap()
+        );
+        remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_examples() {
+        for expectation in load(default_examples_path()).unwrap() {
+            let check = verify(&expectation);
+
+            assert_eq!(
+                Outcome::Correct,
+                check.outcome,
+                "{} day {} part {}",
+                expectation.input,
+                expectation.day,
+                expectation.part
+            );
+        }
+    }
+}
@@ -0,0 +1,991 @@
+use std::collections::{HashSet, VecDeque};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::ops::RangeInclusive;
+
+use crate::day11;
+use crate::day16::{self, Operator, Packet};
+use crate::geometry::Point2;
+use crate::grid::Grid;
+
+/// SplitMix64. Small and good enough for puzzle inputs, and the same seed gives the same input everywhere.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..n`, `n` must not be 0.
+    pub fn below(&mut self, n: usize) -> usize {
+        ((self.next_u64() as u128 * n as u128) >> 64) as usize
+    }
+
+    pub fn range(&mut self, range: RangeInclusive<usize>) -> usize {
+        range.start() + self.below(range.end() - range.start() + 1)
+    }
+
+    pub fn chance(&mut self, numerator: usize, denominator: usize) -> bool {
+        self.below(denominator) < numerator
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+/// How often a generator draws again when its candidate would not be a valid puzzle input.
+const ATTEMPTS: usize = 100;
+
+/// What `size` and `width` mean depends on the day, see [`Params::puzzle`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Params {
+    pub seed: u64,
+    pub size: usize,
+    pub width: usize,
+}
+
+impl Params {
+    /// Roughly the dimensions of the real puzzle input.
+    ///
+    /// | day | size | width |
+    /// |-----|------|-------|
+    /// | 1, 2 | lines | - |
+    /// | 3 | lines | bits per line |
+    /// | 4 | boards | largest drawn number + 1 |
+    /// | 5 | lines | largest coordinate + 1 |
+    /// | 6 | fish | - |
+    /// | 7 | crabs | largest position + 1 |
+    /// | 8 | notes | - |
+    /// | 9, 11, 15 | rows | columns |
+    /// | 10 | lines | longest line |
+    /// | 12 | caves besides start and end | - |
+    /// | 13 | dots | folds |
+    /// | 14 | template length | elements |
+    /// | 16 | packets | - |
+    /// | 17 | farthest x | deepest y below 0 |
+    /// | 18 | numbers | - |
+    pub fn puzzle(day: u8, seed: u64) -> Option<Params> {
+        let (size, width) = match day {
+            1 => (2000, 0),
+            2 => (1000, 0),
+            3 => (1000, 12),
+            4 => (100, 100),
+            5 => (500, 1000),
+            6 => (300, 0),
+            7 => (1000, 2000),
+            8 => (200, 0),
+            9 => (100, 100),
+            10 => (100, 110),
+            11 => (10, 10),
+            12 => (12, 0),
+            13 => (900, 12),
+            14 => (20, 10),
+            15 => (100, 100),
+            16 => (250, 0),
+            17 => (300, 100),
+            18 => (100, 0),
+            _ => return None,
+        };
+
+        Some(Params { seed, size, width })
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum GenerateError {
+    Unsupported(u8),
+    Invalid(String),
+}
+
+impl Display for GenerateError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GenerateError::Unsupported(day) => {
+                write!(f, "there is no generator for day {}", day)
+            }
+            GenerateError::Invalid(reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
+impl Error for GenerateError {}
+
+/// A random input that the parser of `day` accepts and its solver can answer.
+///
+/// Day 11 part 2 only ends once all octopuses flash together, which random grids
+/// reach quickly in practice, but nothing guarantees it.
+pub fn generate(day: u8, params: Params) -> Result<String, GenerateError> {
+    let mut rng = Rng::new(params.seed);
+    let Params { size, width, .. } = params;
+
+    let require = |valid: bool, reason: &str| match valid {
+        true => Ok(()),
+        false => Err(GenerateError::Invalid(format!("day {}: {}", day, reason))),
+    };
+    require(size > 0, "size must be at least 1")?;
+    let found = |input: Option<String>| {
+        input.ok_or_else(|| {
+            GenerateError::Invalid(format!(
+                "day {}: no valid input after {} attempts, try another size",
+                day, ATTEMPTS
+            ))
+        })
+    };
+
+    Ok(match day {
+        1 => depths(&mut rng, size),
+        2 => commands(&mut rng, size),
+        3 => {
+            require((1..64).contains(&width), "width must be between 1 and 63")?;
+            require(
+                size <= 1 << width,
+                "size must not exceed the number of distinct bit strings",
+            )?;
+            found(bit_strings(&mut rng, size, width))?
+        }
+        4 => {
+            require(width >= 25, "width must leave 25 numbers per board")?;
+            bingo(&mut rng, size, width)
+        }
+        5 => {
+            require(width >= 2, "width must be at least 2")?;
+            vent_lines(&mut rng, size, width)
+        }
+        6 => fish(&mut rng, size),
+        7 => {
+            require(width > 0, "width must be at least 1")?;
+            crabs(&mut rng, size, width)
+        }
+        8 => (0..size)
+            .map(|_| seven_segment_note(&mut rng))
+            .collect::<Vec<String>>()
+            .join("\n"),
+        9 => {
+            require(width > 0, "width must be at least 1")?;
+            height_map(&mut rng, size, width)
+        }
+        11 => {
+            require(width > 0, "width must be at least 1")?;
+            found(synchronising_grid(&mut rng, size, width))?
+        }
+        10 => {
+            require(width >= 2, "width must be at least 2")?;
+            navigation_lines(&mut rng, size, width)
+        }
+        12 => cave_graph(&mut rng, size),
+        13 => transparent_paper(&mut rng, size, width),
+        14 => {
+            require((1..=26).contains(&width), "width must be between 1 and 26")?;
+            polymer(&mut rng, size, width)
+        }
+        15 => {
+            require(width > 0, "width must be at least 1")?;
+            risk_map(&mut rng, size, width)
+        }
+        16 => found(transmission(&mut rng, size))?,
+        17 => {
+            require(width > 0, "width must be at least 1")?;
+            target_area(&mut rng, size, width)
+        }
+        18 => (0..size)
+            .map(|_| snailfish_number(&mut rng, 0))
+            .collect::<Vec<String>>()
+            .join("\n"),
+        day => return Err(GenerateError::Unsupported(day)),
+    })
+}
+
+fn depths(rng: &mut Rng, size: usize) -> String {
+    let mut depth = rng.range(100..=200);
+
+    (0..size)
+        .map(|_| {
+            depth = (depth + rng.range(0..=20)).saturating_sub(8);
+            depth.to_string()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Never goes up further than the current depth, so the submarine stays below the surface.
+fn commands(rng: &mut Rng, size: usize) -> String {
+    let mut depth = 0;
+
+    (0..size)
+        .map(|_| {
+            let amount = rng.range(1..=9);
+            match rng.below(3) {
+                0 => format!("forward {}", amount),
+                1 if amount <= depth => {
+                    depth -= amount;
+                    format!("up {}", amount)
+                }
+                _ => {
+                    depth += amount;
+                    format!("down {}", amount)
+                }
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Distinct numbers, drawn again until looking for the CO2 scrubber rating never leaves
+/// a column with the same bit in all remaining numbers, which has no least common bit.
+fn bit_strings(rng: &mut Rng, size: usize, width: usize) -> Option<String> {
+    (0..ATTEMPTS).find_map(|_| {
+        let mut seen = HashSet::new();
+        let mut numbers = vec![];
+        while numbers.len() < size {
+            let number = rng.next_u64() >> (64 - width);
+            if seen.insert(number) {
+                numbers.push(number);
+            }
+        }
+
+        has_least_common_bits(numbers.clone(), width).then(|| {
+            numbers
+                .iter()
+                .map(|number| format!("{:0width$b}", number, width = width))
+                .collect::<Vec<String>>()
+                .join("\n")
+        })
+    })
+}
+
+fn has_least_common_bits(mut numbers: Vec<u64>, width: usize) -> bool {
+    for position in (0..width).rev() {
+        if numbers.len() == 1 {
+            break;
+        }
+
+        let ones = numbers
+            .iter()
+            .filter(|number| *number >> position & 1 == 1)
+            .count();
+        if ones == 0 || ones == numbers.len() {
+            return false;
+        }
+
+        let least_common = (2 * ones < numbers.len()) as u64;
+        numbers.retain(|number| number >> position & 1 == least_common);
+    }
+    true
+}
+
+/// Every number up to `width` gets drawn, so every board wins eventually.
+fn bingo(rng: &mut Rng, boards: usize, width: usize) -> String {
+    let mut draws = (0..width).collect::<Vec<usize>>();
+    rng.shuffle(&mut draws);
+    let mut input = join(&draws, ",");
+
+    for _ in 0..boards {
+        let mut numbers = (0..width).collect::<Vec<usize>>();
+        rng.shuffle(&mut numbers);
+        input.push('\n');
+        for row in numbers[..25].chunks(5) {
+            let row = row
+                .iter()
+                .map(|number| format!("{:>2}", number))
+                .collect::<Vec<String>>();
+            input.push('\n');
+            input.push_str(&row.join(" "));
+        }
+    }
+
+    input
+}
+
+const DIRECTIONS: [(isize, isize); 8] = [
+    (1, 0),
+    (-1, 0),
+    (0, 1),
+    (0, -1),
+    (1, 1),
+    (1, -1),
+    (-1, 1),
+    (-1, -1),
+];
+
+/// Horizontal, vertical or diagonal lines with both ends inside `0..width`.
+fn vent_lines(rng: &mut Rng, size: usize, width: usize) -> String {
+    let room = |from: usize, step: isize| match step {
+        1 => width - 1 - from,
+        -1 => from,
+        _ => usize::MAX,
+    };
+
+    (0..size)
+        .map(|_| loop {
+            let (x, y) = (rng.below(width), rng.below(width));
+            let (dx, dy) = *rng.choose(&DIRECTIONS);
+            let longest = room(x, dx).min(room(y, dy));
+            if longest == 0 {
+                continue;
+            }
+
+            let length = rng.range(1..=longest) as isize;
+            break format!(
+                "{},{} -> {},{}",
+                x,
+                y,
+                x as isize + dx * length,
+                y as isize + dy * length
+            );
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn fish(rng: &mut Rng, size: usize) -> String {
+    join(
+        &(0..size).map(|_| rng.range(1..=5)).collect::<Vec<usize>>(),
+        ",",
+    )
+}
+
+fn crabs(rng: &mut Rng, size: usize, width: usize) -> String {
+    join(
+        &(0..size).map(|_| rng.below(width)).collect::<Vec<usize>>(),
+        ",",
+    )
+}
+
+const DIGIT_SEGMENTS: [&str; 10] = [
+    "abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg",
+];
+
+/// All ten digits and four displayed ones, with the wires crossed by a random permutation
+/// and the segments of every pattern in random order.
+fn seven_segment_note(rng: &mut Rng) -> String {
+    let mut wiring = "abcdefg".chars().collect::<Vec<char>>();
+    rng.shuffle(&mut wiring);
+
+    let mut digits = (0..10).collect::<Vec<usize>>();
+    rng.shuffle(&mut digits);
+    digits.extend((0..4).map(|_| rng.below(10)));
+
+    let patterns = digits
+        .into_iter()
+        .map(|digit| {
+            let mut segments = DIGIT_SEGMENTS[digit]
+                .chars()
+                .map(|segment| wiring[(segment as u8 - b'a') as usize])
+                .collect::<Vec<char>>();
+            rng.shuffle(&mut segments);
+            segments.into_iter().collect::<String>()
+        })
+        .collect::<Vec<String>>();
+
+    format!(
+        "{} | {}",
+        patterns[..10].join(" "),
+        patterns[10..].join(" ")
+    )
+}
+
+fn digit_grid(rng: &mut Rng, rows: usize, columns: usize) -> String {
+    (0..rows)
+        .map(|_| {
+            (0..columns)
+                .map(|_| char::from(b'0' + rng.below(10) as u8))
+                .collect::<String>()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Low points at least three apart, every other location rising with its distance to the
+/// nearest one and ridges of 9 where two basins meet. Like in the puzzle, every location
+/// that is not a 9 belongs to exactly one basin.
+fn height_map(rng: &mut Rng, rows: usize, columns: usize) -> String {
+    let count = (rows * columns / 40).max(1);
+    let mut low_points: Vec<Point2> = vec![];
+    for _ in 0..10 * count {
+        let point = Point2::new(rng.below(columns) as isize, rng.below(rows) as isize);
+        if low_points
+            .iter()
+            .all(|low_point| low_point.manhattan_distance(point) >= 3)
+        {
+            low_points.push(point);
+        }
+        if low_points.len() == count {
+            break;
+        }
+    }
+
+    let basins = distances(&low_points, Grid::from_fn(columns, rows, |_| true));
+    let open = Grid::from_fn(columns, rows, |point| match basins[point] {
+        Some((basin, distance)) => {
+            distance == 0
+                || basins.neighbours_4(point).all(
+                    |neighbour| matches!(basins[neighbour], Some((other, _)) if other == basin),
+                )
+        }
+        None => false,
+    });
+
+    distances(&low_points, open)
+        .map(|basin| match basin {
+            Some((_, distance)) => (*distance).min(8),
+            None => 9,
+        })
+        .to_string()
+}
+
+/// Breadth first from all low points at once through the open locations, giving the index of
+/// the nearest low point and the distance to it.
+fn distances(low_points: &[Point2], open: Grid<bool>) -> Grid<Option<(usize, usize)>> {
+    let mut result = open.map(|_| None);
+    let mut queue = low_points
+        .iter()
+        .enumerate()
+        .map(|(basin, point)| (*point, basin, 0))
+        .collect::<VecDeque<(Point2, usize, usize)>>();
+    for (point, basin, _) in &queue {
+        result[*point] = Some((*basin, 0));
+    }
+
+    while let Some((point, basin, distance)) = queue.pop_front() {
+        for neighbour in open.neighbours_4(point) {
+            if open[neighbour] && result[neighbour].is_none() {
+                result[neighbour] = Some((basin, distance + 1));
+                queue.push_back((neighbour, basin, distance + 1));
+            }
+        }
+    }
+    result
+}
+
+/// Drawn again until all octopuses flash at once within a thousand steps, which part 2 waits for.
+fn synchronising_grid(rng: &mut Rng, rows: usize, columns: usize) -> Option<String> {
+    (0..ATTEMPTS).find_map(|_| {
+        let input = digit_grid(rng, rows, columns);
+        let mut grid = day11::parse(&input).unwrap();
+
+        (0..1000)
+            .any(|_| day11::do_step_and_get_flashing_points(&mut grid).len() == rows * columns)
+            .then_some(input)
+    })
+}
+
+const BRACKETS: [(char, char); 4] = [('(', ')'), ('[', ']'), ('{', '}'), ('<', '>')];
+
+/// Only corrupted and incomplete lines, with an odd number of incomplete ones so that
+/// there is a middle completion score.
+fn navigation_lines(rng: &mut Rng, size: usize, width: usize) -> String {
+    let incomplete = 2 * rng.below(size.div_ceil(2)) + 1;
+    let mut corrupted = (0..size)
+        .map(|line| line >= incomplete)
+        .collect::<Vec<bool>>();
+    rng.shuffle(&mut corrupted);
+
+    corrupted
+        .into_iter()
+        .map(|corrupted| {
+            let length = rng.range(width / 2..=width).max(2);
+            navigation_line(rng, length, corrupted)
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn navigation_line(rng: &mut Rng, length: usize, corrupted: bool) -> String {
+    let valid_length = match corrupted {
+        true => rng.range(1..=length - 1),
+        false => length,
+    };
+
+    let mut line = String::new();
+    let mut open = vec![];
+    while line.len() < valid_length || open.is_empty() {
+        match open.is_empty() || rng.chance(1, 2) {
+            true => {
+                let (opening, closing) = *rng.choose(&BRACKETS);
+                line.push(opening);
+                open.push(closing);
+            }
+            false => line.push(open.pop().unwrap()),
+        }
+    }
+
+    if corrupted {
+        let expected = open.pop().unwrap();
+        let wrong = loop {
+            let (_, closing) = *rng.choose(&BRACKETS);
+            if closing != expected {
+                break closing;
+            }
+        };
+        line.push(wrong);
+        while line.len() < length {
+            let (opening, closing) = *rng.choose(&BRACKETS);
+            line.push(match rng.chance(1, 2) {
+                true => opening,
+                false => closing,
+            });
+        }
+    }
+    line
+}
+
+/// Small and large caves hanging off a random tree that connects start with end, plus
+/// as many extra tunnels again. Two large caves are never connected, otherwise there
+/// would be infinitely many paths.
+fn cave_graph(rng: &mut Rng, size: usize) -> String {
+    let mut caves = vec!["start".to_string()];
+    let mut large = vec![false];
+    for index in 0..size {
+        let is_large = rng.chance(1, 4);
+        let name = format!(
+            "{}{}",
+            char::from(b'a' + (index / 26) as u8),
+            char::from(b'a' + (index % 26) as u8)
+        );
+        caves.push(match is_large {
+            true => name.to_uppercase(),
+            false => name,
+        });
+        large.push(is_large);
+    }
+    caves.push("end".to_string());
+    large.push(false);
+
+    let mut tunnels = HashSet::new();
+    for cave in 1..caves.len() {
+        let other = loop {
+            let other = rng.below(cave);
+            if !(large[cave] && large[other]) {
+                break other;
+            }
+        };
+        tunnels.insert((other, cave));
+    }
+    for _ in 0..size {
+        let (a, b) = (rng.below(caves.len()), rng.below(caves.len()));
+        if a != b && !(large[a] && large[b]) && !tunnels.contains(&(b, a)) {
+            tunnels.insert((a, b));
+        }
+    }
+
+    let mut tunnels = tunnels.into_iter().collect::<Vec<(usize, usize)>>();
+    tunnels.sort();
+    rng.shuffle(&mut tunnels);
+    tunnels
+        .into_iter()
+        .map(|(a, b)| format!("{}-{}", caves[a], caves[b]))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Picks a few dots on the folded 40x6 sheet and unfolds it `folds` times along random
+/// axes, mirroring copies of them to random sides until there are `size` dots, fewer if
+/// the folds do not leave room for that many. No dot ends up on a fold line.
+fn transparent_paper(rng: &mut Rng, size: usize, folds: usize) -> String {
+    let (mut width, mut height) = (40, 6);
+    let mut pool = (0..width * height).collect::<Vec<usize>>();
+    rng.shuffle(&mut pool);
+    pool.truncate(size.div_ceil(8));
+    let pool = pool
+        .into_iter()
+        .map(|cell| (cell % width, cell / width))
+        .collect::<Vec<(usize, usize)>>();
+
+    let mut unfolds = vec![];
+    for _ in 0..folds {
+        let along_x = rng.chance(1, 2);
+        match along_x {
+            true => {
+                unfolds.push((true, width));
+                width = 2 * width + 1;
+            }
+            false => {
+                unfolds.push((false, height));
+                height = 2 * height + 1;
+            }
+        }
+    }
+
+    let mut seen = HashSet::new();
+    let mut dots = vec![];
+    for _ in 0..10 * size {
+        let (mut x, mut y) = *rng.choose(&pool);
+        for (along_x, line) in &unfolds {
+            let coordinate = match along_x {
+                true => &mut x,
+                false => &mut y,
+            };
+            if rng.chance(1, 2) {
+                *coordinate = 2 * line - *coordinate;
+            }
+        }
+        if seen.insert((x, y)) {
+            dots.push(format!("{},{}", x, y));
+        }
+        if dots.len() == size {
+            break;
+        }
+    }
+
+    let instructions = unfolds
+        .iter()
+        .rev()
+        .map(|(along_x, line)| format!("fold along {}={}", if *along_x { 'x' } else { 'y' }, line))
+        .collect::<Vec<String>>();
+    format!("{}\n\n{}", dots.join("\n"), instructions.join("\n"))
+}
+
+fn join(numbers: &[usize], separator: &str) -> String {
+    numbers
+        .iter()
+        .map(|number| number.to_string())
+        .collect::<Vec<String>>()
+        .join(separator)
+}
+
+/// A rule for every pair of elements, like the puzzle, so every step doubles the polymer.
+fn polymer(rng: &mut Rng, size: usize, width: usize) -> String {
+    let mut elements = ('A'..='Z').collect::<Vec<char>>();
+    rng.shuffle(&mut elements);
+    elements.truncate(width);
+
+    let template = (0..size)
+        .map(|_| *rng.choose(&elements))
+        .collect::<String>();
+    let rules = elements
+        .iter()
+        .flat_map(|first| elements.iter().map(move |second| (*first, *second)))
+        .map(|(first, second)| format!("{}{} -> {}", first, second, rng.choose(&elements)))
+        .collect::<Vec<String>>();
+
+    format!("{}\n\n{}", template, rules.join("\n"))
+}
+
+fn risk_map(rng: &mut Rng, size: usize, width: usize) -> String {
+    (0..size)
+        .map(|_| (0..width).map(|_| rng.range(1..=9).to_string()).collect())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// A packet tree of `size` packets whose value fits into the answer, as hexadecimal.
+fn transmission(rng: &mut Rng, size: usize) -> Option<String> {
+    let packet = (0..ATTEMPTS)
+        .map(|_| random_packet(rng, size))
+        .find(|packet| {
+            day16::depth(packet) <= day16::MAX_DEPTH
+                && day16::evaluate(packet).is_some_and(|value| value <= i64::MAX as u64)
+        })?;
+
+    let mut bits = vec![];
+    encode(rng, &packet, &mut bits);
+    bits.resize(bits.len().div_ceil(8) * 8, false);
+
+    Some(
+        bits.chunks(4)
+            .map(|nibble| {
+                let digit = nibble.iter().fold(0, |digit, bit| digit << 1 | *bit as u32);
+                char::from_digit(digit, 16).unwrap().to_ascii_uppercase()
+            })
+            .collect(),
+    )
+}
+
+fn random_packet(rng: &mut Rng, size: usize) -> Packet {
+    let version = rng.range(0..=7) as u8;
+    if size == 1 {
+        let groups = rng.range(1..=4);
+        return Packet::Literal {
+            version,
+            value: rng.below(1 << (4 * groups)) as u64,
+        };
+    }
+
+    let operators = [
+        Operator::Sum,
+        Operator::Product,
+        Operator::Minimum,
+        Operator::Maximum,
+        Operator::GreaterThan,
+        Operator::LessThan,
+        Operator::EqualTo,
+    ];
+    let operator = match size {
+        2 => *rng.choose(&operators[..4]),
+        _ => *rng.choose(&operators),
+    };
+    let count = match operator.is_comparison() {
+        true => 2,
+        false => rng.range(1..=(size - 1).min(5)),
+    };
+    let mut sizes = vec![1; count];
+    for _ in count..size - 1 {
+        sizes[rng.below(count)] += 1;
+    }
+
+    Packet::Operator {
+        version,
+        operator,
+        packets: sizes
+            .into_iter()
+            .map(|size| random_packet(rng, size))
+            .collect(),
+    }
+}
+
+/// Chooses the length type of every operator at random, unless the total length does not fit.
+fn encode(rng: &mut Rng, packet: &Packet, bits: &mut Vec<bool>) {
+    let push = |bits: &mut Vec<bool>, number: u64, width: usize| {
+        bits.extend((0..width).rev().map(|bit| number >> bit & 1 == 1))
+    };
+
+    match packet {
+        Packet::Literal { version, value } => {
+            push(bits, *version as u64, 3);
+            push(bits, day16::LITERAL, 3);
+            let groups = ((64 - value.leading_zeros() as usize).div_ceil(4)).max(1);
+            for group in (0..groups).rev() {
+                push(bits, (group > 0) as u64, 1);
+                push(bits, value >> (4 * group) & 0xF, 4);
+            }
+        }
+        Packet::Operator {
+            version,
+            operator,
+            packets,
+        } => {
+            push(bits, *version as u64, 3);
+            push(bits, operator.type_id(), 3);
+            let mut sub_packets = vec![];
+            for packet in packets {
+                encode(rng, packet, &mut sub_packets);
+            }
+            match rng.chance(1, 2) && sub_packets.len() < 1 << 15 {
+                true => {
+                    push(bits, 0, 1);
+                    push(bits, sub_packets.len() as u64, 15);
+                }
+                false => {
+                    push(bits, 1, 1);
+                    push(bits, packets.len() as u64, 11);
+                }
+            }
+            bits.extend(sub_packets);
+        }
+    }
+}
+
+fn target_area(rng: &mut Rng, size: usize, width: usize) -> String {
+    format!(
+        "target area: x={}..{}, y=-{}..-{}",
+        rng.range((size / 2).max(1)..=size),
+        size,
+        width,
+        rng.range(1..=width)
+    )
+}
+
+/// A reduced number, so nothing is nested in more than four pairs and regular numbers are digits.
+fn snailfish_number(rng: &mut Rng, depth: usize) -> String {
+    match depth == 0 || depth < 4 && rng.chance(2, 3) {
+        true => format!(
+            "[{},{}]",
+            snailfish_number(rng, depth + 1),
+            snailfish_number(rng, depth + 1)
+        ),
+        false => rng.range(0..=9).to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use crate::day13;
+    use crate::generate::*;
+    use crate::registry;
+    use crate::solution::Part;
+
+    fn small(day: u8, seed: u64) -> Params {
+        let params = Params::puzzle(day, seed).unwrap();
+        match day {
+            12 => Params { size: 6, ..params },
+            11 => params,
+            _ => Params {
+                size: params.size.min(50),
+                width: params.width.min(60),
+                ..params
+            },
+        }
+    }
+
+    #[rstest]
+    #[case(1)]
+    #[case(2)]
+    #[case(3)]
+    #[case(4)]
+    #[case(5)]
+    #[case(6)]
+    #[case(7)]
+    #[case(8)]
+    #[case(9)]
+    #[case(10)]
+    #[case(11)]
+    #[case(12)]
+    #[case(13)]
+    #[case(14)]
+    #[case(15)]
+    #[case(16)]
+    #[case(17)]
+    #[case(18)]
+    fn test_generated_inputs_are_solvable(#[case] day: u8) {
+        let solver = registry::solver(day).unwrap();
+
+        for seed in 0..5 {
+            let input = generate(day, small(day, seed)).unwrap();
+
+            let parsed = solver
+                .parse(&input)
+                .unwrap_or_else(|error| panic!("seed {}: {}\n{}", seed, error, input));
+            solver.run(&parsed, Part::One);
+            solver.run(&parsed, Part::Two);
+        }
+    }
+
+    #[test]
+    fn test_same_seed_same_input() {
+        let params = Params::puzzle(5, 42).unwrap();
+
+        assert_eq!(generate(5, params), generate(5, params));
+        assert_ne!(
+            generate(5, params),
+            generate(5, Params { seed: 43, ..params })
+        );
+    }
+
+    #[test]
+    fn test_bit_strings_have_width() {
+        let input = generate(
+            3,
+            Params {
+                seed: 1,
+                size: 20,
+                width: 7,
+            },
+        )
+        .unwrap();
+
+        assert_eq!(20, input.lines().count());
+        assert!(input.lines().all(|line| line.len() == 7));
+    }
+
+    #[test]
+    fn test_no_adjacent_large_caves() {
+        for seed in 0..20 {
+            let input = generate(
+                12,
+                Params {
+                    seed,
+                    size: 30,
+                    width: 0,
+                },
+            )
+            .unwrap();
+
+            for line in input.lines() {
+                let (a, b) = line.split_once('-').unwrap();
+                let is_large = |cave: &str| cave.chars().all(|c| c.is_ascii_uppercase());
+                assert!(!(is_large(a) && is_large(b)), "{}", line);
+            }
+        }
+    }
+
+    #[test]
+    fn test_vent_lines_stay_inside() {
+        let input = generate(
+            5,
+            Params {
+                seed: 3,
+                size: 200,
+                width: 10,
+            },
+        )
+        .unwrap();
+
+        let lines = crate::day5::parse(&input).unwrap();
+        assert_eq!(200, lines.len());
+        assert!(input
+            .split(|c: char| !c.is_ascii_digit())
+            .filter(|number| !number.is_empty())
+            .all(|number| number.parse::<usize>().unwrap() < 10));
+    }
+
+    #[test]
+    fn test_folds_end_on_the_small_sheet() {
+        let input = generate(
+            13,
+            Params {
+                seed: 7,
+                size: 100,
+                width: 8,
+            },
+        )
+        .unwrap();
+
+        let answer = crate::runner::solve(13, Part::Two, &input)
+            .unwrap()
+            .to_string();
+        assert!(answer.lines().count() <= 6);
+        assert!(answer.lines().all(|line| line.len() <= 40));
+    }
+
+    #[test]
+    fn test_paper_without_folds_is_rejected() {
+        let input = generate(
+            13,
+            Params {
+                seed: 0,
+                size: 100,
+                width: 0,
+            },
+        )
+        .unwrap();
+
+        let error = day13::parse(&input).unwrap_err();
+
+        assert_eq!("expected folds after the dots", error.reason);
+    }
+
+    #[test]
+    fn test_unsupported_day() {
+        assert_eq!(
+            Err(GenerateError::Unsupported(25)),
+            generate(
+                25,
+                Params {
+                    seed: 0,
+                    size: 1,
+                    width: 1
+                }
+            )
+        );
+    }
+}
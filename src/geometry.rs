@@ -0,0 +1,258 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+use crate::parse::{InputLine, ParseError};
+
+/// A position on the integer plane.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Point2 {
+    pub x: isize,
+    pub y: isize,
+}
+
+/// A displacement between two [`Point2`]s.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Vec2 {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl Point2 {
+    pub const ORIGIN: Point2 = Point2 { x: 0, y: 0 };
+
+    pub const fn new(x: isize, y: isize) -> Point2 {
+        Point2 { x, y }
+    }
+
+    /// Parses `x,y` as part of an input line.
+    pub fn parse(line: &InputLine, str: &str) -> Result<Point2, ParseError> {
+        let (x, y) = str
+            .split_once(',')
+            .ok_or_else(|| line.error(str, "expected a point x,y"))?;
+
+        Ok(Point2 {
+            x: line.parse::<isize>(x, "a number")?,
+            y: line.parse::<isize>(y, "a number")?,
+        })
+    }
+
+    pub fn manhattan_distance(&self, other: Point2) -> usize {
+        (other - *self).manhattan_length()
+    }
+
+    pub fn chebyshev_distance(&self, other: Point2) -> usize {
+        (other - *self).chebyshev_length()
+    }
+
+    /// Mirrors the point across the vertical line through `x`.
+    pub fn reflect_x(&self, x: isize) -> Point2 {
+        Point2::new(2 * x - self.x, self.y)
+    }
+
+    /// Mirrors the point across the horizontal line through `y`.
+    pub fn reflect_y(&self, y: isize) -> Point2 {
+        Point2::new(self.x, 2 * y - self.y)
+    }
+}
+
+impl Vec2 {
+    pub const ZERO: Vec2 = Vec2 { x: 0, y: 0 };
+
+    pub const fn new(x: isize, y: isize) -> Vec2 {
+        Vec2 { x, y }
+    }
+
+    /// The step of length at most one per axis that points in the same direction.
+    pub fn signum(&self) -> Vec2 {
+        Vec2::new(self.x.signum(), self.y.signum())
+    }
+
+    pub fn manhattan_length(&self) -> usize {
+        self.x.unsigned_abs() + self.y.unsigned_abs()
+    }
+
+    pub fn chebyshev_length(&self) -> usize {
+        self.x.unsigned_abs().max(self.y.unsigned_abs())
+    }
+}
+
+impl Add<Vec2> for Point2 {
+    type Output = Point2;
+
+    fn add(self, rhs: Vec2) -> Point2 {
+        Point2::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign<Vec2> for Point2 {
+    fn add_assign(&mut self, rhs: Vec2) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub<Vec2> for Point2 {
+    type Output = Point2;
+
+    fn sub(self, rhs: Vec2) -> Point2 {
+        Point2::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl SubAssign<Vec2> for Point2 {
+    fn sub_assign(&mut self, rhs: Vec2) {
+        *self = *self - rhs;
+    }
+}
+
+impl Sub for Point2 {
+    type Output = Vec2;
+
+    fn sub(self, rhs: Point2) -> Vec2 {
+        Vec2::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Add for Vec2 {
+    type Output = Vec2;
+
+    fn add(self, rhs: Vec2) -> Vec2 {
+        Vec2::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign for Vec2 {
+    fn add_assign(&mut self, rhs: Vec2) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Vec2 {
+    type Output = Vec2;
+
+    fn sub(self, rhs: Vec2) -> Vec2 {
+        Vec2::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl SubAssign for Vec2 {
+    fn sub_assign(&mut self, rhs: Vec2) {
+        *self = *self - rhs;
+    }
+}
+
+impl Neg for Vec2 {
+    type Output = Vec2;
+
+    fn neg(self) -> Vec2 {
+        Vec2::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<isize> for Vec2 {
+    type Output = Vec2;
+
+    fn mul(self, rhs: isize) -> Vec2 {
+        Vec2::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl Display for Point2 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+impl Display for Vec2 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParsePointError(String);
+
+impl Display for ParsePointError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "expected a point x,y, found {:?}", self.0)
+    }
+}
+
+impl Error for ParsePointError {}
+
+impl FromStr for Point2 {
+    type Err = ParsePointError;
+
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        Point2::parse(&InputLine::new(0, 0, str), str).map_err(|_| ParsePointError(str.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use crate::geometry::*;
+
+    #[test]
+    fn test_arithmetic() {
+        let mut point = Point2::new(3, 4);
+
+        point += Vec2::new(1, -2) * 2;
+
+        assert_eq!(Point2::new(5, 0), point);
+        assert_eq!(Vec2::new(5, 0), point - Point2::ORIGIN);
+        assert_eq!(
+            Point2::new(6, 1),
+            point + (Vec2::new(2, 2) - Vec2::new(1, 1))
+        );
+        assert_eq!(Vec2::new(-1, 1), -Vec2::new(1, -1));
+    }
+
+    #[rstest]
+    #[case(Point2::new(0, 0), Point2::new(3, -4), 7, 4)]
+    #[case(Point2::new(2, 2), Point2::new(2, 2), 0, 0)]
+    #[case(Point2::new(-1, 5), Point2::new(1, 0), 7, 5)]
+    fn test_distances(
+        #[case] from: Point2,
+        #[case] to: Point2,
+        #[case] manhattan: usize,
+        #[case] chebyshev: usize,
+    ) {
+        assert_eq!(manhattan, from.manhattan_distance(to));
+        assert_eq!(chebyshev, from.chebyshev_distance(to));
+    }
+
+    #[rstest]
+    #[case(Vec2::new(5, -3), Vec2::new(1, -1))]
+    #[case(Vec2::new(0, 7), Vec2::new(0, 1))]
+    #[case(Vec2::ZERO, Vec2::ZERO)]
+    fn test_signum(#[case] vector: Vec2, #[case] expected: Vec2) {
+        assert_eq!(expected, vector.signum());
+    }
+
+    #[test]
+    fn test_reflect() {
+        let point = Point2::new(9, 10);
+
+        assert_eq!(Point2::new(1, 10), point.reflect_x(5));
+        assert_eq!(Point2::new(9, 4), point.reflect_y(7));
+    }
+
+    #[rstest]
+    #[case("284,294", Ok(Point2::new(284, 294)))]
+    #[case("-3,0", Ok(Point2::new(-3, 0)))]
+    #[case("3;0", Err(ParsePointError("3;0".to_string())))]
+    #[case("3,y", Err(ParsePointError("3,y".to_string())))]
+    fn test_from_str(#[case] str: &str, #[case] expected: Result<Point2, ParsePointError>) {
+        assert_eq!(expected, str.parse::<Point2>());
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        let point = Point2::new(-7, 12);
+
+        assert_eq!(Ok(point), point.to_string().parse());
+    }
+}
@@ -0,0 +1,329 @@
+use std::fmt::{Display, Formatter};
+use std::ops::{Index, IndexMut};
+
+use crate::geometry::{Point2, Vec2};
+use crate::parse::{input_lines, InputLine, ParseError};
+
+pub const NEIGHBOURS_4: [Vec2; 4] = [
+    Vec2::new(0, -1),
+    Vec2::new(1, 0),
+    Vec2::new(0, 1),
+    Vec2::new(-1, 0),
+];
+
+pub const NEIGHBOURS_8: [Vec2; 8] = [
+    Vec2::new(0, -1),
+    Vec2::new(1, -1),
+    Vec2::new(1, 0),
+    Vec2::new(1, 1),
+    Vec2::new(0, 1),
+    Vec2::new(-1, 1),
+    Vec2::new(-1, 0),
+    Vec2::new(-1, -1),
+];
+
+/// A dense rectangular grid, stored row by row. `x` counts columns and `y` rows from the top left.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// `None` if the rows do not all have the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Grid<T>> {
+        let width = rows.first().map(Vec::len).unwrap_or(0);
+        if rows.iter().any(|row| row.len() != width) {
+            return None;
+        }
+
+        Some(Grid {
+            width,
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut cell: impl FnMut(Point2) -> T) -> Grid<T> {
+        Grid {
+            width,
+            height,
+            cells: (0..width * height)
+                .map(|index| {
+                    cell(Point2::new(
+                        (index % width) as isize,
+                        (index / width) as isize,
+                    ))
+                })
+                .collect(),
+        }
+    }
+
+    /// Parses one cell per character, every line being a row of the same width.
+    pub fn parse(
+        day: u8,
+        input: &str,
+        what: &str,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Grid<T>, ParseError> {
+        let lines = input_lines(day, input).collect::<Vec<InputLine>>();
+        let width = lines
+            .first()
+            .map(|line| line.text.chars().count())
+            .unwrap_or(0);
+
+        let mut cells = vec![];
+        for line in &lines {
+            for (index, char) in line.text.char_indices() {
+                let value = cell(char).ok_or_else(|| {
+                    line.error(
+                        &line.text[index..index + char.len_utf8()],
+                        format!("expected {}", what),
+                    )
+                })?;
+                cells.push(value);
+            }
+            if line.text.chars().count() != width {
+                return Err(line.error(line.text, format!("expected {} cells", width)));
+            }
+        }
+
+        Ok(Grid {
+            width,
+            height: lines.len(),
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, Point2 { x, y }: Point2) -> bool {
+        x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height
+    }
+
+    pub fn get(&self, position: Point2) -> Option<&T> {
+        self.index_of(position).map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, position: Point2) -> Option<&mut T> {
+        self.index_of(position).map(|index| &mut self.cells[index])
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Point2> {
+        let width = self.width;
+        (0..self.cells.len())
+            .map(move |index| Point2::new((index % width) as isize, (index / width) as isize))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point2, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Point2, &mut T)> {
+        self.positions().zip(self.cells.iter_mut())
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    /// The horizontal and vertical neighbours that lie inside the grid.
+    pub fn neighbours_4(&self, position: Point2) -> impl Iterator<Item = Point2> + '_ {
+        self.neighbours(position, &NEIGHBOURS_4)
+    }
+
+    /// Like [`Grid::neighbours_4`], but including diagonal neighbours.
+    pub fn neighbours_8(&self, position: Point2) -> impl Iterator<Item = Point2> + '_ {
+        self.neighbours(position, &NEIGHBOURS_8)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter().skip(x).step_by(self.width.max(1))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.column(x))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    fn neighbours<'a>(
+        &'a self,
+        position: Point2,
+        offsets: &'static [Vec2],
+    ) -> impl Iterator<Item = Point2> + 'a {
+        offsets
+            .iter()
+            .map(move |offset| position + *offset)
+            .filter(|neighbour| self.contains(*neighbour))
+    }
+
+    fn index_of(&self, position: Point2) -> Option<usize> {
+        match self.contains(position) {
+            true => Some(position.y as usize * self.width + position.x as usize),
+            false => None,
+        }
+    }
+}
+
+impl Grid<usize> {
+    pub fn parse_digits(day: u8, input: &str) -> Result<Grid<usize>, ParseError> {
+        Grid::parse(day, input, "a digit", |char| {
+            char.to_digit(10).map(|digit| digit as usize)
+        })
+    }
+}
+
+impl Grid<char> {
+    pub fn parse_chars(day: u8, input: &str) -> Result<Grid<char>, ParseError> {
+        Grid::parse(day, input, "a character", Some)
+    }
+}
+
+impl<T> Index<Point2> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Point2) -> &T {
+        self.get(position)
+            .unwrap_or_else(|| panic!("{} is outside of the grid", position))
+    }
+}
+
+impl<T> IndexMut<Point2> for Grid<T> {
+    fn index_mut(&mut self, position: Point2) -> &mut T {
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("{} is outside of the grid", position))
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use crate::grid::*;
+
+    #[test]
+    fn test_parse_digits() {
+        let grid = Grid::parse_digits(9, "219\n398\n").unwrap();
+
+        assert_eq!((3, 2), (grid.width(), grid.height()));
+        assert_eq!(2, grid[Point2::new(0, 0)]);
+        assert_eq!(8, grid[Point2::new(2, 1)]);
+        assert_eq!("219\n398", grid.to_string());
+    }
+
+    #[rstest]
+    #[case("219\n3x8\n", 2, 2, "x")]
+    #[case("219\n39\n", 2, 1, "39")]
+    fn test_parse_error(
+        #[case] input: &str,
+        #[case] line: usize,
+        #[case] column: usize,
+        #[case] text: &str,
+    ) {
+        let error = Grid::parse_digits(9, input).unwrap_err();
+
+        assert_eq!(
+            (9, line, column, text),
+            (error.day, error.line, error.column, error.text.as_str())
+        );
+    }
+
+    #[test]
+    fn test_parse_chars() {
+        let grid = Grid::parse_chars(1, "#.\n.#").unwrap();
+
+        assert_eq!(Some(&'#'), grid.get(Point2::new(1, 1)));
+    }
+
+    #[rstest]
+    #[case(Point2::new(0, 0), Some(1))]
+    #[case(Point2::new(2, 1), Some(6))]
+    #[case(Point2::new(3, 0), None)]
+    #[case(Point2::new(0, -1), None)]
+    fn test_get(#[case] position: Point2, #[case] expected: Option<usize>) {
+        let grid = Grid::from_fn(3, 2, |point| (point.y * 3 + point.x + 1) as usize);
+
+        assert_eq!(expected.as_ref(), grid.get(position));
+    }
+
+    #[rstest]
+    #[case(Point2::new(1, 1), 4, 8)]
+    #[case(Point2::new(0, 0), 2, 3)]
+    #[case(Point2::new(2, 0), 2, 3)]
+    #[case(Point2::new(1, 2), 3, 5)]
+    fn test_neighbours(
+        #[case] position: Point2,
+        #[case] expected_4: usize,
+        #[case] expected_8: usize,
+    ) {
+        let grid = Grid::from_fn(3, 3, |_| 0);
+
+        assert_eq!(expected_4, grid.neighbours_4(position).count());
+        assert_eq!(expected_8, grid.neighbours_8(position).count());
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = Grid::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap();
+
+        assert_eq!(
+            vec![&[1, 2, 3][..], &[4, 5, 6][..]],
+            grid.rows().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![vec![1, 4], vec![2, 5], vec![3, 6]],
+            grid.columns()
+                .map(|column| column.copied().collect::<Vec<_>>())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_from_ragged_rows() {
+        assert_eq!(None, Grid::from_rows(vec![vec![1, 2], vec![3]]));
+    }
+}
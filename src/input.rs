@@ -0,0 +1,169 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::fs::read_to_string;
+use std::io;
+use std::io::{stdin, BufRead};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::parse::ParseError;
+
+#[derive(Debug)]
+pub enum InputError {
+    Io { path: String, source: io::Error },
+    Parse(ParseError),
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::Io { path, source } => write!(f, "could not read {}: {}", path, source),
+            InputError::Parse(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            InputError::Io { source, .. } => Some(source),
+            InputError::Parse(error) => Some(error),
+        }
+    }
+}
+
+impl From<ParseError> for InputError {
+    fn from(error: ParseError) -> Self {
+        InputError::Parse(error)
+    }
+}
+
+/// Where a puzzle input comes from. `-` on the command line stands for stdin.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum InputSource {
+    File(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    pub fn read(&self) -> Result<String, InputError> {
+        match self {
+            InputSource::File(path) => read_input(path),
+            InputSource::Stdin => read_stdin(),
+        }
+    }
+}
+
+impl FromStr for InputSource {
+    type Err = std::convert::Infallible;
+
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        match str {
+            "-" => Ok(InputSource::Stdin),
+            path => Ok(InputSource::File(PathBuf::from(path))),
+        }
+    }
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputSource::File(path) => write!(f, "{}", path.display()),
+            InputSource::Stdin => write!(f, "<stdin>"),
+        }
+    }
+}
+
+/// The committed input of a day, independent of the working directory.
+pub fn default_input_path(day: u8) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join(format!("src/day{}/input.txt", day))
+}
+
+/// Whether the personal input of a day is committed. New days start with an empty file until it
+/// is fetched, days solved without one have no file at all.
+pub fn has_input(day: u8) -> bool {
+    default_input_path(day)
+        .metadata()
+        .is_ok_and(|metadata| metadata.len() > 0)
+}
+
+pub fn read_input(path: impl AsRef<Path>) -> Result<String, InputError> {
+    read_to_string(path.as_ref()).map_err(|source| InputError::Io {
+        path: path.as_ref().display().to_string(),
+        source,
+    })
+}
+
+pub fn read_from(mut reader: impl BufRead) -> Result<String, InputError> {
+    let mut content = String::new();
+    reader
+        .read_to_string(&mut content)
+        .map_err(|source| InputError::Io {
+            path: "<reader>".to_string(),
+            source,
+        })?;
+    Ok(content)
+}
+
+pub fn read_stdin() -> Result<String, InputError> {
+    read_from(stdin().lock()).map_err(|error| match error {
+        InputError::Io { source, .. } => InputError::Io {
+            path: "<stdin>".to_string(),
+            source,
+        },
+        error => error,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::input::*;
+
+    #[test]
+    fn test_read_input() {
+        let content = read_input("src/day7/test_input.txt").unwrap();
+
+        assert_eq!("16,1,2,0,4,2,7,1,2,14", content);
+    }
+
+    #[test]
+    fn test_read_missing_input() {
+        let error = read_input("src/day7/missing.txt").unwrap_err();
+
+        assert!(matches!(error, InputError::Io { .. }));
+        assert!(error
+            .to_string()
+            .starts_with("could not read src/day7/missing.txt: "));
+    }
+
+    #[test]
+    fn test_read_from() {
+        let content = read_from("3,4,3,1,2".as_bytes()).unwrap();
+
+        assert_eq!("3,4,3,1,2", content);
+    }
+
+    #[test]
+    fn test_default_input_path_does_not_depend_on_working_directory() {
+        let path = default_input_path(7);
+
+        assert!(path.is_absolute());
+        assert!(path.ends_with("src/day7/input.txt"));
+        assert!(path.exists());
+    }
+
+    #[test]
+    fn test_has_input() {
+        assert!(has_input(7));
+        assert!(!has_input(25));
+    }
+
+    #[test]
+    fn test_input_source_from_str() {
+        assert_eq!(Ok(InputSource::Stdin), "-".parse::<InputSource>());
+        assert_eq!(
+            Ok(InputSource::File(PathBuf::from("in.txt"))),
+            "in.txt".parse::<InputSource>()
+        );
+    }
+}
@@ -1,13 +1,38 @@
-mod day1;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
+pub mod animate;
+pub mod answers;
+pub mod aoc;
+pub mod bench;
+pub mod bits;
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod examples;
+pub mod generate;
+pub mod geometry;
+pub mod grid;
+pub mod input;
+pub mod parse;
+pub mod reference;
+pub mod registry;
+pub mod render;
+pub mod repl;
+pub mod report;
+pub mod runner;
+pub mod scaffold;
+pub mod solution;
+pub mod supervise;
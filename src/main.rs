@@ -0,0 +1,848 @@
+#[cfg(feature = "json")]
+use std::env::current_exe;
+use std::fs::File;
+#[cfg(feature = "json")]
+use std::io::read_to_string;
+use std::io::{stdin, stdout, BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::exit;
+use std::sync::mpsc::channel;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use clap::{Args, Parser, Subcommand};
+
+use aoc2021::animate::{animate, Control, Player};
+use aoc2021::answers::{default_answers_path, load, verify, Outcome};
+use aoc2021::aoc::client::{AocClient, DEFAULT_BASE_URL};
+use aoc2021::aoc::inputs::{default_cache_dir, InputCache, InputManager, Offline};
+#[cfg(feature = "json")]
+use aoc2021::aoc::ledger::{default_ledger_path, Ledger};
+#[cfg(feature = "json")]
+use aoc2021::aoc::submit::submit;
+use aoc2021::aoc::AocError;
+#[cfg(feature = "json")]
+use aoc2021::bench::{load_baseline, save_baseline};
+use aoc2021::bench::{measure, regressions};
+use aoc2021::examples::{default_examples_path, extract, read_html, save};
+use aoc2021::generate::{generate, GenerateError, Params};
+use aoc2021::input::read_input;
+use aoc2021::input::{default_input_path, InputSource};
+use aoc2021::registry;
+use aoc2021::render::{frames, write_frame, write_sequence, Format};
+use aoc2021::repl::{run as run_repl, Session};
+#[cfg(feature = "json")]
+use aoc2021::report::encode;
+use aoc2021::report::{report, report_parallel, Format as ReportFormat, Report};
+#[cfg(feature = "json")]
+use aoc2021::runner::solve;
+use aoc2021::scaffold::scaffold;
+use aoc2021::solution::{Answer, Part};
+#[cfg(feature = "json")]
+use aoc2021::supervise::supervise_parallel;
+use aoc2021::supervise::Limits;
+
+/// The year of the puzzles these solutions are for.
+const YEAR: u16 = 2021;
+
+#[derive(Parser)]
+#[command(about = "Advent of Code 2021 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Runs the solution of one day or of all days
+    Run(RunArgs),
+    /// Downloads the puzzle input of a day into the local cache
+    Fetch(FetchArgs),
+    /// Submits the answer of one part and records the verdict
+    Submit(SubmitArgs),
+    /// Compares the solutions against the known answers in answers.toml
+    Verify(VerifyArgs),
+    /// Times parse, part 1 and part 2 of one day or of all days
+    Bench(BenchArgs),
+    /// Draws the puzzle state of day 5, 9, 11, 13 or 15 as PPM or SVG images
+    Render(RenderArgs),
+    /// Writes a random but valid puzzle input of a day
+    Generate(GenerateArgs),
+    /// Saves the example and its answers from a saved puzzle page as src/dayN/example.txt and
+    /// in examples.toml, which the tests check
+    Extract(ExtractArgs),
+    /// Loads the input of a day to inspect it, step through it and try its sub-functions
+    Repl(ReplArgs),
+    /// Creates the module, inputs and registrations of a day that is not solved yet
+    NewDay(NewDayArgs),
+    /// Solves one part on stdin and prints its JSON report, used by run --timeout and --memory
+    #[command(hide = true)]
+    SolveTask(SolveTaskArgs),
+}
+
+#[derive(Args)]
+struct RunArgs {
+    /// The day to run
+    #[arg(long, required_unless_present = "all", conflicts_with = "all")]
+    day: Option<u8>,
+    /// The part to run, both parts if omitted
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+    /// The puzzle input file or - for stdin, defaults to the cached input or src/dayN/input.txt
+    #[arg(long, conflicts_with = "all")]
+    input: Option<InputSource>,
+    /// Runs every day
+    #[arg(long)]
+    all: bool,
+    /// How many days and parts --all solves at the same time, defaults to the number of cores
+    #[arg(long, requires = "all", value_parser = clap::value_parser!(u64).range(1..))]
+    jobs: Option<u64>,
+    /// Draws every step of day 6, 11 or 13 in the terminal. Type p to pause or resume,
+    /// an empty line to advance while paused and q to stop drawing, each followed by enter
+    #[arg(long, requires = "day")]
+    animate: bool,
+    /// Milliseconds between two animation steps
+    #[arg(long, default_value_t = 100, requires = "animate")]
+    delay: u64,
+    /// Starts the animation paused
+    #[arg(long, requires = "animate")]
+    paused: bool,
+    /// text, json (one array) or jsonl (one object per line)
+    #[arg(long, default_value = "text", conflicts_with = "animate")]
+    format: ReportFormat,
+    /// Seconds a part may take before it is stopped and reported as timed out
+    #[arg(long, conflicts_with = "animate", value_parser = parse_timeout)]
+    timeout: Option<Duration>,
+    /// Megabytes of memory a part may use before it is reported as out of memory
+    #[arg(long, conflicts_with = "animate", value_parser = clap::value_parser!(u64).range(1..))]
+    memory: Option<u64>,
+}
+
+#[derive(Args)]
+struct ExtractArgs {
+    /// The puzzle description as saved by the browser
+    html: PathBuf,
+    /// The example manifest, defaults to examples.toml in the crate root
+    #[arg(long)]
+    manifest: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct ReplArgs {
+    /// The day to load
+    #[arg(long)]
+    day: u8,
+    /// The puzzle input file or - for stdin, defaults to the cached input or src/dayN/input.txt
+    #[arg(long)]
+    input: Option<InputSource>,
+    /// Runs the commands of this file, one per line, instead of reading them from the terminal
+    #[arg(long)]
+    script: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct NewDayArgs {
+    /// The day to create
+    day: u8,
+}
+
+#[derive(Args)]
+#[cfg_attr(not(feature = "json"), allow(dead_code))]
+struct SolveTaskArgs {
+    #[arg(long)]
+    day: u8,
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: u8,
+}
+
+#[derive(Args)]
+struct FetchArgs {
+    /// The day to download
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+    /// The year of the puzzle
+    #[arg(long, default_value_t = YEAR)]
+    year: u16,
+    /// Where inputs are cached, defaults to $AOC_CACHE_DIR or inputs/
+    #[arg(long)]
+    cache_dir: Option<PathBuf>,
+    /// The Advent of Code server
+    #[arg(long, default_value = DEFAULT_BASE_URL)]
+    base_url: String,
+}
+
+#[derive(Args)]
+#[cfg_attr(not(feature = "json"), allow(dead_code))]
+struct SubmitArgs {
+    /// The day to submit
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+    /// The part to submit
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: u8,
+    /// The answer, computed from the cached input or src/dayN/input.txt if omitted
+    #[arg(long)]
+    answer: Option<Answer>,
+    /// The year of the puzzle
+    #[arg(long, default_value_t = YEAR)]
+    year: u16,
+    /// The JSON file of earlier attempts, defaults to ledger.json in the cache dir
+    #[arg(long)]
+    ledger: Option<PathBuf>,
+    /// The Advent of Code server
+    #[arg(long, default_value = DEFAULT_BASE_URL)]
+    base_url: String,
+}
+
+#[derive(Args)]
+struct VerifyArgs {
+    /// Only verifies this day
+    #[arg(long)]
+    day: Option<u8>,
+    /// Only verifies this input name, e.g. input or test_input
+    #[arg(long)]
+    input: Option<String>,
+    /// The answers file, defaults to answers.toml in the crate root
+    #[arg(long)]
+    answers: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct BenchArgs {
+    /// Only benchmarks this day
+    #[arg(long)]
+    day: Option<u8>,
+    /// How often every stage is run
+    #[arg(long, default_value_t = 5, value_parser = clap::value_parser!(u64).range(1..))]
+    runs: u64,
+    /// A baseline file of an earlier run to compare against
+    #[arg(long)]
+    baseline: Option<PathBuf>,
+    /// Writes the measurements as a baseline file
+    #[arg(long)]
+    save: Option<PathBuf>,
+    /// How much slower than the baseline median a stage may get, 0.2 meaning 20%
+    #[arg(long, default_value_t = 0.2)]
+    tolerance: f64,
+}
+
+#[derive(Args)]
+struct RenderArgs {
+    /// The day to draw
+    #[arg(long)]
+    day: u8,
+    /// The puzzle input file or - for stdin, defaults to the cached input or src/dayN/input.txt
+    #[arg(long)]
+    input: Option<InputSource>,
+    /// The image format, ppm or svg
+    #[arg(long, default_value = "ppm")]
+    format: Format,
+    /// The image file, or the directory of the frames with --sequence
+    #[arg(long)]
+    output: PathBuf,
+    /// Writes every step as numbered frames instead of only the final state
+    #[arg(long)]
+    sequence: bool,
+}
+
+#[derive(Args)]
+struct GenerateArgs {
+    /// The day to generate an input for
+    #[arg(long)]
+    day: u8,
+    /// The same seed gives the same input, random if omitted
+    #[arg(long)]
+    seed: Option<u64>,
+    /// Lines, boards, fish, rows, caves or dots depending on the day, defaults to the puzzle size
+    #[arg(long)]
+    size: Option<usize>,
+    /// Bits, largest number, columns or folds depending on the day, defaults to the puzzle size
+    #[arg(long)]
+    width: Option<usize>,
+    /// The input file, stdout if omitted
+    #[arg(long)]
+    output: Option<PathBuf>,
+}
+
+fn parse_timeout(seconds: &str) -> Result<Duration, String> {
+    seconds
+        .parse::<f64>()
+        .map_err(|error| error.to_string())
+        .and_then(|seconds| Duration::try_from_secs_f64(seconds).map_err(|error| error.to_string()))
+        .and_then(|timeout| match timeout.is_zero() {
+            true => Err("the timeout must be longer than zero".to_string()),
+            false => Ok(timeout),
+        })
+}
+
+fn main() {
+    match Cli::parse().command {
+        Command::Run(args) => run(args),
+        Command::Fetch(args) => fetch(args),
+        Command::Submit(args) => submit_answer(args),
+        Command::Verify(args) => verify_answers(args),
+        Command::Bench(args) => bench(args),
+        Command::Render(args) => render(args),
+        Command::Generate(args) => generate_input(args),
+        Command::Extract(args) => extract_example(args),
+        Command::Repl(args) => repl(args),
+        Command::NewDay(args) => new_day(args),
+        Command::SolveTask(args) => solve_task(args),
+    }
+}
+
+fn fetch(args: FetchArgs) {
+    let cache = InputCache::new(args.cache_dir.unwrap_or_else(default_cache_dir));
+    let result = AocClient::from_env()
+        .map(|client| InputManager::new(cache, client.with_base_url(args.base_url)))
+        .and_then(|manager| manager.fetch(args.year, args.day));
+
+    match result {
+        Ok(path) => println!("{}", path.display()),
+        Err(error) => {
+            eprintln!("day {}: {}", args.day, error);
+            exit(1);
+        }
+    }
+}
+
+#[cfg(not(feature = "json"))]
+fn submit_answer(args: SubmitArgs) {
+    needs_json(&format!("day {}: submitting", args.day));
+}
+
+#[cfg(feature = "json")]
+fn submit_answer(args: SubmitArgs) {
+    let part = Part::from_number(args.part).unwrap();
+    let answer = match args.answer {
+        Some(answer) => answer,
+        None => answer_of(args.year, args.day, part),
+    };
+
+    let result =
+        Ledger::load(args.ledger.unwrap_or_else(default_ledger_path)).and_then(|mut ledger| {
+            let client = AocClient::from_env()?.with_base_url(args.base_url);
+            submit(&client, &mut ledger, args.year, args.day, part, &answer)
+        });
+
+    match result {
+        Ok(verdict) => println!("day {} part {}: {} is {}", args.day, part, answer, verdict),
+        Err(error) => {
+            eprintln!("day {} part {}: {}", args.day, part, error);
+            exit(1);
+        }
+    }
+}
+
+/// Solves the part on the input of the day, or exits when there is no answer to submit.
+#[cfg(feature = "json")]
+fn answer_of(year: u16, day: u8, part: Part) -> Answer {
+    if registry::solver(day).is_none() {
+        eprintln!("day {} part {}: not solved yet, pass --answer", day, part);
+        exit(1);
+    }
+    let path = input_path(year, day);
+    if !is_present(&path) {
+        eprintln!(
+            "day {} part {}: {} is missing or empty, add your puzzle input or pass --answer",
+            day,
+            part,
+            path.display()
+        );
+        exit(1);
+    }
+
+    match read_input(path)
+        .map_err(|error| error.to_string())
+        .and_then(|input| solve(day, part, &input).map_err(|error| error.to_string()))
+    {
+        // Drawn letters have to be read by a human first
+        Ok(Answer::Text(text)) if text.contains('\n') => {
+            eprintln!(
+                "day {} part {}: the answer is a drawing, read it and pass it with --answer\n{}",
+                day, part, text
+            );
+            exit(1);
+        }
+        Ok(answer) => answer,
+        Err(error) => {
+            eprintln!("day {} part {}: {}", day, part, error);
+            exit(1);
+        }
+    }
+}
+
+fn verify_answers(args: VerifyArgs) {
+    let expectations = match load(args.answers.unwrap_or_else(default_answers_path)) {
+        Ok(expectations) => expectations,
+        Err(error) => {
+            eprintln!("{}", error);
+            exit(1);
+        }
+    };
+
+    let mut failed = false;
+    for expectation in expectations.iter().filter(|expectation| {
+        args.day.is_none_or(|day| expectation.day == day)
+            && args
+                .input
+                .as_ref()
+                .is_none_or(|input| &expectation.input == input)
+    }) {
+        let check = verify(expectation);
+        let label = format!(
+            "{} day {} part {}",
+            expectation.input, expectation.day, expectation.part
+        );
+        match check.outcome {
+            Outcome::Correct => println!("{}: ok ({:.3?})", label, check.duration),
+            Outcome::Wrong(answer) => {
+                eprintln!("{}: expected {}, got {}", label, expectation.answer, answer);
+                failed = true;
+            }
+            Outcome::Failed(error) => {
+                eprintln!("{}: {}", label, error);
+                failed = true;
+            }
+        }
+    }
+
+    if failed {
+        exit(1);
+    }
+}
+
+fn bench(args: BenchArgs) {
+    #[cfg(not(feature = "json"))]
+    if args.baseline.is_some() || args.save.is_some() {
+        needs_json("--baseline and --save");
+    }
+    #[cfg(not(feature = "json"))]
+    let baseline: Option<Vec<_>> = None;
+    #[cfg(feature = "json")]
+    let baseline = args.baseline.map(|path| match load_baseline(path) {
+        Ok(baseline) => baseline,
+        Err(error) => {
+            eprintln!("{}", error);
+            exit(1);
+        }
+    });
+
+    let mut failed = false;
+    let mut measurements = vec![];
+    let days = match args.day {
+        Some(day) => vec![(day, None)],
+        None => days_with_input(),
+    };
+    for (day, path) in days {
+        let Some(solver) = registry::solver(day) else {
+            eprintln!("day {}: not solved yet", day);
+            failed = true;
+            continue;
+        };
+        let result = read_input(path.unwrap_or_else(|| input_path(YEAR, day)))
+            .map_err(|error| error.to_string())
+            .and_then(|input| {
+                measure(solver, &input, args.runs as usize).map_err(|error| error.to_string())
+            });
+        match result {
+            Ok(mut day_measurements) => {
+                for measurement in &day_measurements {
+                    println!(
+                        "day {} {}: min {:.3?}, median {:.3?}, max {:.3?}",
+                        measurement.day,
+                        measurement.stage,
+                        measurement.min(),
+                        measurement.median(),
+                        measurement.max()
+                    );
+                }
+                measurements.append(&mut day_measurements);
+            }
+            Err(error) => {
+                eprintln!("day {}: {}", day, error);
+                failed = true;
+            }
+        }
+    }
+
+    #[cfg(feature = "json")]
+    if let Some(path) = args.save {
+        if let Err(error) = save_baseline(path, &measurements) {
+            eprintln!("{}", error);
+            failed = true;
+        }
+    }
+
+    if let Some(baseline) = baseline {
+        for regression in regressions(&baseline, &measurements, args.tolerance) {
+            eprintln!(
+                "day {} {}: regressed from {:.3?} to {:.3?}",
+                regression.day, regression.stage, regression.baseline, regression.current
+            );
+            failed = true;
+        }
+    }
+
+    if failed {
+        exit(1);
+    }
+}
+
+/// The solved days that have a puzzle input, with its path. The others are skipped with a note.
+fn days_with_input() -> Vec<(u8, Option<PathBuf>)> {
+    registry::solvers()
+        .iter()
+        .map(|solver| (solver.day(), input_path(YEAR, solver.day())))
+        .filter(|(day, path)| match is_present(path) {
+            true => true,
+            false => {
+                eprintln!("day {}: no puzzle input, skipped", day);
+                false
+            }
+        })
+        .map(|(day, path)| (day, Some(path)))
+        .collect()
+}
+
+/// The input of a day when none is given: the cached one, downloaded first when AOC_SESSION is
+/// set, or the committed src/dayN/input.txt when the cache cannot provide it.
+fn input_path(year: u16, day: u8) -> PathBuf {
+    let cache = InputCache::new(default_cache_dir());
+    let resolved = match AocClient::from_env() {
+        Ok(client) => InputManager::new(cache, client).resolve(year, day),
+        Err(_) => InputManager::new(cache, Offline).resolve(year, day),
+    };
+
+    match resolved {
+        Ok(path) => path,
+        Err(AocError::MissingSession) => default_input_path(day),
+        Err(error) => {
+            eprintln!(
+                "day {}: {}, using {}",
+                day,
+                error,
+                default_input_path(day).display()
+            );
+            default_input_path(day)
+        }
+    }
+}
+
+/// New days start with an empty input.txt until their input is fetched.
+fn is_present(path: &Path) -> bool {
+    path.metadata().is_ok_and(|metadata| metadata.len() > 0)
+}
+
+fn render(args: RenderArgs) {
+    let source = args
+        .input
+        .unwrap_or_else(|| InputSource::File(default_input_path(args.day)));
+    let result = source
+        .read()
+        .map_err(|error| error.to_string())
+        .and_then(|input| frames(args.day, &input).map_err(|error| error.to_string()))
+        .and_then(|frames| {
+            let written = match args.sequence {
+                true => write_sequence(
+                    &frames,
+                    args.format,
+                    &args.output,
+                    &format!("day{}", args.day),
+                ),
+                false => write_frame(frames.last().unwrap(), args.format, &args.output)
+                    .map(|_| vec![args.output.clone()]),
+            };
+            written.map_err(|error| error.to_string())
+        });
+
+    match result {
+        Ok(paths) => println!("day {}: wrote {} image(s)", args.day, paths.len()),
+        Err(error) => {
+            eprintln!("day {}: {}", args.day, error);
+            exit(1);
+        }
+    }
+}
+
+fn generate_input(args: GenerateArgs) {
+    let seed = args.seed.unwrap_or_else(|| {
+        let seed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_nanos() as u64)
+            .unwrap_or(0);
+        eprintln!("day {}: seed {}", args.day, seed);
+        seed
+    });
+    let result = Params::puzzle(args.day, seed)
+        .ok_or(GenerateError::Unsupported(args.day))
+        .map(|params| Params {
+            size: args.size.unwrap_or(params.size),
+            width: args.width.unwrap_or(params.width),
+            ..params
+        })
+        .and_then(|params| generate(args.day, params))
+        .map_err(|error| error.to_string())
+        .and_then(|input| match &args.output {
+            Some(path) => std::fs::write(path, input + "\n")
+                .map_err(|error| format!("{}: {}", path.display(), error)),
+            None => {
+                println!("{}", input);
+                Ok(())
+            }
+        });
+
+    if let Err(error) = result {
+        eprintln!("day {}: {}", args.day, error);
+        exit(1);
+    }
+}
+
+fn run(args: RunArgs) {
+    let days = match args.day {
+        Some(day) => vec![(day, None)],
+        None => days_with_input(),
+    };
+    let parts = match args.part.and_then(Part::from_number) {
+        Some(part) => vec![part],
+        None => Part::BOTH.to_vec(),
+    };
+
+    let mut player = args.animate.then(|| {
+        let (sender, receiver) = channel();
+        if args.input != Some(InputSource::Stdin) {
+            thread::spawn(move || {
+                for line in stdin().lock().lines().map_while(Result::ok) {
+                    if let Some(control) = Control::parse(&line) {
+                        let _ = sender.send(control);
+                    }
+                }
+            });
+        }
+        Player::new(stdout(), Duration::from_millis(args.delay), receiver).with_paused(args.paused)
+    });
+
+    let jobs = args.jobs.map(|jobs| jobs as usize).unwrap_or_else(|| {
+        thread::available_parallelism()
+            .map(|threads| threads.get())
+            .unwrap_or(1)
+    });
+
+    let limits = Limits {
+        timeout: args.timeout,
+        memory_bytes: args.memory.map(|megabytes| megabytes << 20),
+    };
+    let program: Option<PathBuf> = match limits.is_unlimited() {
+        true => None,
+        // The supervised children send their reports back as JSON
+        #[cfg(not(feature = "json"))]
+        false => needs_json("--timeout and --memory"),
+        #[cfg(feature = "json")]
+        false => match current_exe() {
+            Ok(program) => Some(program),
+            Err(error) => {
+                eprintln!("cannot supervise the solvers: {}", error);
+                exit(1);
+            }
+        },
+    };
+
+    let mut failed = false;
+    let mut reports = vec![];
+    let mut inputs = vec![];
+    for (day, path) in days {
+        // The committed inputs are named like in answers.toml, anything else by its path
+        let input_id = match &args.input {
+            Some(source) => source.to_string(),
+            None => "input".to_string(),
+        };
+        // An unknown day has no input worth reading, its reports say that it is not solved
+        if registry::solver(day).is_none() {
+            reports.extend(report(day, &parts, &input_id, ""));
+            continue;
+        }
+        let source = args
+            .input
+            .clone()
+            .unwrap_or_else(|| InputSource::File(path.unwrap_or_else(|| input_path(YEAR, day))));
+        let input = match source.read() {
+            Ok(input) => input,
+            Err(error) => {
+                reports.extend(Report::failed(day, &parts, &input_id, &error));
+                continue;
+            }
+        };
+
+        if let Some(player) = player.as_mut() {
+            for part in &parts {
+                match animate(day, *part, &input, player) {
+                    Ok(answer) => println!("day {} part {}: {}", day, part, answer),
+                    Err(error) => {
+                        eprintln!("day {} part {}: {}", day, part, error);
+                        failed = true;
+                    }
+                }
+            }
+            continue;
+        }
+
+        inputs.push((day, input_id, input));
+    }
+
+    let start = Instant::now();
+    match (&program, args.all) {
+        #[cfg(feature = "json")]
+        (Some(program), true) => {
+            reports.extend(supervise_parallel(program, &inputs, &parts, jobs, &limits))
+        }
+        #[cfg(feature = "json")]
+        (Some(program), false) => {
+            reports.extend(supervise_parallel(program, &inputs, &parts, 1, &limits))
+        }
+        (_, true) => reports.extend(report_parallel(&inputs, &parts, jobs)),
+        (_, false) => reports.extend(
+            inputs
+                .iter()
+                .flat_map(|(day, input_id, input)| report(*day, &parts, input_id, input)),
+        ),
+    }
+    let total = start.elapsed();
+    reports.sort_by_key(|report| report.day);
+
+    failed |= reports.iter().any(|report| !report.is_ok());
+    match args.format {
+        ReportFormat::Text => {
+            for report in &reports {
+                match report.is_ok() {
+                    true => println!("{}", report),
+                    false => eprintln!("{}", report),
+                }
+            }
+        }
+        #[cfg(feature = "json")]
+        format => print!("{}", encode(&reports, format)),
+    }
+    if args.all {
+        let summary = format!("all days: {:.3?} on {} thread(s)", total, jobs);
+        match args.format {
+            ReportFormat::Text => println!("{}", summary),
+            #[cfg(feature = "json")]
+            _ => eprintln!("{}", summary),
+        }
+    }
+
+    if failed {
+        exit(1);
+    }
+}
+
+fn extract_example(args: ExtractArgs) {
+    let manifest = args.manifest.unwrap_or_else(default_examples_path);
+    let result = read_html(&args.html).and_then(|html| {
+        let example = extract(&html)?;
+        save(Path::new(env!("CARGO_MANIFEST_DIR")), &manifest, &example)
+            .map(|files| (example, files))
+    });
+
+    match result {
+        Ok((example, files)) => {
+            for (part, answer) in &example.answers {
+                println!("day {} part {}: {}", example.day, part, answer);
+            }
+            for file in files {
+                println!("{}", file.display());
+            }
+        }
+        Err(error) => {
+            eprintln!("{}: {}", args.html.display(), error);
+            exit(1);
+        }
+    }
+}
+
+fn repl(args: ReplArgs) {
+    if registry::solver(args.day).is_none() {
+        eprintln!("day {}: not solved yet", args.day);
+        exit(1);
+    }
+    let source = args
+        .input
+        .unwrap_or_else(|| InputSource::File(input_path(YEAR, args.day)));
+    if source == InputSource::Stdin && args.script.is_none() {
+        eprintln!(
+            "day {}: commands come from stdin, so the input needs --script",
+            args.day
+        );
+        exit(1);
+    }
+    let mut session = match source
+        .read()
+        .map_err(|error| error.to_string())
+        .and_then(|input| Session::new(args.day, &input))
+    {
+        Ok(session) => session,
+        Err(error) => {
+            eprintln!("day {}: {}", args.day, error);
+            exit(1);
+        }
+    };
+
+    let result = match &args.script {
+        Some(path) => File::open(path)
+            .and_then(|file| run_repl(&mut session, BufReader::new(file), stdout(), true)),
+        None => run_repl(&mut session, stdin().lock(), stdout(), false),
+    };
+    match result {
+        Ok(0) => {}
+        Ok(_) => exit(1),
+        Err(error) => {
+            eprintln!("day {}: {}", args.day, error);
+            exit(1);
+        }
+    }
+}
+
+fn new_day(args: NewDayArgs) {
+    match scaffold(Path::new(env!("CARGO_MANIFEST_DIR")), args.day) {
+        Ok(files) => {
+            for file in files {
+                println!("{}", file.display());
+            }
+        }
+        Err(error) => {
+            eprintln!("day {}: {}", args.day, error);
+            exit(1);
+        }
+    }
+}
+
+#[cfg(not(feature = "json"))]
+fn solve_task(_: SolveTaskArgs) {
+    needs_json("solve-task");
+}
+
+#[cfg(feature = "json")]
+fn solve_task(args: SolveTaskArgs) {
+    let part = Part::from_number(args.part).unwrap();
+    let input = match read_to_string(stdin()) {
+        Ok(input) => input,
+        Err(error) => {
+            eprintln!("could not read stdin: {}", error);
+            exit(1);
+        }
+    };
+
+    print!(
+        "{}",
+        encode(
+            &report(args.day, &[part], "", &input),
+            ReportFormat::JsonLines
+        )
+    );
+}
+
+#[cfg(not(feature = "json"))]
+fn needs_json(what: &str) -> ! {
+    eprintln!("{} needs the json feature", what);
+    exit(1);
+}
@@ -0,0 +1,161 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// A malformed puzzle input. Line and column are 1-based, `text` is the offending part of the line.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseError {
+    pub day: u8,
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub reason: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "day {}, line {}, column {}: {} (found {:?})",
+            self.day, self.line, self.column, self.reason, self.text
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// One line of a puzzle input together with the position information needed to report errors.
+#[derive(Debug, Copy, Clone)]
+pub struct InputLine<'a> {
+    pub day: u8,
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> InputLine<'a> {
+    pub fn new(day: u8, index: usize, text: &'a str) -> InputLine<'a> {
+        InputLine {
+            day,
+            number: index + 1,
+            text,
+        }
+    }
+
+    /// Creates an error pointing at `part`, which should be a slice of this line's text.
+    pub fn error(&self, part: &str, reason: impl Into<String>) -> ParseError {
+        ParseError {
+            day: self.day,
+            line: self.number,
+            column: self.column_of(part),
+            text: part.to_string(),
+            reason: reason.into(),
+        }
+    }
+
+    pub fn parse<T: FromStr>(&self, part: &str, what: &str) -> Result<T, ParseError> {
+        part.trim()
+            .parse::<T>()
+            .map_err(|_| self.error(part, format!("expected {}", what)))
+    }
+
+    pub fn split_once(&self, delimiter: &str) -> Result<(&'a str, &'a str), ParseError> {
+        self.text
+            .split_once(delimiter)
+            .ok_or_else(|| self.error(self.text, format!("expected {:?}", delimiter)))
+    }
+
+    pub fn digits(&self) -> Result<Vec<usize>, ParseError> {
+        self.text
+            .char_indices()
+            .map(|(index, char)| {
+                char.to_digit(10)
+                    .map(|digit| digit as usize)
+                    .ok_or_else(|| {
+                        self.error(
+                            &self.text[index..index + char.len_utf8()],
+                            "expected a digit",
+                        )
+                    })
+            })
+            .collect()
+    }
+
+    fn column_of(&self, part: &str) -> usize {
+        let start = self.text.as_ptr() as usize;
+        let position = part.as_ptr() as usize;
+
+        match position >= start && position + part.len() <= start + self.text.len() {
+            true => self.text[..position - start].chars().count() + 1,
+            false => 1,
+        }
+    }
+}
+
+pub fn input_lines(day: u8, input: &str) -> impl Iterator<Item = InputLine<'_>> {
+    input
+        .lines()
+        .enumerate()
+        .map(move |(index, text)| InputLine::new(day, index, text))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse::*;
+
+    #[test]
+    fn test_error_points_at_part() {
+        let line = InputLine::new(5, 2, "0,9 -> 5,x");
+
+        let error = line.error(&line.text[9..], "expected a number");
+
+        assert_eq!(
+            ParseError {
+                day: 5,
+                line: 3,
+                column: 10,
+                text: "x".to_string(),
+                reason: "expected a number".to_string(),
+            },
+            error
+        );
+        assert_eq!(
+            "day 5, line 3, column 10: expected a number (found \"x\")",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn test_error_for_foreign_text_points_at_line_start() {
+        let line = InputLine::new(1, 0, "abc");
+
+        assert_eq!(1, line.error("other", "oops").column);
+    }
+
+    #[test]
+    fn test_parse() {
+        let line = InputLine::new(2, 0, "forward x");
+
+        assert_eq!(Ok(7), line.parse::<i32>("7", "a number"));
+
+        let error = line.parse::<i32>(&line.text[8..], "a number").unwrap_err();
+        assert_eq!(9, error.column);
+        assert_eq!("expected a number", error.reason);
+    }
+
+    #[test]
+    fn test_digits() {
+        assert_eq!(Ok(vec![2, 1, 9]), InputLine::new(9, 0, "219").digits());
+
+        let error = InputLine::new(9, 4, "21a9").digits().unwrap_err();
+        assert_eq!((5, 3, "a"), (error.line, error.column, error.text.as_str()));
+    }
+
+    #[test]
+    fn test_input_lines_are_numbered_from_1() {
+        let numbers = input_lines(1, "a\nb\n")
+            .map(|line| (line.number, line.text))
+            .collect::<Vec<(usize, &str)>>();
+
+        assert_eq!(vec![(1, "a"), (2, "b")], numbers);
+    }
+}
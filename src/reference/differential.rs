@@ -0,0 +1,176 @@
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use crate::generate::{generate, Params};
+use crate::reference;
+use crate::runner;
+use crate::solution::{Answer, Part};
+
+/// An input on which a solver and the reference give different answers.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Disagreement {
+    pub day: u8,
+    pub part: Part,
+    pub params: Params,
+    pub input: String,
+    pub expected: Answer,
+    pub actual: Result<Answer, String>,
+}
+
+impl Display for Disagreement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let actual = match &self.actual {
+            Ok(answer) => answer.to_string(),
+            Err(error) => error.clone(),
+        };
+        write!(
+            f,
+            "day {} part {} with seed {}, size {} and width {}: expected {}, got {}\n{}",
+            self.day,
+            self.part,
+            self.params.seed,
+            self.params.size,
+            self.params.width,
+            self.expected,
+            actual,
+            self.input
+        )
+    }
+}
+
+/// Narrow enough that the growing size alone decides how large an input gets.
+fn small_width(day: u8) -> usize {
+    match day {
+        3 => 6,
+        4 => 30,
+        5 => 10,
+        7 => 20,
+        9 => 8,
+        10 => 12,
+        11 => 10,
+        13 => 3,
+        14 => 4,
+        15 => 8,
+        17 => 6,
+        _ => 0,
+    }
+}
+
+/// Compares the registered solver against the reference, see [`find_disagreement`].
+pub fn check(day: u8, part: Part, seeds: Range<u64>, max_size: usize) -> Option<Disagreement> {
+    find_disagreement(day, part, seeds, max_size, |input| {
+        runner::solve(day, part, input).map_err(|error| error.to_string())
+    })
+}
+
+/// Generates inputs for every size from 1 to `max_size` and every seed, and returns the
+/// shortest input of the smallest size on which `candidate` disagrees with the reference.
+/// A panicking candidate disagrees, too.
+pub fn find_disagreement(
+    day: u8,
+    part: Part,
+    seeds: Range<u64>,
+    max_size: usize,
+    candidate: impl Fn(&str) -> Result<Answer, String>,
+) -> Option<Disagreement> {
+    (1..=max_size).find_map(|size| {
+        seeds
+            .clone()
+            .filter_map(|seed| {
+                let params = Params {
+                    seed,
+                    size,
+                    width: small_width(day),
+                };
+                let input = generate(day, params).ok()?;
+                let expected = reference::solve(day, part, &input)?;
+                let actual = catch_unwind(AssertUnwindSafe(|| candidate(&input)))
+                    .unwrap_or_else(|_| Err("a panic".to_string()));
+
+                (actual.as_ref() != Ok(&expected)).then_some(Disagreement {
+                    day,
+                    part,
+                    params,
+                    input,
+                    expected,
+                    actual,
+                })
+            })
+            .min_by_key(|disagreement| disagreement.input.len())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use crate::reference::differential::*;
+
+    #[rstest]
+    #[case(1, Part::One)]
+    #[case(1, Part::Two)]
+    #[case(2, Part::One)]
+    #[case(2, Part::Two)]
+    #[case(3, Part::One)]
+    #[case(3, Part::Two)]
+    #[case(4, Part::One)]
+    #[case(4, Part::Two)]
+    #[case(5, Part::One)]
+    #[case(5, Part::Two)]
+    #[case(6, Part::One)]
+    #[case(7, Part::One)]
+    #[case(7, Part::Two)]
+    #[case(8, Part::One)]
+    #[case(8, Part::Two)]
+    #[case(9, Part::One)]
+    #[case(9, Part::Two)]
+    #[case(10, Part::One)]
+    #[case(10, Part::Two)]
+    #[case(11, Part::One)]
+    #[case(11, Part::Two)]
+    #[case(12, Part::One)]
+    #[case(12, Part::Two)]
+    #[case(13, Part::One)]
+    #[case(13, Part::Two)]
+    #[case(14, Part::One)]
+    #[case(15, Part::One)]
+    #[case(15, Part::Two)]
+    #[case(16, Part::One)]
+    #[case(16, Part::Two)]
+    #[case(17, Part::One)]
+    #[case(17, Part::Two)]
+    #[case(18, Part::One)]
+    #[case(18, Part::Two)]
+    fn test_solver_agrees_with_reference(#[case] day: u8, #[case] part: Part) {
+        if let Some(disagreement) = check(day, part, 0..4, 8) {
+            panic!("{}", disagreement);
+        }
+    }
+
+    #[test]
+    fn test_reports_smallest_disagreement() {
+        let off_by_one_from_four_lines = |input: &str| {
+            let answer = reference::solve(1, Part::One, input).unwrap();
+            match (input.lines().count(), answer) {
+                (4.., Answer::Number(number)) => Ok(Answer::Number(number + 1)),
+                (_, answer) => Ok(answer),
+            }
+        };
+
+        let disagreement =
+            find_disagreement(1, Part::One, 0..3, 10, off_by_one_from_four_lines).unwrap();
+
+        assert_eq!(4, disagreement.params.size);
+        assert_eq!(4, disagreement.input.lines().count());
+    }
+
+    #[test]
+    fn test_panics_are_disagreements() {
+        let disagreement =
+            find_disagreement(7, Part::One, 0..1, 3, |_| panic!("not implemented")).unwrap();
+
+        assert_eq!(1, disagreement.params.size);
+        assert_eq!(Err("a panic".to_string()), disagreement.actual);
+    }
+}
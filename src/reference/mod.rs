@@ -0,0 +1,931 @@
+//! Deliberately simple solutions, written straight from the puzzle texts. They are slow and
+//! trust their input, but have no shortcuts to get wrong, which makes them the yardstick for
+//! the real solvers in [`differential`].
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::solution::{Answer, Part};
+
+pub mod differential;
+
+/// The reference answer, `None` where there is no feasible naive way, like 256 days of
+/// single fish. Panics on malformed input.
+pub fn solve(day: u8, part: Part, input: &str) -> Option<Answer> {
+    let lines = input.lines().collect::<Vec<&str>>();
+
+    Some(match (day, part) {
+        (1, part) => sonar_sweep(&lines, part).into(),
+        (2, part) => dive(&lines, part).into(),
+        (3, Part::One) => power_consumption(&lines).into(),
+        (3, Part::Two) => life_support_rating(&lines).into(),
+        (4, part) => bingo(&lines, part).into(),
+        (5, part) => vents(&lines, part).into(),
+        (6, Part::One) => lanternfish(input, 80).into(),
+        (7, part) => crabs(input, part).into(),
+        (8, Part::One) => easy_digits(&lines).into(),
+        (8, Part::Two) => decode_displays(&lines).into(),
+        (9, Part::One) => low_point_risk(&lines).into(),
+        (9, Part::Two) => largest_basins(&lines).into(),
+        (10, part) => syntax_scores(&lines, part).into(),
+        (11, part) => octopuses(&lines, part).into(),
+        (12, part) => cave_paths(&lines, part).into(),
+        (13, Part::One) => fold_paper(&lines, true).len().into(),
+        (13, Part::Two) => draw_dots(&fold_paper(&lines, false)).into(),
+        (14, Part::One) => polymer(&lines, 10).into(),
+        (15, Part::One) => lowest_risk(&digits(&lines)).into(),
+        (15, Part::Two) => lowest_risk(&tile(&digits(&lines), 5)).into(),
+        (16, Part::One) => packet(&binary(input), 0).0.into(),
+        (16, Part::Two) => (packet(&binary(input), 0).1 as usize).into(),
+        (17, part) => trick_shots(input, part).into(),
+        (18, part) => snailfish_homework(&lines, part).into(),
+        _ => return None,
+    })
+}
+
+fn numbers(text: &str, separator: char) -> Vec<isize> {
+    text.split(separator)
+        .filter(|number| !number.is_empty())
+        .map(|number| number.trim().parse().unwrap())
+        .collect()
+}
+
+fn digits(lines: &[&str]) -> Vec<Vec<usize>> {
+    lines
+        .iter()
+        .map(|line| line.bytes().map(|byte| (byte - b'0') as usize).collect())
+        .collect()
+}
+
+fn sonar_sweep(lines: &[&str], part: Part) -> usize {
+    let depths = lines
+        .iter()
+        .map(|line| line.parse::<isize>().unwrap())
+        .collect::<Vec<isize>>();
+    let window = match part {
+        Part::One => 1,
+        Part::Two => 3,
+    };
+
+    let sums = depths
+        .windows(window)
+        .map(|window| window.iter().sum())
+        .collect::<Vec<isize>>();
+    (1..sums.len()).filter(|&i| sums[i] > sums[i - 1]).count()
+}
+
+fn dive(lines: &[&str], part: Part) -> isize {
+    let (mut position, mut depth, mut aim) = (0, 0, 0);
+
+    for line in lines {
+        let (command, amount) = line.split_once(' ').unwrap();
+        let amount = amount.parse::<isize>().unwrap();
+        match (command, part) {
+            ("forward", Part::One) => position += amount,
+            ("forward", Part::Two) => {
+                position += amount;
+                depth += aim * amount;
+            }
+            ("down", Part::One) => depth += amount,
+            ("up", Part::One) => depth -= amount,
+            ("down", Part::Two) => aim += amount,
+            ("up", Part::Two) => aim -= amount,
+            _ => panic!("unknown command {}", line),
+        }
+    }
+
+    position * depth
+}
+
+fn ones_at(lines: &[&str], position: usize) -> usize {
+    lines
+        .iter()
+        .filter(|line| line.as_bytes()[position] == b'1')
+        .count()
+}
+
+/// A column with as many ones as zeros counts as zero for the gamma rate.
+fn power_consumption(lines: &[&str]) -> isize {
+    let width = lines[0].len();
+    let gamma = (0..width)
+        .map(
+            |position| match 2 * ones_at(lines, position) > lines.len() {
+                true => '1',
+                false => '0',
+            },
+        )
+        .collect::<String>();
+    let epsilon = gamma
+        .chars()
+        .map(|bit| if bit == '1' { '0' } else { '1' })
+        .collect::<String>();
+
+    isize::from_str_radix(&gamma, 2).unwrap() * isize::from_str_radix(&epsilon, 2).unwrap()
+}
+
+fn life_support_rating(lines: &[&str]) -> isize {
+    let rating = |keep_most_common: bool| {
+        let mut candidates = lines.to_vec();
+        let mut position = 0;
+        while candidates.len() > 1 {
+            let ones = ones_at(&candidates, position);
+            let most_common = match 2 * ones >= candidates.len() {
+                true => b'1',
+                false => b'0',
+            };
+            candidates
+                .retain(|line| (line.as_bytes()[position] == most_common) == keep_most_common);
+            position += 1;
+        }
+        isize::from_str_radix(candidates[0], 2).unwrap()
+    };
+
+    rating(true) * rating(false)
+}
+
+/// Computes for every board the draw that completes it, then picks the first or the last.
+/// Boards completing on the same draw are ordered by their position in the input.
+fn bingo(lines: &[&str], part: Part) -> isize {
+    let draws = numbers(lines[0], ',');
+    let boards = lines[1..]
+        .split(|line| line.trim().is_empty())
+        .filter(|rows| !rows.is_empty())
+        .map(|rows| {
+            rows.iter()
+                .map(|row| numbers(row, ' '))
+                .collect::<Vec<Vec<isize>>>()
+        })
+        .collect::<Vec<Vec<Vec<isize>>>>();
+
+    let results = boards.iter().enumerate().map(|(index, board)| {
+        let is_drawn = |turn: usize, number: &isize| draws[..=turn].contains(number);
+        let turn = (0..draws.len())
+            .find(|&turn| {
+                (0..5).any(|i| {
+                    (0..5).all(|j| is_drawn(turn, &board[i][j]))
+                        || (0..5).all(|j| is_drawn(turn, &board[j][i]))
+                })
+            })
+            .unwrap();
+        let unmarked = board
+            .iter()
+            .flatten()
+            .filter(|number| !is_drawn(turn, number))
+            .sum::<isize>();
+        ((turn, index), unmarked * draws[turn])
+    });
+
+    let (_, score) = match part {
+        Part::One => results.min_by_key(|(order, _)| *order),
+        Part::Two => results.max_by_key(|(order, _)| *order),
+    }
+    .unwrap();
+    score
+}
+
+/// Tests every point of the floor against every line.
+fn vents(lines: &[&str], part: Part) -> usize {
+    let segments = lines
+        .iter()
+        .map(|line| {
+            let (from, to) = line.split_once(" -> ").unwrap();
+            let from = numbers(from, ',');
+            let to = numbers(to, ',');
+            (from[0], from[1], to[0], to[1])
+        })
+        .filter(|(x1, y1, x2, y2)| part == Part::Two || x1 == x2 || y1 == y2)
+        .collect::<Vec<(isize, isize, isize, isize)>>();
+
+    let covers = |(x1, y1, x2, y2): (isize, isize, isize, isize), x: isize, y: isize| {
+        let steps = (x2 - x1).abs().max((y2 - y1).abs());
+        (0..=steps)
+            .any(|step| x1 + (x2 - x1).signum() * step == x && y1 + (y2 - y1).signum() * step == y)
+    };
+
+    let size = segments
+        .iter()
+        .map(|(x1, y1, x2, y2)| x1.max(y1).max(x2).max(y2) + 1)
+        .max()
+        .unwrap_or(0);
+    (0..size)
+        .flat_map(|x| (0..size).map(move |y| (x, y)))
+        .filter(|&(x, y)| {
+            segments
+                .iter()
+                .filter(|segment| covers(**segment, x, y))
+                .count()
+                >= 2
+        })
+        .count()
+}
+
+/// One entry per fish.
+fn lanternfish(input: &str, days: usize) -> usize {
+    let mut fish = numbers(input.trim(), ',');
+
+    for _ in 0..days {
+        let newborn = fish.iter().filter(|timer| **timer == 0).count();
+        for timer in fish.iter_mut() {
+            *timer = match *timer {
+                0 => 6,
+                timer => timer - 1,
+            };
+        }
+        fish.extend(vec![8; newborn]);
+    }
+
+    fish.len()
+}
+
+/// Walks every crab step by step to every position in range.
+fn crabs(input: &str, part: Part) -> isize {
+    let positions = numbers(input.trim(), ',');
+    let (min, max) = (
+        *positions.iter().min().unwrap(),
+        *positions.iter().max().unwrap(),
+    );
+
+    (min..=max)
+        .map(|target| {
+            positions
+                .iter()
+                .map(|position| {
+                    let steps = (position - target).abs();
+                    match part {
+                        Part::One => steps,
+                        Part::Two => (1..=steps).sum(),
+                    }
+                })
+                .sum::<isize>()
+        })
+        .min()
+        .unwrap()
+}
+
+fn easy_digits(lines: &[&str]) -> usize {
+    lines
+        .iter()
+        .flat_map(|line| line.split(" | ").nth(1).unwrap().split_whitespace())
+        .filter(|pattern| matches!(pattern.len(), 2 | 3 | 4 | 7))
+        .count()
+}
+
+const DIGIT_SEGMENTS: [&str; 10] = [
+    "abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg",
+];
+
+fn permutations(items: Vec<u8>) -> Vec<Vec<u8>> {
+    if items.len() <= 1 {
+        return vec![items];
+    }
+
+    (0..items.len())
+        .flat_map(|index| {
+            let mut rest = items.clone();
+            let first = rest.remove(index);
+            permutations(rest).into_iter().map(move |mut permutation| {
+                permutation.insert(0, first);
+                permutation
+            })
+        })
+        .collect()
+}
+
+/// Tries all 5040 wirings until every pattern of the note shows a digit.
+fn decode_displays(lines: &[&str]) -> usize {
+    let wirings = permutations(b"abcdefg".to_vec());
+    let sorted = |pattern: &str| {
+        let mut segments = pattern.bytes().collect::<Vec<u8>>();
+        segments.sort();
+        segments
+    };
+
+    lines
+        .iter()
+        .map(|line| {
+            let (patterns, display) = line.split_once(" | ").unwrap();
+            let digit_of = |wiring: &[u8], pattern: &str| {
+                let segments = sorted(
+                    &pattern
+                        .bytes()
+                        .map(|wire| {
+                            (b'a' + wiring.iter().position(|w| *w == wire).unwrap() as u8) as char
+                        })
+                        .collect::<String>(),
+                );
+                DIGIT_SEGMENTS
+                    .iter()
+                    .position(|digit| sorted(digit) == segments)
+            };
+            let wiring = wirings
+                .iter()
+                .find(|wiring| {
+                    patterns
+                        .split_whitespace()
+                        .all(|pattern| digit_of(wiring, pattern).is_some())
+                })
+                .unwrap();
+
+            display.split_whitespace().fold(0, |value, pattern| {
+                10 * value + digit_of(wiring, pattern).unwrap()
+            })
+        })
+        .sum()
+}
+
+fn neighbours(
+    x: usize,
+    y: usize,
+    width: usize,
+    height: usize,
+    diagonal: bool,
+) -> Vec<(usize, usize)> {
+    let mut result = vec![];
+    for dy in -1..=1_isize {
+        for dx in -1..=1_isize {
+            let is_straight = (dx == 0) != (dy == 0);
+            if (dx, dy) == (0, 0) || !diagonal && !is_straight {
+                continue;
+            }
+            let (nx, ny) = (x as isize + dx, y as isize + dy);
+            if nx >= 0 && ny >= 0 && (nx as usize) < width && (ny as usize) < height {
+                result.push((nx as usize, ny as usize));
+            }
+        }
+    }
+    result
+}
+
+fn low_point_risk(lines: &[&str]) -> usize {
+    let heights = digits(lines);
+    let (width, height) = (heights[0].len(), heights.len());
+
+    (0..height)
+        .flat_map(|y| (0..width).map(move |x| (x, y)))
+        .filter(|&(x, y)| {
+            neighbours(x, y, width, height, false)
+                .iter()
+                .all(|&(nx, ny)| heights[y][x] < heights[ny][nx])
+        })
+        .map(|(x, y)| heights[y][x] + 1)
+        .sum()
+}
+
+/// Flood fills every region of locations not of height 9.
+fn largest_basins(lines: &[&str]) -> usize {
+    let heights = digits(lines);
+    let (width, height) = (heights[0].len(), heights.len());
+    let mut visited = HashSet::new();
+    let mut sizes = vec![];
+
+    for y in 0..height {
+        for x in 0..width {
+            if heights[y][x] == 9 || !visited.insert((x, y)) {
+                continue;
+            }
+            let mut size = 0;
+            let mut queue = VecDeque::from([(x, y)]);
+            while let Some((x, y)) = queue.pop_front() {
+                size += 1;
+                for (nx, ny) in neighbours(x, y, width, height, false) {
+                    if heights[ny][nx] != 9 && visited.insert((nx, ny)) {
+                        queue.push_back((nx, ny));
+                    }
+                }
+            }
+            sizes.push(size);
+        }
+    }
+
+    sizes.sort();
+    sizes.iter().rev().take(3).product()
+}
+
+/// Removes matching pairs until none are left. What remains is either a line of
+/// openings, or its first closing bracket is the illegal one.
+fn syntax_scores(lines: &[&str], part: Part) -> usize {
+    let mut completions = lines
+        .iter()
+        .filter_map(|line| {
+            let mut line = line.to_string();
+            loop {
+                let shorter = ["()", "[]", "{}", "<>"]
+                    .iter()
+                    .fold(line.clone(), |line, pair| line.replace(pair, ""));
+                if shorter == line {
+                    break;
+                }
+                line = shorter;
+            }
+            match line.find([')', ']', '}', '>']) {
+                Some(index) => match part {
+                    Part::One => Some(match &line[index..=index] {
+                        ")" => 3,
+                        "]" => 57,
+                        "}" => 1197,
+                        _ => 25137,
+                    }),
+                    Part::Two => None,
+                },
+                None => match part {
+                    Part::One => None,
+                    Part::Two => Some(line.chars().rev().fold(0, |score, opening| {
+                        5 * score + " ([{<".find(opening).unwrap()
+                    })),
+                },
+            }
+        })
+        .collect::<Vec<usize>>();
+
+    match part {
+        Part::One => completions.iter().sum(),
+        Part::Two => {
+            completions.sort();
+            completions[completions.len() / 2]
+        }
+    }
+}
+
+fn octopuses(lines: &[&str], part: Part) -> usize {
+    let mut energy = digits(lines);
+    let (width, height) = (energy[0].len(), energy.len());
+    let mut flashes = 0;
+
+    for step in 1.. {
+        let mut flashed = HashSet::new();
+        let mut queue = VecDeque::new();
+        for (y, row) in energy.iter_mut().enumerate() {
+            for (x, level) in row.iter_mut().enumerate() {
+                *level += 1;
+                if *level > 9 {
+                    flashed.insert((x, y));
+                    queue.push_back((x, y));
+                }
+            }
+        }
+        while let Some((x, y)) = queue.pop_front() {
+            for (nx, ny) in neighbours(x, y, width, height, true) {
+                energy[ny][nx] += 1;
+                if energy[ny][nx] > 9 && flashed.insert((nx, ny)) {
+                    queue.push_back((nx, ny));
+                }
+            }
+        }
+        for (x, y) in &flashed {
+            energy[*y][*x] = 0;
+        }
+        flashes += flashed.len();
+
+        match part {
+            Part::One if step == 100 => return flashes,
+            Part::Two if flashed.len() == width * height => return step,
+            _ => {}
+        }
+    }
+    unreachable!()
+}
+
+/// Depth first search over all paths, counting each one that reaches the end.
+fn cave_paths(lines: &[&str], part: Part) -> usize {
+    let mut tunnels: HashMap<&str, Vec<&str>> = HashMap::new();
+    for line in lines {
+        let (a, b) = line.split_once('-').unwrap();
+        tunnels.entry(a).or_default().push(b);
+        tunnels.entry(b).or_default().push(a);
+    }
+
+    fn walk<'a>(
+        tunnels: &HashMap<&'a str, Vec<&'a str>>,
+        path: &mut Vec<&'a str>,
+        may_revisit: bool,
+    ) -> usize {
+        let cave = *path.last().unwrap();
+        if cave == "end" {
+            return 1;
+        }
+
+        let mut count = 0;
+        for next in tunnels.get(cave).into_iter().flatten() {
+            let is_small = next.chars().all(|c| c.is_ascii_lowercase());
+            let visited = path.contains(next);
+            let revisits = match (is_small && visited, *next) {
+                (_, "start") => continue,
+                (false, _) => false,
+                (true, _) if may_revisit && *next != "end" => true,
+                (true, _) => continue,
+            };
+            path.push(next);
+            count += walk(tunnels, path, may_revisit && !revisits);
+            path.pop();
+        }
+        count
+    }
+
+    walk(&tunnels, &mut vec!["start"], part == Part::Two)
+}
+
+/// Folds a sheet of booleans, keeping the half before each fold line.
+fn fold_paper(lines: &[&str], only_first: bool) -> Vec<(usize, usize)> {
+    let (dots, folds) = lines.split_at(lines.iter().position(|line| line.is_empty()).unwrap());
+    let dots = dots
+        .iter()
+        .map(|line| {
+            let dot = numbers(line, ',');
+            (dot[0] as usize, dot[1] as usize)
+        })
+        .collect::<Vec<(usize, usize)>>();
+    let width = dots.iter().map(|(x, _)| x + 1).max().unwrap_or(0);
+    let height = dots.iter().map(|(_, y)| y + 1).max().unwrap_or(0);
+    let mut sheet = vec![vec![false; width]; height];
+    for (x, y) in dots {
+        sheet[y][x] = true;
+    }
+
+    let folds = folds.iter().filter(|line| !line.is_empty());
+    for fold in folds.take(if only_first { 1 } else { usize::MAX }) {
+        let (axis, line) = fold
+            .trim_start_matches("fold along ")
+            .split_once('=')
+            .unwrap();
+        let line = line.parse::<usize>().unwrap();
+        let (width, height) = (sheet[0].len(), sheet.len());
+        let mut folded = match axis {
+            "x" => vec![vec![false; line]; height],
+            _ => vec![vec![false; width]; line],
+        };
+        for (y, row) in sheet.iter().enumerate() {
+            for (x, dot) in row.iter().enumerate() {
+                let (x, y) = match axis {
+                    "x" if x > line => (2 * line - x, y),
+                    "y" if y > line => (x, 2 * line - y),
+                    _ => (x, y),
+                };
+                if *dot && x < folded[0].len() && y < folded.len() {
+                    folded[y][x] = true;
+                }
+            }
+        }
+        sheet = folded;
+    }
+
+    let mut dots = vec![];
+    for (y, row) in sheet.iter().enumerate() {
+        for (x, dot) in row.iter().enumerate() {
+            if *dot {
+                dots.push((x, y));
+            }
+        }
+    }
+    dots
+}
+
+/// The dots as `*` in their bounding box from the top left corner, like `day13::fold_all`.
+fn draw_dots(dots: &[(usize, usize)]) -> String {
+    let width = dots.iter().map(|(x, _)| x + 1).max().unwrap_or(0);
+    let height = dots.iter().map(|(_, y)| y + 1).max().unwrap_or(0);
+
+    (0..height)
+        .map(|y| {
+            (0..width)
+                .map(|x| match dots.contains(&(x, y)) {
+                    true => '*',
+                    false => ' ',
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Builds the whole polymer, which doubles every step.
+fn polymer(lines: &[&str], steps: usize) -> usize {
+    let rules = lines[2..]
+        .iter()
+        .filter_map(|line| line.split_once(" -> "))
+        .map(|(pair, element)| (pair.to_string(), element.chars().next().unwrap()))
+        .collect::<HashMap<String, char>>();
+
+    let mut polymer = lines[0].chars().collect::<Vec<char>>();
+    for _ in 0..steps {
+        let mut next = vec![polymer[0]];
+        for window in polymer.windows(2) {
+            if let Some(element) = rules.get(&String::from_iter(window)) {
+                next.push(*element);
+            }
+            next.push(window[1]);
+        }
+        polymer = next;
+    }
+
+    let mut counts = HashMap::new();
+    for element in polymer {
+        *counts.entry(element).or_insert(0) += 1;
+    }
+    counts.values().max().unwrap() - counts.values().min().unwrap()
+}
+
+/// Relaxes every cell against its neighbours until nothing improves, which is slow but
+/// obviously right even when the best path goes up or left.
+fn lowest_risk(risks: &[Vec<usize>]) -> usize {
+    let (height, width) = (risks.len(), risks[0].len());
+    let mut totals = vec![vec![usize::MAX; width]; height];
+    totals[0][0] = 0;
+
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for y in 0..height {
+            for x in 0..width {
+                for (nx, ny) in neighbours(x, y, width, height, false) {
+                    if totals[ny][nx] != usize::MAX && totals[ny][nx] + risks[y][x] < totals[y][x] {
+                        totals[y][x] = totals[ny][nx] + risks[y][x];
+                        changed = true;
+                    }
+                }
+            }
+        }
+    }
+
+    totals[height - 1][width - 1]
+}
+
+fn tile(risks: &[Vec<usize>], times: usize) -> Vec<Vec<usize>> {
+    (0..risks.len() * times)
+        .map(|y| {
+            (0..risks[0].len() * times)
+                .map(|x| {
+                    let mut risk = risks[y % risks.len()][x % risks[0].len()];
+                    for _ in 0..x / risks[0].len() + y / risks.len() {
+                        risk = if risk == 9 { 1 } else { risk + 1 };
+                    }
+                    risk
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn binary(hex: &str) -> String {
+    hex.trim()
+        .chars()
+        .map(|char| format!("{:04b}", char.to_digit(16).unwrap()))
+        .collect()
+}
+
+/// The version sum and value of the packet at `start` of a string of ones and zeros, and where
+/// the next packet starts.
+fn packet(bits: &str, start: usize) -> (usize, u64, usize) {
+    let number = |from: usize, to: usize| u64::from_str_radix(&bits[from..to], 2).unwrap();
+    let version = number(start, start + 3) as usize;
+    let type_id = number(start + 3, start + 6);
+
+    if type_id == 4 {
+        let (mut value, mut position) = (0, start + 6);
+        loop {
+            value = value * 16 + number(position + 1, position + 5);
+            position += 5;
+            if &bits[position - 5..position - 4] == "0" {
+                return (version, value, position);
+            }
+        }
+    }
+
+    let mut results = vec![];
+    let mut position;
+    if &bits[start + 6..start + 7] == "0" {
+        let end = start + 22 + number(start + 7, start + 22) as usize;
+        position = start + 22;
+        while position < end {
+            let result = packet(bits, position);
+            position = result.2;
+            results.push(result);
+        }
+    } else {
+        position = start + 18;
+        for _ in 0..number(start + 7, start + 18) {
+            let result = packet(bits, position);
+            position = result.2;
+            results.push(result);
+        }
+    }
+
+    let versions = version + results.iter().map(|result| result.0).sum::<usize>();
+    let values = results.iter().map(|result| result.1).collect::<Vec<u64>>();
+    let value = match type_id {
+        0 => values.iter().sum(),
+        1 => values.iter().product(),
+        2 => *values.iter().min().unwrap(),
+        3 => *values.iter().max().unwrap(),
+        5 => (values[0] > values[1]) as u64,
+        6 => (values[0] < values[1]) as u64,
+        _ => (values[0] == values[1]) as u64,
+    };
+    (versions, value, position)
+}
+
+/// Shoots with every velocity that does not fly away at once, and upwards up to twice as fast
+/// as the target is deep.
+fn trick_shots(input: &str, part: Part) -> usize {
+    let (x, y) = input.trim()["target area: x=".len()..]
+        .split_once(", y=")
+        .unwrap();
+    let (x_min, x_max) = x.split_once("..").unwrap();
+    let (y_min, y_max) = y.split_once("..").unwrap();
+    let [x_min, x_max, y_min, y_max] =
+        [x_min, x_max, y_min, y_max].map(|number| number.parse::<isize>().unwrap());
+
+    let mut highest = vec![];
+    for start_vx in 0..=x_max {
+        for start_vy in y_min..=-2 * y_min {
+            let (mut x, mut y, mut vx, mut vy) = (0, 0, start_vx, start_vy);
+            let mut top = 0;
+            while y >= y_min {
+                x += vx;
+                y += vy;
+                vx -= vx.signum();
+                vy -= 1;
+                top = top.max(y);
+                if (x_min..=x_max).contains(&x) && (y_min..=y_max).contains(&y) {
+                    highest.push(top as usize);
+                    break;
+                }
+            }
+        }
+    }
+
+    match part {
+        Part::One => highest.into_iter().max().unwrap(),
+        Part::Two => highest.len(),
+    }
+}
+
+/// Snailfish numbers as a flat list of tokens, with `None` for the brackets and commas. Explodes
+/// and splits edit the list in place, like the puzzle text does with the written number.
+fn snailfish_homework(lines: &[&str], part: Part) -> usize {
+    let numbers = lines
+        .iter()
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            line.chars()
+                .map(|char| (char, char.to_digit(10)))
+                .collect::<Vec<(char, Option<u32>)>>()
+        })
+        .collect::<Vec<Vec<(char, Option<u32>)>>>();
+
+    match part {
+        Part::One => snailfish_magnitude(
+            &numbers[1..].iter().fold(numbers[0].clone(), |sum, number| {
+                snailfish_add(&sum, number)
+            }),
+        ),
+        Part::Two => {
+            let mut largest = 0;
+            for first in 0..numbers.len() {
+                for second in 0..numbers.len() {
+                    if first != second {
+                        let sum = snailfish_add(&numbers[first], &numbers[second]);
+                        largest = largest.max(snailfish_magnitude(&sum));
+                    }
+                }
+            }
+            largest
+        }
+    }
+}
+
+fn snailfish_add(
+    left: &[(char, Option<u32>)],
+    right: &[(char, Option<u32>)],
+) -> Vec<(char, Option<u32>)> {
+    let mut sum = vec![('[', None)];
+    sum.extend_from_slice(left);
+    sum.push((',', None));
+    sum.extend_from_slice(right);
+    sum.push((']', None));
+
+    loop {
+        let mut depth = 0;
+        let mut exploding = None;
+        for (index, token) in sum.iter().enumerate() {
+            match token.0 {
+                '[' => depth += 1,
+                ']' => depth -= 1,
+                _ => {}
+            }
+            if token.0 == '['
+                && depth > 4
+                && sum[index + 1].1.is_some()
+                && sum[index + 3].1.is_some()
+            {
+                exploding = Some(index);
+                break;
+            }
+        }
+
+        if let Some(index) = exploding {
+            let (left, right) = (sum[index + 1].1.unwrap(), sum[index + 3].1.unwrap());
+            if let Some(token) = sum[..index]
+                .iter_mut()
+                .rev()
+                .find(|token| token.1.is_some())
+            {
+                token.1 = Some(token.1.unwrap() + left);
+            }
+            if let Some(token) = sum[index + 5..].iter_mut().find(|token| token.1.is_some()) {
+                token.1 = Some(token.1.unwrap() + right);
+            }
+            sum.splice(index..index + 5, [('0', Some(0))]);
+            continue;
+        }
+
+        match sum
+            .iter()
+            .position(|token| token.1.is_some_and(|value| value >= 10))
+        {
+            Some(index) => {
+                let value = sum[index].1.unwrap();
+                sum.splice(
+                    index..index + 1,
+                    [
+                        ('[', None),
+                        ('0', Some(value / 2)),
+                        (',', None),
+                        ('0', Some(value - value / 2)),
+                        (']', None),
+                    ],
+                );
+            }
+            None => return sum,
+        }
+    }
+}
+
+fn snailfish_magnitude(number: &[(char, Option<u32>)]) -> usize {
+    let mut stack = vec![];
+    for token in number {
+        match token {
+            (_, Some(value)) => stack.push(*value as usize),
+            (']', None) => {
+                let right = stack.pop().unwrap();
+                let left = stack.pop().unwrap();
+                stack.push(3 * left + 2 * right);
+            }
+            _ => {}
+        }
+    }
+    stack[0]
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use crate::answers::{default_answers_path, load};
+    use crate::input::read_input;
+    use crate::reference::*;
+
+    /// The reference must know the puzzle answers just as well as the real solvers. Only the
+    /// first days are quick enough on the full puzzle input.
+    #[rstest]
+    #[case(1, "input")]
+    #[case(2, "input")]
+    #[case(3, "input")]
+    #[case(4, "test_input")]
+    #[case(5, "test_input")]
+    #[case(6, "test_input")]
+    #[case(7, "test_input")]
+    #[case(8, "test_input")]
+    #[case(9, "test_input")]
+    #[case(10, "test_input")]
+    #[case(11, "test_input")]
+    #[case(12, "test_input")]
+    #[case(13, "test_input")]
+    #[case(14, "test_input")]
+    #[case(15, "test_input")]
+    #[case(16, "test_input")]
+    #[case(17, "test_input")]
+    #[case(18, "test_input")]
+    fn test_reference_knows_answers(#[case] day: u8, #[case] input: &str) {
+        let expectations = load(default_answers_path()).unwrap();
+
+        for expectation in expectations
+            .iter()
+            .filter(|expectation| expectation.day == day && expectation.input == input)
+        {
+            let input = read_input(expectation.input_path()).unwrap();
+
+            if let Some(answer) = solve(day, expectation.part, &input) {
+                assert_eq!(expectation.answer, answer, "part {}", expectation.part);
+            }
+        }
+    }
+
+    #[test]
+    fn test_lanternfish() {
+        assert_eq!(26, lanternfish("3,4,3,1,2", 18));
+    }
+
+    #[test]
+    fn test_no_reference_for_256_days() {
+        assert_eq!(None, solve(6, Part::Two, "3,4,3,1,2"));
+    }
+}
@@ -0,0 +1,54 @@
+use crate::solution::Solver;
+
+static SOLVERS: [&dyn Solver; 18] = [
+    &crate::day1::Day1,
+    &crate::day2::Day2,
+    &crate::day3::Day3,
+    &crate::day4::Day4,
+    &crate::day5::Day5,
+    &crate::day6::Day6,
+    &crate::day7::Day7,
+    &crate::day8::Day8,
+    &crate::day9::Day9,
+    &crate::day10::Day10,
+    &crate::day11::Day11,
+    &crate::day12::Day12,
+    &crate::day13::Day13,
+    &crate::day14::Day14,
+    &crate::day15::Day15,
+    &crate::day16::Day16,
+    &crate::day17::Day17,
+    &crate::day18::Day18,
+];
+
+pub fn solvers() -> &'static [&'static dyn Solver] {
+    &SOLVERS
+}
+
+pub fn solver(day: u8) -> Option<&'static dyn Solver> {
+    SOLVERS.iter().find(|solver| solver.day() == day).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::registry::*;
+
+    #[test]
+    fn test_solvers_are_in_day_order() {
+        let days = solvers()
+            .iter()
+            .map(|solver| solver.day())
+            .collect::<Vec<u8>>();
+
+        // Days added with new-day come after the solved ones
+        assert!(days.windows(2).all(|pair| pair[0] < pair[1]), "{:?}", days);
+        assert_eq!((1..=18).collect::<Vec<u8>>(), days[..18]);
+    }
+
+    #[test]
+    fn test_solver() {
+        assert_eq!(Some(9), solver(9).map(|solver| solver.day()));
+        assert!(solver(0).is_none());
+        assert!(solver(26).is_none());
+    }
+}
@@ -0,0 +1,311 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter, Write as _};
+use std::fs::{create_dir_all, write};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::grid::Grid;
+use crate::parse::ParseError;
+use crate::{day11, day13, day15, day5, day9};
+
+/// One picture, one color per grid cell.
+pub type Frame = Grid<Color>;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Color {
+    pub const BLACK: Color = Color::rgb(0, 0, 0);
+    pub const WHITE: Color = Color::rgb(255, 255, 255);
+    pub const NAVY: Color = Color::rgb(15, 15, 35);
+    pub const GOLD: Color = Color::rgb(255, 255, 102);
+
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Color {
+        Color { r, g, b }
+    }
+
+    /// Linear blend, `t = 0` being `self` and `t = 1` being `other`.
+    pub fn mix(&self, other: Color, t: f64) -> Color {
+        let t = t.clamp(0.0, 1.0);
+        let channel =
+            |from: u8, to: u8| (from as f64 + (to as f64 - from as f64) * t).round() as u8;
+
+        Color::rgb(
+            channel(self.r, other.r),
+            channel(self.g, other.g),
+            channel(self.b, other.b),
+        )
+    }
+
+    /// Well distinguishable colors for labelling regions, repeating after a while.
+    pub fn palette(index: usize) -> Color {
+        const PALETTE: [Color; 8] = [
+            Color::rgb(230, 25, 75),
+            Color::rgb(60, 180, 75),
+            Color::rgb(255, 225, 25),
+            Color::rgb(67, 99, 216),
+            Color::rgb(245, 130, 49),
+            Color::rgb(145, 30, 180),
+            Color::rgb(66, 212, 244),
+            Color::rgb(240, 50, 230),
+        ];
+
+        PALETTE[index % PALETTE.len()]
+    }
+}
+
+impl Display for Color {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Format {
+    Ppm,
+    Svg,
+}
+
+impl Format {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Format::Ppm => "ppm",
+            Format::Svg => "svg",
+        }
+    }
+
+    pub fn encode(&self, frame: &Frame) -> String {
+        match self {
+            Format::Ppm => to_ppm(frame),
+            Format::Svg => to_svg(frame),
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        match str {
+            "ppm" => Ok(Format::Ppm),
+            "svg" => Ok(Format::Svg),
+            _ => Err(format!("unknown image format {}, expected ppm or svg", str)),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum RenderError {
+    Unsupported(u8),
+    Parse(ParseError),
+    Io { path: PathBuf, source: io::Error },
+}
+
+impl Display for RenderError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RenderError::Unsupported(day) => write!(f, "day {} cannot be rendered", day),
+            RenderError::Parse(error) => write!(f, "{}", error),
+            RenderError::Io { path, source } => write!(f, "{}: {}", path.display(), source),
+        }
+    }
+}
+
+impl Error for RenderError {}
+
+impl From<ParseError> for RenderError {
+    fn from(error: ParseError) -> Self {
+        RenderError::Parse(error)
+    }
+}
+
+/// All states of a day worth looking at, in order. Days without a process yield a single frame.
+pub fn frames(day: u8, input: &str) -> Result<Vec<Frame>, RenderError> {
+    match day {
+        5 => Ok(vec![day5::render_heatmap(&day5::parse(input)?)]),
+        9 => Ok(vec![day9::render_basins(&day9::parse(input)?)]),
+        11 => Ok(day11::render_steps(day11::parse(input)?, 100)),
+        13 => Ok(day13::render_folds(day13::parse(input)?)),
+        15 => Ok(vec![day15::render_route(&day15::expand(
+            &day15::parse(input)?,
+            5,
+        ))]),
+        day => Err(RenderError::Unsupported(day)),
+    }
+}
+
+/// Plain text PPM (P3), one pixel per cell.
+pub fn to_ppm(frame: &Frame) -> String {
+    let mut ppm = format!("P3\n{} {}\n255\n", frame.width(), frame.height());
+    for row in frame.rows() {
+        let pixels = row
+            .iter()
+            .map(|color| format!("{} {} {}", color.r, color.g, color.b))
+            .collect::<Vec<String>>();
+        ppm.push_str(&pixels.join(" "));
+        ppm.push('\n');
+    }
+    ppm
+}
+
+/// One unit square per cell. Runs of equal color within a row are merged into one rectangle.
+pub fn to_svg(frame: &Frame) -> String {
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {} {}\" shape-rendering=\"crispEdges\">\n",
+        frame.width(),
+        frame.height()
+    );
+    for (y, row) in frame.rows().enumerate() {
+        let mut x = 0;
+        for run in row.chunk_by(|a, b| a == b) {
+            writeln!(
+                svg,
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"1\" fill=\"{}\"/>",
+                x,
+                y,
+                run.len(),
+                run[0]
+            )
+            .unwrap();
+            x += run.len();
+        }
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+pub fn write_frame(
+    frame: &Frame,
+    format: Format,
+    path: impl AsRef<Path>,
+) -> Result<(), RenderError> {
+    let path = path.as_ref();
+    let io_error = |source| RenderError::Io {
+        path: path.to_path_buf(),
+        source,
+    };
+
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        create_dir_all(parent).map_err(io_error)?;
+    }
+    write(path, format.encode(frame)).map_err(io_error)
+}
+
+/// Writes `<prefix>_0000.<ext>`, `<prefix>_0001.<ext>`, ... into `dir`.
+pub fn write_sequence(
+    frames: &[Frame],
+    format: Format,
+    dir: impl AsRef<Path>,
+    prefix: &str,
+) -> Result<Vec<PathBuf>, RenderError> {
+    frames
+        .iter()
+        .enumerate()
+        .map(|(index, frame)| {
+            let path = dir
+                .as_ref()
+                .join(format!("{}_{:04}.{}", prefix, index, format.extension()));
+            write_frame(frame, format, &path).map(|_| path)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::env::temp_dir;
+    use std::fs::{read_to_string, remove_dir_all};
+
+    use crate::render::*;
+
+    /// Compares against a checked-in file. Run with `UPDATE_GOLDEN=1` to rewrite it after intended changes.
+    pub fn assert_golden(path: &str, actual: &str) {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(path);
+        if std::env::var_os("UPDATE_GOLDEN").is_some() {
+            write(&path, actual).unwrap();
+        }
+
+        let expected = read_to_string(&path).unwrap();
+        assert!(
+            expected == actual,
+            "{} differs:\n{}",
+            path.display(),
+            actual
+        );
+    }
+
+    fn checkerboard() -> Frame {
+        Grid::from_fn(3, 2, |point| match (point.x + point.y) % 2 {
+            0 => Color::BLACK,
+            _ => Color::WHITE,
+        })
+    }
+
+    #[test]
+    fn test_to_ppm() {
+        assert_eq!(
+            "P3\n3 2\n255\n0 0 0 255 255 255 0 0 0\n255 255 255 0 0 0 255 255 255\n",
+            to_ppm(&checkerboard())
+        );
+    }
+
+    #[test]
+    fn test_to_svg_merges_runs() {
+        let frame = Grid::from_fn(3, 1, |point| match point.x {
+            0 => Color::BLACK,
+            _ => Color::GOLD,
+        });
+
+        assert_eq!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 3 1\" shape-rendering=\"crispEdges\">\n\
+             <rect x=\"0\" y=\"0\" width=\"1\" height=\"1\" fill=\"#000000\"/>\n\
+             <rect x=\"1\" y=\"0\" width=\"2\" height=\"1\" fill=\"#ffff66\"/>\n\
+             </svg>\n",
+            to_svg(&frame)
+        );
+    }
+
+    #[test]
+    fn test_mix() {
+        assert_eq!(
+            Color::rgb(128, 128, 128),
+            Color::BLACK.mix(Color::WHITE, 0.5)
+        );
+        assert_eq!(Color::WHITE, Color::BLACK.mix(Color::WHITE, 3.0));
+    }
+
+    #[test]
+    fn test_write_sequence() {
+        let dir = temp_dir().join(format!("aoc2021-render-{}", std::process::id()));
+
+        let paths = write_sequence(
+            &[checkerboard(), checkerboard()],
+            Format::Ppm,
+            &dir,
+            "day11",
+        )
+        .unwrap();
+
+        assert_eq!(
+            vec![dir.join("day11_0000.ppm"), dir.join("day11_0001.ppm")],
+            paths
+        );
+        assert_eq!(to_ppm(&checkerboard()), read_to_string(&paths[1]).unwrap());
+        remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_unsupported_day() {
+        assert!(matches!(
+            frames(1, "199\n"),
+            Err(RenderError::Unsupported(1))
+        ));
+    }
+}
@@ -0,0 +1,522 @@
+use std::collections::{HashMap, HashSet};
+use std::io::{self, BufRead, Write};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use crate::day10::{self, Bracket, Line, Token};
+use crate::day13::{self, Fold};
+use crate::day8::{self, Note};
+use crate::geometry::Point2;
+use crate::grid::Grid;
+use crate::parse::{InputLine, ParseError};
+use crate::registry;
+use crate::runner::panic_message;
+use crate::solution::{Parsed, Part, Solver};
+use crate::{day11, day6};
+
+const HELP: &str = "show [n]        the model, or its nth entry
+step [n]        advances day 6, 11 or 13 by one or n steps
+reset           goes back to the parsed input
+part1, part2    solves the loaded input
+solve <n|note>  day 8: decodes the nth or the given note
+state <n|line>  day 10: checks the nth or the given line
+fold <x=n|y=n>  day 13: folds the paper along the given line
+history         the commands so far, !n runs the nth again
+quit";
+
+/// What the REPL knows about the loaded input beyond the solver, for the days with steps or
+/// sub-functions to explore.
+enum Model {
+    Fish {
+        timers: HashMap<u8, usize>,
+        day: usize,
+    },
+    Octopuses {
+        energy: Grid<usize>,
+        step: usize,
+        flashes: usize,
+    },
+    Paper {
+        dots: HashSet<Point2>,
+        folds: Vec<Fold>,
+        folded: usize,
+    },
+    Notes(Vec<Note>),
+    Navigation(Vec<Vec<Token>>),
+    Lines(Vec<String>),
+}
+
+/// A loaded input of one day and the commands run on it so far.
+pub struct Session {
+    day: u8,
+    input: String,
+    solver: &'static dyn Solver,
+    parsed: Parsed,
+    model: Model,
+    history: Vec<String>,
+}
+
+impl Session {
+    /// Fails for days that are not solved yet and for inputs the day cannot parse.
+    pub fn new(day: u8, input: &str) -> Result<Session, String> {
+        let solver =
+            registry::solver(day).ok_or_else(|| format!("day {} is not solved yet", day))?;
+        let parsed = solver.parse(input).map_err(|error| error.to_string())?;
+
+        Ok(Session {
+            day,
+            input: input.to_string(),
+            solver,
+            parsed,
+            model: model(day, input).map_err(|error| error.to_string())?,
+            history: vec![],
+        })
+    }
+
+    /// Runs one command and returns what it prints. `!n` runs the nth command of the history.
+    pub fn execute(&mut self, command: &str) -> Result<String, String> {
+        let command = match command.trim().strip_prefix('!') {
+            Some(number) => number
+                .parse::<usize>()
+                .ok()
+                .and_then(|number| self.history.get(number.checked_sub(1)?))
+                .cloned()
+                .ok_or_else(|| format!("no command {} in the history", number))?,
+            None => command.trim().to_string(),
+        };
+        if command != "history" {
+            self.history.push(command.clone());
+        }
+
+        let (name, argument) = command.split_once(' ').unwrap_or((&command, ""));
+        let argument = argument.trim();
+        match name {
+            "help" => Ok(HELP.to_string()),
+            "history" => Ok(self
+                .history
+                .iter()
+                .enumerate()
+                .map(|(index, command)| format!("{} {}", index + 1, command))
+                .collect::<Vec<String>>()
+                .join("\n")),
+            "show" => self.show(argument),
+            "step" => self.step(count(argument)?),
+            "reset" => {
+                self.model = model(self.day, &self.input).map_err(|error| error.to_string())?;
+                self.show("")
+            }
+            "part1" => self.solve(Part::One),
+            "part2" => self.solve(Part::Two),
+            "solve" | "state" | "fold" => self.call(name, argument),
+            _ => Err(format!("unknown command {}, try help", name)),
+        }
+    }
+
+    /// A panicking solver ends only the command, like a failed part in `run --all`.
+    fn solve(&self, part: Part) -> Result<String, String> {
+        catch_unwind(AssertUnwindSafe(|| self.solver.run(&self.parsed, part)))
+            .map(|answer| answer.to_string())
+            .map_err(|payload| format!("panicked: {}", panic_message(payload)))
+    }
+
+    fn show(&self, argument: &str) -> Result<String, String> {
+        let index = match argument {
+            "" => None,
+            argument => Some(count(argument)? - 1),
+        };
+        let entry = |length: usize| match index {
+            Some(index) if index < length => Ok(index),
+            Some(_) => Err(format!("there are only {} entries", length)),
+            None => Err(format!("{} entries, show <n> prints one", length)),
+        };
+
+        match &self.model {
+            Model::Fish { timers, day } => Ok(format!(
+                "day {}: {} fish\n{}",
+                day,
+                timers.values().sum::<usize>(),
+                (0..=8)
+                    .map(|timer| format!("{}: {}", timer, timers.get(&timer).unwrap_or(&0)))
+                    .collect::<Vec<String>>()
+                    .join("\n")
+            )),
+            Model::Octopuses {
+                energy,
+                step,
+                flashes,
+            } => Ok(format!("step {}: {} flashes\n{}", step, flashes, energy)),
+            Model::Paper {
+                dots,
+                folds,
+                folded,
+            } => Ok(format!(
+                "{} dots after {} of {} folds\n{}",
+                dots.len(),
+                folded,
+                folds.len(),
+                draw_dots(dots)
+            )),
+            Model::Notes(notes) => entry(notes.len()).map(|index| {
+                let (code, display) = &notes[index];
+                format!("{} | {}", code.join(" "), display.join(" "))
+            }),
+            Model::Navigation(lines) => {
+                entry(lines.len()).map(|index| lines[index].iter().map(token_char).collect())
+            }
+            Model::Lines(lines) => entry(lines.len()).map(|index| lines[index].clone()),
+        }
+    }
+
+    /// A panicking step ends only the command, like a panicking part. The model may be stuck
+    /// half way through that step, so it goes back to the parsed input.
+    fn step(&mut self, steps: usize) -> Result<String, String> {
+        catch_unwind(AssertUnwindSafe(|| self.advance(steps))).unwrap_or_else(|payload| {
+            self.model = model(self.day, &self.input).map_err(|error| error.to_string())?;
+            Err(format!(
+                "panicked: {}, back at the parsed input",
+                panic_message(payload)
+            ))
+        })
+    }
+
+    fn advance(&mut self, steps: usize) -> Result<String, String> {
+        match &mut self.model {
+            Model::Fish { timers, day } => {
+                for _ in 0..steps {
+                    *timers = day6::next_day(timers);
+                    *day += 1;
+                }
+            }
+            Model::Octopuses {
+                energy,
+                step,
+                flashes,
+            } => {
+                for _ in 0..steps {
+                    *flashes += day11::do_step_and_get_flashing_points(energy).len();
+                    *step += 1;
+                }
+            }
+            Model::Paper {
+                dots,
+                folds,
+                folded,
+            } => {
+                for _ in 0..steps {
+                    let fold = folds
+                        .get(*folded)
+                        .ok_or_else(|| "all folds are done".to_string())?;
+                    *dots = fold_dots(dots, fold)?;
+                    *folded += 1;
+                }
+            }
+            _ => return Err(format!("day {} has no steps", self.day)),
+        }
+        self.show("")
+    }
+
+    /// The sub-functions of days 8, 10 and 13, on an entry of the input or on an ad-hoc line.
+    fn call(&mut self, name: &str, argument: &str) -> Result<String, String> {
+        let loaded = argument.parse::<usize>().ok();
+        match (name, &mut self.model) {
+            ("solve", Model::Notes(notes)) => {
+                let note = match loaded {
+                    Some(number) => notes.get(number.wrapping_sub(1)).cloned(),
+                    None => day8::parse(argument).map_err(error)?.pop(),
+                };
+                let note = note.ok_or_else(|| format!("no note {}", argument))?;
+                Ok(day8::solve_line(note).to_string())
+            }
+            ("state", Model::Navigation(lines)) => {
+                let line = match loaded {
+                    Some(number) => lines.get(number.wrapping_sub(1)).cloned(),
+                    None => {
+                        Some(day10::parse_line(InputLine::new(10, 0, argument)).map_err(error)?)
+                    }
+                };
+                let line = line.ok_or_else(|| format!("no line {}", argument))?;
+                Ok(describe(day10::compute_line_state(line)))
+            }
+            ("fold", Model::Paper { dots, .. }) => {
+                let line = format!("fold along {}", argument);
+                let fold = Fold::parse(InputLine::new(13, 0, &line)).map_err(error)?;
+                *dots = fold_dots(dots, &fold)?;
+                Ok(format!("{} dots\n{}", dots.len(), draw_dots(dots)))
+            }
+            _ => Err(format!("day {} has no {} command", self.day, name)),
+        }
+    }
+}
+
+fn model(day: u8, input: &str) -> Result<Model, ParseError> {
+    Ok(match day {
+        6 => Model::Fish {
+            timers: day6::group_values(day6::parse(input)?),
+            day: 0,
+        },
+        8 => Model::Notes(day8::parse(input)?),
+        10 => Model::Navigation(day10::parse(input)?),
+        11 => Model::Octopuses {
+            energy: day11::parse(input)?,
+            step: 0,
+            flashes: 0,
+        },
+        13 => {
+            let (dots, folds) = day13::parse(input)?;
+            Model::Paper {
+                dots,
+                folds,
+                folded: 0,
+            }
+        }
+        _ => Model::Lines(input.lines().map(str::to_string).collect()),
+    })
+}
+
+fn count(argument: &str) -> Result<usize, String> {
+    match argument {
+        "" => Ok(1),
+        argument => argument
+            .parse::<usize>()
+            .ok()
+            .filter(|count| *count > 0)
+            .ok_or_else(|| format!("expected a positive number, found {}", argument)),
+    }
+}
+
+fn error(error: ParseError) -> String {
+    format!(
+        "column {}: {} (found {:?})",
+        error.column, error.reason, error.text
+    )
+}
+
+/// Refuses folds too close to the edge, which would reflect dots past 0.
+fn fold_dots(dots: &HashSet<Point2>, fold: &Fold) -> Result<HashSet<Point2>, String> {
+    match dots.iter().all(|dot| fold.keeps(*dot)) {
+        true => Ok(dots.iter().map(|dot| fold.transform(*dot)).collect()),
+        false => Err(match fold {
+            Fold::X(n) => format!("folding along x={} would move dots off the paper", n),
+            Fold::Y(n) => format!("folding along y={} would move dots off the paper", n),
+        }),
+    }
+}
+
+fn draw_dots(dots: &HashSet<Point2>) -> String {
+    let width = dots.iter().map(|dot| dot.x + 1).max().unwrap_or(0);
+    let height = dots.iter().map(|dot| dot.y + 1).max().unwrap_or(0);
+
+    Grid::from_fn(width as usize, height as usize, |point| {
+        match dots.contains(&point) {
+            true => '#',
+            false => '.',
+        }
+    })
+    .to_string()
+}
+
+fn describe(line: Line) -> String {
+    match line {
+        Line::Corrupted(score) => format!("corrupted, scores {}", score),
+        Line::Complete => "complete".to_string(),
+        Line::Incomplete(open) => format!(
+            "incomplete, completed by {}",
+            open.iter()
+                .rev()
+                .map(|bracket| token_char(&Token::Close(*bracket)))
+                .collect::<String>()
+        ),
+    }
+}
+
+fn token_char(token: &Token) -> char {
+    match token {
+        Token::Open(Bracket::Round) => '(',
+        Token::Open(Bracket::Square) => '[',
+        Token::Open(Bracket::Curly) => '{',
+        Token::Open(Bracket::Pointy) => '<',
+        Token::Close(Bracket::Round) => ')',
+        Token::Close(Bracket::Square) => ']',
+        Token::Close(Bracket::Curly) => '}',
+        Token::Close(Bracket::Pointy) => '>',
+    }
+}
+
+/// Runs the commands of `commands` until it ends or says quit. A prompt is written before every
+/// command, scripts are echoed instead so that the output reads like a session. Returns how many
+/// commands failed.
+pub fn run(
+    session: &mut Session,
+    commands: impl BufRead,
+    mut output: impl Write,
+    script: bool,
+) -> io::Result<usize> {
+    let prompt = format!("day {}> ", session.day);
+    let mut failed = 0;
+    if !script {
+        write!(output, "{}", prompt)?;
+        output.flush()?;
+    }
+
+    for command in commands.lines() {
+        let command = command?;
+        if script {
+            writeln!(output, "{}{}", prompt, command)?;
+        }
+        match command.trim() {
+            command if command.is_empty() || command.starts_with('#') => {}
+            "quit" | "exit" => break,
+            command => match session.execute(command) {
+                Ok(result) => writeln!(output, "{}", result)?,
+                Err(message) => {
+                    writeln!(output, "error: {}", message)?;
+                    failed += 1;
+                }
+            },
+        }
+        if !script {
+            write!(output, "{}", prompt)?;
+            output.flush()?;
+        }
+    }
+
+    Ok(failed)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::BufReader;
+
+    use rstest::*;
+
+    use crate::input::read_input;
+    use crate::repl::*;
+
+    fn session(day: u8) -> Session {
+        Session::new(
+            day,
+            &read_input(format!("src/day{}/test_input.txt", day)).unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[rstest]
+    #[case(6, "step 18", "day 18: 26 fish")]
+    #[case(6, "step 80", "day 80: 5934 fish")]
+    #[case(11, "step 10", "step 10: 204 flashes")]
+    #[case(13, "step", "17 dots after 1 of 2 folds")]
+    fn test_step(#[case] day: u8, #[case] command: &str, #[case] expected: &str) {
+        let result = session(day).execute(command).unwrap();
+
+        assert_eq!(expected, result.lines().next().unwrap());
+    }
+
+    #[rstest]
+    #[case(
+        8,
+        "solve acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf",
+        "5353"
+    )]
+    #[case(8, "solve 1", "8394")]
+    #[case(10, "state {([(<{}[<>[]}>{[]{[(<()>", "corrupted, scores 1197")]
+    #[case(10, "state 1", "incomplete, completed by }}]])})]")]
+    #[case(10, "state ([])", "complete")]
+    fn test_sub_functions(#[case] day: u8, #[case] command: &str, #[case] expected: &str) {
+        assert_eq!(Ok(expected.to_string()), session(day).execute(command));
+    }
+
+    #[test]
+    fn test_fold_and_reset() {
+        let mut session = session(13);
+
+        let folded = session.execute("fold y=7").unwrap();
+        let reset = session.execute("reset").unwrap();
+
+        assert!(folded.starts_with("17 dots\n"));
+        assert!(reset.starts_with("18 dots after 0 of 2 folds\n"));
+    }
+
+    #[rstest]
+    #[case(13, "solve 1", "day 13 has no solve command")]
+    #[case(8, "step", "day 8 has no steps")]
+    #[case(10, "state 1,2", "column 1: expected one of ()[]{}<> (found \"1\")")]
+    #[case(10, "show 11", "there are only 10 entries")]
+    #[case(10, "!3", "no command 3 in the history")]
+    #[case(6, "jump", "unknown command jump, try help")]
+    fn test_errors(#[case] day: u8, #[case] command: &str, #[case] expected: &str) {
+        assert_eq!(Err(expected.to_string()), session(day).execute(command));
+    }
+
+    #[test]
+    fn test_fold_off_the_paper() {
+        let mut session = session(13);
+
+        let result = session.execute("fold x=2");
+
+        assert_eq!(
+            Err("folding along x=2 would move dots off the paper".to_string()),
+            result
+        );
+        assert!(session.execute("show").unwrap().starts_with("18 dots"));
+    }
+
+    #[test]
+    fn test_panicking_step() {
+        let mut session = session(6);
+
+        let result = session.execute("step 1000").unwrap_err();
+
+        assert!(result.starts_with("panicked: "));
+        assert!(result.ends_with(", back at the parsed input"));
+        assert_eq!(
+            Ok("day 18: 26 fish"),
+            session
+                .execute("step 18")
+                .as_deref()
+                .map(|shown| shown.lines().next().unwrap())
+        );
+    }
+
+    #[test]
+    fn test_panicking_part() {
+        let mut session = Session::new(8, "a b c d e f g ab abc abcd | a b c d\n").unwrap();
+
+        assert!(session
+            .execute("part2")
+            .unwrap_err()
+            .starts_with("panicked: "));
+        assert_eq!(Ok("1 part2".to_string()), session.execute("history"));
+    }
+
+    #[test]
+    fn test_history() {
+        let mut session = session(11);
+
+        session.execute("step 5").unwrap();
+        session.execute("show").unwrap();
+        let repeated = session.execute("!1").unwrap();
+        let history = session.execute("history").unwrap();
+
+        assert!(repeated.starts_with("step 10: 204 flashes"));
+        assert_eq!("1 step 5\n2 show\n3 step 5", history);
+    }
+
+    #[test]
+    fn test_script() {
+        let script = read_input("src/repl/day13_script.txt").unwrap();
+        let mut output = vec![];
+
+        let failed = run(
+            &mut session(13),
+            BufReader::new(script.as_bytes()),
+            &mut output,
+            true,
+        )
+        .unwrap();
+
+        assert_eq!(1, failed);
+        assert_eq!(
+            read_input("src/repl/day13_output.txt").unwrap(),
+            String::from_utf8(output).unwrap()
+        );
+    }
+}
@@ -0,0 +1,509 @@
+use std::fmt::{Display, Formatter};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "json")]
+use serde::{Deserialize, Serialize};
+
+use crate::input::InputError;
+use crate::registry;
+use crate::runner::{panic_message, run_parallel};
+use crate::solution::{Answer, Part};
+
+/// The outcome of one part on one input, in a shape that scripts and dashboards can consume.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct Report {
+    pub day: u8,
+    pub part: u8,
+    pub input: String,
+    #[cfg_attr(feature = "json", serde(skip_serializing_if = "Option::is_none"))]
+    pub answer: Option<Answer>,
+    pub timings: Timings,
+    #[cfg_attr(feature = "json", serde(skip_serializing_if = "Option::is_none"))]
+    pub error: Option<Diagnostic>,
+}
+
+/// Wall times in nanoseconds. Parsing is shared by both parts of a day, so both report the same `parse_ns`.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct Timings {
+    #[cfg_attr(feature = "json", serde(skip_serializing_if = "Option::is_none"))]
+    pub parse_ns: Option<u64>,
+    #[cfg_attr(feature = "json", serde(skip_serializing_if = "Option::is_none"))]
+    pub solve_ns: Option<u64>,
+}
+
+impl Timings {
+    pub fn total(&self) -> Duration {
+        Duration::from_nanos(self.parse_ns.unwrap_or(0) + self.solve_ns.unwrap_or(0))
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "json",
+    derive(Serialize, Deserialize),
+    serde(tag = "kind", rename_all = "snake_case")
+)]
+pub enum Diagnostic {
+    UnknownDay,
+    Panic {
+        message: String,
+    },
+    TimedOut {
+        limit_ms: u64,
+    },
+    OutOfMemory {
+        limit_bytes: u64,
+    },
+    Io {
+        path: String,
+        message: String,
+    },
+    Parse {
+        line: usize,
+        column: usize,
+        text: String,
+        reason: String,
+    },
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Diagnostic::UnknownDay => write!(f, "not solved yet"),
+            Diagnostic::Panic { message } => write!(f, "panicked: {}", message),
+            Diagnostic::TimedOut { limit_ms } => write!(f, "timed out after {} ms", limit_ms),
+            Diagnostic::OutOfMemory { limit_bytes } => {
+                write!(f, "out of memory ({} MB limit)", limit_bytes >> 20)
+            }
+            Diagnostic::Io { path, message } => write!(f, "could not read {}: {}", path, message),
+            Diagnostic::Parse {
+                line,
+                column,
+                text,
+                reason,
+            } => write!(
+                f,
+                "line {}, column {}: {} (found {:?})",
+                line, column, reason, text
+            ),
+        }
+    }
+}
+
+impl From<&InputError> for Diagnostic {
+    fn from(error: &InputError) -> Self {
+        match error {
+            InputError::Io { path, source } => Diagnostic::Io {
+                path: path.clone(),
+                message: source.to_string(),
+            },
+            InputError::Parse(error) => Diagnostic::Parse {
+                line: error.line,
+                column: error.column,
+                text: error.text.clone(),
+                reason: error.reason.clone(),
+            },
+        }
+    }
+}
+
+/// The JSON formats need the `json` feature.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Format {
+    Text,
+    #[cfg(feature = "json")]
+    Json,
+    #[cfg(feature = "json")]
+    JsonLines,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        match str {
+            "text" => Ok(Format::Text),
+            #[cfg(feature = "json")]
+            "json" => Ok(Format::Json),
+            #[cfg(feature = "json")]
+            "jsonl" => Ok(Format::JsonLines),
+            #[cfg(not(feature = "json"))]
+            "json" | "jsonl" => Err(format!("output format {} needs the json feature", str)),
+            _ => Err(format!(
+                "unknown output format {}, expected text, json or jsonl",
+                str
+            )),
+        }
+    }
+}
+
+impl Report {
+    fn new(day: u8, part: Part, input: &str) -> Report {
+        Report {
+            day,
+            part: part.number(),
+            input: input.to_string(),
+            answer: None,
+            timings: Timings::default(),
+            error: None,
+        }
+    }
+
+    /// The reports for a day whose input could not even be read.
+    pub fn failed(day: u8, parts: &[Part], input: &str, error: &InputError) -> Vec<Report> {
+        parts
+            .iter()
+            .map(|part| Report {
+                error: Some(error.into()),
+                ..Report::new(day, *part, input)
+            })
+            .collect()
+    }
+
+    /// The report for a part that failed before it could report anything itself.
+    pub fn diagnosed(day: u8, part: Part, input: &str, error: Diagnostic) -> Report {
+        Report {
+            error: Some(error),
+            ..Report::new(day, part, input)
+        }
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match (&self.answer, &self.error) {
+            (_, Some(error)) => write!(f, "day {} part {}: {}", self.day, self.part, error),
+            (Some(Answer::Text(answer)), None) if answer.contains('\n') => write!(
+                f,
+                "day {} part {} ({:.3?}):\n{}",
+                self.day,
+                self.part,
+                self.timings.total(),
+                answer
+            ),
+            (Some(answer), None) => write!(
+                f,
+                "day {} part {}: {} ({:.3?})",
+                self.day,
+                self.part,
+                answer,
+                self.timings.total()
+            ),
+            (None, None) => write!(f, "day {} part {}: no answer", self.day, self.part),
+        }
+    }
+}
+
+/// Parses `input` once and solves the given parts on it. `input_id` names the input in the reports.
+/// A solver that panics is reported with a `Panic` diagnostic, like in [`report_parallel`].
+pub fn report(day: u8, parts: &[Part], input_id: &str, input: &str) -> Vec<Report> {
+    let solver = match registry::solver(day) {
+        Some(solver) => solver,
+        None => {
+            return parts
+                .iter()
+                .map(|part| Report {
+                    error: Some(Diagnostic::UnknownDay),
+                    ..Report::new(day, *part, input_id)
+                })
+                .collect()
+        }
+    };
+
+    let start = Instant::now();
+    let parsed = catch_unwind(AssertUnwindSafe(|| solver.parse(input))).map_err(panic_message);
+    let parse_ns = Some(start.elapsed().as_nanos() as u64);
+
+    parts
+        .iter()
+        .map(|part| {
+            let report = Report::new(day, *part, input_id);
+            match &parsed {
+                Ok(Ok(parsed)) => {
+                    let start = Instant::now();
+                    let answer = catch_unwind(AssertUnwindSafe(|| solver.run(parsed, *part)));
+                    let timings = Timings {
+                        parse_ns,
+                        solve_ns: Some(start.elapsed().as_nanos() as u64),
+                    };
+                    match answer {
+                        Ok(answer) => Report {
+                            answer: Some(answer),
+                            timings,
+                            ..report
+                        },
+                        Err(payload) => Report {
+                            timings,
+                            error: Some(Diagnostic::Panic {
+                                message: panic_message(payload),
+                            }),
+                            ..report
+                        },
+                    }
+                }
+                Ok(Err(error)) => Report {
+                    timings: Timings {
+                        parse_ns,
+                        solve_ns: None,
+                    },
+                    error: Some((&InputError::Parse(error.clone())).into()),
+                    ..report
+                },
+                Err(message) => Report {
+                    timings: Timings {
+                        parse_ns,
+                        solve_ns: None,
+                    },
+                    error: Some(Diagnostic::Panic {
+                        message: message.clone(),
+                    }),
+                    ..report
+                },
+            }
+        })
+        .collect()
+}
+
+/// One task per day and part, run by `threads` workers. The reports come in the order of
+/// `inputs` and `parts`, each part timing its own parse. `inputs` holds the day, the input
+/// identifier and the input.
+pub fn report_parallel(
+    inputs: &[(u8, String, String)],
+    parts: &[Part],
+    threads: usize,
+) -> Vec<Report> {
+    let tasks = inputs
+        .iter()
+        .flat_map(|(day, input_id, input)| {
+            parts
+                .iter()
+                .map(move |part| move || report(*day, &[*part], input_id, input).remove(0))
+        })
+        .collect::<Vec<_>>();
+    let keys = inputs
+        .iter()
+        .flat_map(|(day, input_id, _)| parts.iter().map(move |part| (*day, *part, input_id)));
+
+    run_parallel(tasks, threads)
+        .into_iter()
+        .zip(keys)
+        .map(|(result, (day, part, input_id))| {
+            result.unwrap_or_else(|message| {
+                Report::diagnosed(day, part, input_id, Diagnostic::Panic { message })
+            })
+        })
+        .collect()
+}
+
+/// JSON is one array of all reports, JSON lines one object per line. Text is meant for humans.
+pub fn encode(reports: &[Report], format: Format) -> String {
+    match format {
+        Format::Text => reports
+            .iter()
+            .map(|report| format!("{}\n", report))
+            .collect(),
+        #[cfg(feature = "json")]
+        Format::Json => format!("{}\n", serde_json::to_string_pretty(reports).unwrap()),
+        #[cfg(feature = "json")]
+        Format::JsonLines => reports
+            .iter()
+            .map(|report| format!("{}\n", serde_json::to_string(report).unwrap()))
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "json")]
+    use serde_json::{json, Value};
+
+    use crate::input::read_input;
+    use crate::report::*;
+
+    #[cfg(feature = "json")]
+    fn without_timings(mut value: Value) -> Value {
+        value["timings"] = json!({});
+        value
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_report_numeric_answers() {
+        let reports = report(7, &Part::BOTH, "example", "16,1,2,0,4,2,7,1,2,14");
+
+        let lines = encode(&reports, Format::JsonLines)
+            .lines()
+            .map(|line| without_timings(serde_json::from_str(line).unwrap()))
+            .collect::<Vec<Value>>();
+
+        assert_eq!(
+            vec![
+                json!({"day": 7, "part": 1, "input": "example", "answer": 37, "timings": {}}),
+                json!({"day": 7, "part": 2, "input": "example", "answer": 168, "timings": {}}),
+            ],
+            lines
+        );
+        assert!(reports[0].timings.parse_ns.is_some());
+        assert!(reports[0].timings.solve_ns.is_some());
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_report_text_answer() {
+        let input = read_input("src/day13/test_input.txt").unwrap();
+
+        let reports = report(13, &[Part::Two], "test_input", &input);
+
+        let json: Value = serde_json::from_str(&encode(&reports, Format::Json)).unwrap();
+        assert_eq!(
+            json!("*****\n*   *\n*   *\n*   *\n*****"),
+            json[0]["answer"]
+        );
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_report_parse_error() {
+        let reports = report(7, &[Part::One], "broken", "16,1,x");
+
+        let json: Value = serde_json::from_str(&encode(&reports, Format::Json)).unwrap();
+        assert_eq!(
+            json!({"kind": "parse", "line": 1, "column": 6, "text": "x", "reason": "expected a position"}),
+            json[0]["error"]
+        );
+        assert_eq!(Value::Null, json[0]["answer"]);
+        assert_eq!(Value::Null, json[0]["timings"]["solve_ns"]);
+        assert!(!reports[0].is_ok());
+    }
+
+    #[test]
+    fn test_report_unknown_day() {
+        let reports = report(25, &Part::BOTH, "input", "");
+
+        assert_eq!(2, reports.len());
+        assert_eq!(Some(Diagnostic::UnknownDay), reports[1].error);
+        assert_eq!("day 25 part 2: not solved yet", reports[1].to_string());
+    }
+
+    #[test]
+    fn test_report_panicking_part() {
+        let undecodable = "a b c d e f g ab abc abcd | a b c d";
+
+        let reports = report(8, &Part::BOTH, "undecodable", undecodable);
+
+        assert_eq!(Some(Answer::Number(0)), reports[0].answer);
+        assert!(matches!(reports[1].error, Some(Diagnostic::Panic { .. })));
+        assert!(reports[1].timings.solve_ns.is_some());
+    }
+
+    #[test]
+    fn test_report_parallel_in_day_order() {
+        let inputs = vec![
+            (
+                9,
+                "test_input".to_string(),
+                read_input("src/day9/test_input.txt").unwrap(),
+            ),
+            (
+                7,
+                "example".to_string(),
+                "16,1,2,0,4,2,7,1,2,14".to_string(),
+            ),
+            (7, "broken".to_string(), "16,x".to_string()),
+        ];
+
+        let reports = report_parallel(&inputs, &Part::BOTH, 4);
+
+        let summary = reports
+            .iter()
+            .map(|report| (report.day, report.part, report.answer.clone()))
+            .collect::<Vec<(u8, u8, Option<Answer>)>>();
+        assert_eq!(
+            vec![
+                (9, 1, Some(Answer::Number(15))),
+                (9, 2, Some(Answer::Number(1134))),
+                (7, 1, Some(Answer::Number(37))),
+                (7, 2, Some(Answer::Number(168))),
+                (7, 1, None),
+                (7, 2, None),
+            ],
+            summary
+        );
+        assert!(!reports[5].is_ok());
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_panic_diagnostic() {
+        let diagnostic = Diagnostic::Panic {
+            message: "index out of bounds".to_string(),
+        };
+
+        assert_eq!(
+            json!({"kind": "panic", "message": "index out of bounds"}),
+            serde_json::to_value(&diagnostic).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_limit_diagnostics() {
+        let timed_out = Report::diagnosed(
+            15,
+            Part::Two,
+            "input",
+            Diagnostic::TimedOut { limit_ms: 2000 },
+        );
+        let out_of_memory = Report::diagnosed(
+            15,
+            Part::Two,
+            "input",
+            Diagnostic::OutOfMemory {
+                limit_bytes: 512 << 20,
+            },
+        );
+
+        assert_eq!(
+            "day 15 part 2: timed out after 2000 ms",
+            timed_out.to_string()
+        );
+        assert_eq!(
+            "day 15 part 2: out of memory (512 MB limit)",
+            out_of_memory.to_string()
+        );
+        #[cfg(feature = "json")]
+        assert_eq!(
+            json!({"kind": "out_of_memory", "limit_bytes": 512 << 20}),
+            serde_json::to_value(&out_of_memory.error).unwrap()
+        );
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_report_round_trips_through_json() {
+        let reports = report(7, &Part::BOTH, "example", "16,1,2,0,4,2,7,1,2,14");
+
+        let decoded: Vec<Report> = serde_json::from_str(&encode(&reports, Format::Json)).unwrap();
+
+        assert_eq!(reports, decoded);
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_failed_input() {
+        let error = read_input("src/day7/missing.txt").unwrap_err();
+
+        let reports = Report::failed(7, &[Part::One], "missing", &error);
+
+        let json: Value = serde_json::from_str(&encode(&reports, Format::Json)).unwrap();
+        assert_eq!(json!("io"), json[0]["error"]["kind"]);
+        assert_eq!(json!("src/day7/missing.txt"), json[0]["error"]["path"]);
+    }
+}
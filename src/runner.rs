@@ -0,0 +1,173 @@
+use std::any::Any;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::mpsc::channel;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::input::InputError;
+use crate::registry;
+use crate::solution::{Answer, Part};
+
+#[derive(Debug)]
+pub enum RunError {
+    UnknownDay(u8),
+    Input(InputError),
+}
+
+impl Display for RunError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RunError::UnknownDay(day) => write!(f, "day {} is not solved yet", day),
+            RunError::Input(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl Error for RunError {}
+
+impl From<InputError> for RunError {
+    fn from(error: InputError) -> Self {
+        RunError::Input(error)
+    }
+}
+
+pub fn solve(day: u8, part: Part, input: &str) -> Result<Answer, RunError> {
+    let solver = registry::solver(day).ok_or(RunError::UnknownDay(day))?;
+
+    solver
+        .solve(input, part)
+        .map_err(|error| RunError::Input(error.into()))
+}
+
+pub fn solve_timed(day: u8, part: Part, input: &str) -> Result<(Answer, Duration), RunError> {
+    let start = Instant::now();
+    let answer = solve(day, part, input)?;
+    Ok((answer, start.elapsed()))
+}
+
+/// Runs the tasks on `threads` worker threads and returns their results in task order,
+/// whichever finishes first. A panicking task yields its panic message and leaves the others alone.
+pub fn run_parallel<T, F>(tasks: Vec<F>, threads: usize) -> Vec<Result<T, String>>
+where
+    T: Send,
+    F: FnOnce() -> T + Send,
+{
+    let count = tasks.len();
+    let queue = Mutex::new(tasks.into_iter().enumerate());
+    let (sender, receiver) = channel();
+
+    thread::scope(|scope| {
+        for _ in 0..threads.clamp(1, count.max(1)) {
+            let sender = sender.clone();
+            let queue = &queue;
+            scope.spawn(move || loop {
+                let next = queue.lock().unwrap().next();
+                let Some((index, task)) = next else {
+                    break;
+                };
+                let result = catch_unwind(AssertUnwindSafe(task)).map_err(panic_message);
+                sender.send((index, result)).unwrap();
+            });
+        }
+    });
+    drop(sender);
+
+    let mut results = receiver
+        .into_iter()
+        .collect::<Vec<(usize, Result<T, String>)>>();
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+pub(crate) fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "panicked".to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use crate::input::{default_input_path, read_input};
+    use crate::runner::*;
+
+    #[rstest]
+    #[case(1, Part::One, Answer::Number(1184))]
+    #[case(6, Part::Two, Answer::Number(1595779846729))]
+    #[case(10, Part::One, Answer::Number(389589))]
+    fn test_solve(#[case] day: u8, #[case] part: Part, #[case] expected: Answer) {
+        let input = read_input(default_input_path(day)).unwrap();
+
+        let result = solve(day, part, &input);
+
+        assert_eq!(expected, result.unwrap());
+    }
+
+    #[rstest]
+    #[case(0)]
+    #[case(25)]
+    fn test_solve_unknown(#[case] day: u8) {
+        let result = solve(day, Part::One, "199\n200");
+
+        assert!(matches!(result, Err(RunError::UnknownDay(unknown)) if unknown == day));
+    }
+
+    #[test]
+    fn test_solve_reports_parse_errors() {
+        let result = solve(1, Part::One, "forward 5\ndown 5");
+
+        assert!(matches!(result, Err(RunError::Input(InputError::Parse(_)))));
+    }
+
+    #[test]
+    fn test_run_parallel_keeps_task_order() {
+        let tasks = (0..8_u64)
+            .map(|n| {
+                move || {
+                    thread::sleep(Duration::from_millis(8 - n));
+                    n * n
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let results = run_parallel(tasks, 3);
+
+        assert_eq!(
+            (0..8)
+                .map(|n| Ok(n * n))
+                .collect::<Vec<Result<u64, String>>>(),
+            results
+        );
+    }
+
+    #[test]
+    fn test_run_parallel_captures_panics() {
+        let tasks: Vec<Box<dyn FnOnce() -> u8 + Send>> = vec![
+            Box::new(|| 1),
+            Box::new(|| panic!("day {} exploded", 2)),
+            Box::new(|| 3),
+        ];
+
+        let results = run_parallel(tasks, 2);
+
+        assert_eq!(
+            vec![Ok(1), Err("day 2 exploded".to_string()), Ok(3)],
+            results
+        );
+    }
+
+    #[test]
+    fn test_solve_with_other_input() {
+        let result = solve(7, Part::One, "16,1,2,0,4,2,7,1,2,14");
+
+        assert_eq!(Answer::Number(37), result.unwrap());
+    }
+}
@@ -0,0 +1,360 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::fs::{create_dir, read_to_string, write};
+use std::io;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug)]
+pub enum ScaffoldError {
+    InvalidDay(u8),
+    Exists(u8),
+    Io { path: PathBuf, source: io::Error },
+    Unrecognized { path: PathBuf, reason: String },
+}
+
+impl Display for ScaffoldError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScaffoldError::InvalidDay(_) => write!(f, "the calendar only has days 1 to 25"),
+            ScaffoldError::Exists(day) => {
+                write!(f, "src/day{} exists already, not overwriting it", day)
+            }
+            ScaffoldError::Io { path, source } => write!(f, "{}: {}", path.display(), source),
+            ScaffoldError::Unrecognized { path, reason } => {
+                write!(
+                    f,
+                    "cannot register the day in {}: {}",
+                    path.display(),
+                    reason
+                )
+            }
+        }
+    }
+}
+
+impl Error for ScaffoldError {}
+
+const MODULE_TEMPLATE: &str = r#"use crate::input::{default_input_path, InputError};
+use crate::parse::{input_lines, ParseError};
+use crate::solution::{Answer, Solution};
+
+const DAY: u8 = {day};
+
+pub fn get_answer_1() -> Result<usize, InputError> {
+    Ok(solve_part_1(Day{day}::read_file(default_input_path(DAY))?))
+}
+
+pub fn get_answer_2() -> Result<usize, InputError> {
+    Ok(solve_part_2(Day{day}::read_file(default_input_path(DAY))?))
+}
+
+pub struct Day{day};
+
+impl Solution for Day{day} {
+    const DAY: u8 = DAY;
+
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: Self::Input) -> Answer {
+        solve_part_1(input).into()
+    }
+
+    fn part2(input: Self::Input) -> Answer {
+        solve_part_2(input).into()
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<String>, ParseError> {
+    input_lines(DAY, input)
+        .filter(|line| !line.text.is_empty())
+        .map(|line| Ok(line.text.to_string()))
+        .collect()
+}
+
+fn solve_part_1(_input: Vec<String>) -> usize {
+    0
+}
+
+fn solve_part_2(_input: Vec<String>) -> usize {
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use crate::day{day}::*;
+
+    #[test]
+    fn test_read_file() {
+        let input = Day{day}::read_file("src/day{day}/test_input.txt").unwrap();
+
+        assert_eq!(0, input.len());
+    }
+
+    #[rstest]
+    #[case("src/day{day}/test_input.txt", 0)]
+    fn test_solve_part_1(#[case] path: &str, #[case] expected: usize) {
+        let result = solve_part_1(Day{day}::read_file(path).unwrap());
+
+        assert_eq!(expected, result);
+    }
+
+    #[rstest]
+    #[case("src/day{day}/test_input.txt", 0)]
+    fn test_solve_part_2(#[case] path: &str, #[case] expected: usize) {
+        let result = solve_part_2(Day{day}::read_file(path).unwrap());
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    #[ignore = "needs the personal input in src/day{day}/input.txt"]
+    fn result() {
+        println!("{}", get_answer_1().unwrap());
+        println!("{}", get_answer_2().unwrap());
+    }
+}
+"#;
+
+/// The skeleton of a new day, with placeholders where the puzzle goes. Its tests pass on the empty
+/// inputs until the placeholders are replaced, `result` prints the answers once there is an input.
+pub fn module(day: u8) -> String {
+    MODULE_TEMPLATE.replace("{day}", &day.to_string())
+}
+
+/// Adds `pub mod dayN;` to the module list of `src/lib.rs`, which is sorted by name.
+fn register_module(lib: &str, day: u8) -> Result<String, String> {
+    let declaration = format!("pub mod day{};", day);
+    let mut lines = lib.lines().collect::<Vec<&str>>();
+    if lines.contains(&declaration.as_str()) {
+        return Err(format!("day{} is declared already", day));
+    }
+
+    let name = format!("day{}", day);
+    let modules = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            Some((index, line.strip_prefix("pub mod ")?.strip_suffix(';')?))
+        })
+        .collect::<Vec<(usize, &str)>>();
+    let index = match modules.iter().find(|(_, module)| *module > name.as_str()) {
+        Some((index, _)) => *index,
+        None => match modules.last() {
+            Some((index, _)) => index + 1,
+            None => return Err("found no module declarations".to_string()),
+        },
+    };
+    lines.insert(index, &declaration);
+
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Adds the solver of the day to `SOLVERS` in `src/registry.rs`, keeping it in day order.
+fn register_solver(registry: &str, day: u8) -> Result<String, String> {
+    const ARRAY: &str = "static SOLVERS: [&dyn Solver; ";
+
+    let start = registry
+        .find(ARRAY)
+        .ok_or_else(|| "found no SOLVERS array".to_string())?
+        + ARRAY.len();
+    let length = registry[start..]
+        .split(']')
+        .next()
+        .and_then(|length| length.parse::<usize>().ok())
+        .ok_or_else(|| "found no length of SOLVERS".to_string())?;
+    let registry = format!(
+        "{}{}{}",
+        &registry[..start],
+        length + 1,
+        &registry[start + length.to_string().len()..]
+    );
+
+    let mut lines = registry.lines().collect::<Vec<&str>>();
+    let entries = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            let day = line
+                .trim()
+                .strip_prefix("&crate::day")?
+                .split("::")
+                .next()?
+                .parse::<u8>()
+                .ok()?;
+            Some((index, day))
+        })
+        .collect::<Vec<(usize, u8)>>();
+    if entries.iter().any(|(_, registered)| *registered == day) {
+        return Err(format!("day {} is registered already", day));
+    }
+
+    let index = match entries.iter().find(|(_, registered)| *registered > day) {
+        Some((index, _)) => *index,
+        None => match entries.last() {
+            Some((index, _)) => index + 1,
+            None => return Err("found no solvers".to_string()),
+        },
+    };
+    let entry = format!("    &crate::day{0}::Day{0},", day);
+    lines.insert(index, &entry);
+
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Commented-out answers at the end of `answers.toml`, to be filled in once the day is solved.
+fn add_answer_placeholders(answers: &str, day: u8) -> String {
+    let mut answers = answers.trim_end().to_string();
+    for input in ["input", "test_input"] {
+        answers.push_str(&format!(
+            "\n\n# [{}.day{}]\n# part1 = 0\n# part2 = 0",
+            input, day
+        ));
+    }
+    answers + "\n"
+}
+
+/// Creates `src/dayN` under the crate `root` with the module skeleton and empty input files, and
+/// registers the day in `src/lib.rs`, `src/registry.rs` and `answers.toml`. Nothing is written
+/// if the day exists already. Returns the created and changed files.
+pub fn scaffold(root: &Path, day: u8) -> Result<Vec<PathBuf>, ScaffoldError> {
+    if !(1..=25).contains(&day) {
+        return Err(ScaffoldError::InvalidDay(day));
+    }
+    let dir = root.join(format!("src/day{}", day));
+    if dir.exists() {
+        return Err(ScaffoldError::Exists(day));
+    }
+
+    let lib_path = root.join("src/lib.rs");
+    let registry_path = root.join("src/registry.rs");
+    let answers_path = root.join("answers.toml");
+    let unrecognized = |path: &Path| {
+        let path = path.to_path_buf();
+        move |reason| ScaffoldError::Unrecognized { path, reason }
+    };
+    let lib = register_module(&read(&lib_path)?, day).map_err(unrecognized(&lib_path))?;
+    let registry =
+        register_solver(&read(&registry_path)?, day).map_err(unrecognized(&registry_path))?;
+    let answers = add_answer_placeholders(&read(&answers_path)?, day);
+
+    create_dir(&dir).map_err(|source| ScaffoldError::Io {
+        path: dir.clone(),
+        source,
+    })?;
+    let files = vec![
+        (dir.join("mod.rs"), module(day)),
+        (dir.join("input.txt"), String::new()),
+        (dir.join("test_input.txt"), String::new()),
+        (lib_path, lib),
+        (registry_path, registry),
+        (answers_path, answers),
+    ];
+    for (path, content) in &files {
+        write(path, content).map_err(|source| ScaffoldError::Io {
+            path: path.clone(),
+            source,
+        })?;
+    }
+
+    Ok(files.into_iter().map(|(path, _)| path).collect())
+}
+
+fn read(path: &Path) -> Result<String, ScaffoldError> {
+    read_to_string(path).map_err(|source| ScaffoldError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env::temp_dir;
+    use std::fs::{create_dir_all, remove_dir_all};
+
+    use crate::scaffold::*;
+
+    const LIB: &str =
+        "pub mod answers;\npub mod day1;\npub mod day13;\npub mod day2;\npub mod geometry;\n";
+
+    const REGISTRY: &str = "use crate::solution::Solver;
+
+static SOLVERS: [&dyn Solver; 2] = [
+    &crate::day1::Day1,
+    &crate::day13::Day13,
+];
+";
+
+    #[test]
+    fn test_module() {
+        let module = module(14);
+
+        assert!(module.contains("const DAY: u8 = 14;"));
+        assert!(module.contains("pub struct Day14;"));
+        assert!(module.contains("Day14::read_file(\"src/day14/test_input.txt\")"));
+        assert!(module.contains("fn result()"));
+        assert!(!module.contains("todo!"));
+        assert!(!module.contains("{day}"));
+    }
+
+    #[test]
+    fn test_register_module_in_name_order() {
+        let lib = register_module(LIB, 14).unwrap();
+
+        assert_eq!(
+            "pub mod answers;\npub mod day1;\npub mod day13;\npub mod day14;\npub mod day2;\npub mod geometry;\n",
+            lib
+        );
+        assert!(register_module(&lib, 14).is_err());
+    }
+
+    #[test]
+    fn test_register_solver_in_day_order() {
+        let registry = register_solver(REGISTRY, 7).unwrap();
+
+        assert!(registry.contains(
+            "[&dyn Solver; 3] = [\n    &crate::day1::Day1,\n    &crate::day7::Day7,\n    &crate::day13::Day13,\n];"
+        ));
+        assert!(register_solver(&registry, 13).is_err());
+    }
+
+    #[test]
+    fn test_answer_placeholders_are_comments() {
+        let answers = add_answer_placeholders("[input.day1]\npart1 = 3\n", 14);
+
+        assert_eq!(
+            1,
+            crate::answers::parse(&answers).unwrap().len(),
+            "{}",
+            answers
+        );
+        assert!(answers.ends_with("# [test_input.day14]\n# part1 = 0\n# part2 = 0\n"));
+    }
+
+    #[test]
+    fn test_scaffold_refuses_to_overwrite() {
+        let root = temp_dir().join(format!("aoc2021-scaffold-{}", std::process::id()));
+        create_dir_all(root.join("src")).unwrap();
+        write(root.join("src/lib.rs"), LIB).unwrap();
+        write(root.join("src/registry.rs"), REGISTRY).unwrap();
+        write(root.join("answers.toml"), "").unwrap();
+
+        let files = scaffold(&root, 14).unwrap();
+        let second = scaffold(&root, 14);
+        let existing = scaffold(&root, 13);
+
+        assert_eq!(6, files.len());
+        assert!(read(&root.join("src/day14/mod.rs"))
+            .unwrap()
+            .contains("pub struct Day14;"));
+        assert!(matches!(second, Err(ScaffoldError::Exists(14))));
+        assert!(matches!(existing, Err(ScaffoldError::Unrecognized { .. })));
+        assert!(!root.join("src/day13").exists());
+        remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,210 @@
+use std::any::Any;
+use std::fmt::{Display, Formatter};
+use std::io::BufRead;
+use std::path::Path;
+use std::str::FromStr;
+
+#[cfg(feature = "json")]
+use serde::{Deserialize, Serialize};
+
+use crate::input::{read_from, read_input, read_stdin, InputError};
+use crate::parse::ParseError;
+
+/// Serializes as a plain JSON number or string.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize), serde(untagged))]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Number(number) => write!(f, "{}", number),
+            Answer::Text(text) => write!(f, "{}", text),
+        }
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(number: usize) -> Self {
+        Answer::Number(number as i64)
+    }
+}
+
+impl From<isize> for Answer {
+    fn from(number: isize) -> Self {
+        Answer::Number(number as i64)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+impl FromStr for Answer {
+    type Err = std::convert::Infallible;
+
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        Ok(match str.parse::<i64>() {
+            Ok(number) => Answer::Number(number),
+            Err(_) => Answer::Text(str.to_string()),
+        })
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn from_number(number: u8) -> Option<Part> {
+        match number {
+            1 => Some(Part::One),
+            2 => Some(Part::Two),
+            _ => None,
+        }
+    }
+
+    pub fn number(&self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+/// One day of the calendar: parses the puzzle input into a model and solves both parts on it.
+pub trait Solution {
+    const DAY: u8;
+
+    type Input: Clone + Send + 'static;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+
+    fn part1(input: Self::Input) -> Answer;
+
+    fn part2(input: Self::Input) -> Answer;
+
+    fn read_file(path: impl AsRef<Path>) -> Result<Self::Input, InputError> {
+        Ok(Self::parse(&read_input(path)?)?)
+    }
+
+    fn read_from(reader: impl BufRead) -> Result<Self::Input, InputError> {
+        Ok(Self::parse(&read_from(reader)?)?)
+    }
+
+    fn read_stdin() -> Result<Self::Input, InputError> {
+        Ok(Self::parse(&read_stdin()?)?)
+    }
+}
+
+pub type Parsed = Box<dyn Any + Send>;
+
+/// Object safe view on a [`Solution`], so that days can be stored side by side in the registry.
+pub trait Solver: Sync {
+    fn day(&self) -> u8;
+
+    fn parse(&self, input: &str) -> Result<Parsed, ParseError>;
+
+    fn run(&self, parsed: &Parsed, part: Part) -> Answer;
+
+    fn solve(&self, input: &str, part: Part) -> Result<Answer, ParseError> {
+        Ok(self.run(&self.parse(input)?, part))
+    }
+}
+
+impl<S: Solution + Sync> Solver for S {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn parse(&self, input: &str) -> Result<Parsed, ParseError> {
+        Ok(Box::new(S::parse(input)?))
+    }
+
+    fn run(&self, parsed: &Parsed, part: Part) -> Answer {
+        let input = parsed
+            .downcast_ref::<S::Input>()
+            .unwrap_or_else(|| panic!("input was not parsed by day {}", S::DAY))
+            .clone();
+
+        match part {
+            Part::One => S::part1(input),
+            Part::Two => S::part2(input),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use crate::solution::*;
+
+    #[rstest]
+    #[case(Answer::from(42_usize), "42")]
+    #[case(Answer::from(- 42_isize), "-42")]
+    #[case(Answer::from("* *\n **".to_string()), "* *\n **")]
+    fn test_display_answer(#[case] answer: Answer, #[case] expected: &str) {
+        assert_eq!(expected, answer.to_string());
+    }
+
+    #[rstest]
+    #[case("-12", Answer::Number(-12))]
+    #[case("EFJKZLBL", Answer::Text("EFJKZLBL".to_string()))]
+    fn test_parse_answer(#[case] str: &str, #[case] expected: Answer) {
+        assert_eq!(Ok(expected), str.parse::<Answer>());
+    }
+
+    #[rstest]
+    #[case(1, Some(Part::One))]
+    #[case(2, Some(Part::Two))]
+    #[case(0, None)]
+    #[case(3, None)]
+    fn test_part_from_number(#[case] number: u8, #[case] expected: Option<Part>) {
+        assert_eq!(expected, Part::from_number(number));
+    }
+
+    #[test]
+    fn test_solver_runs_both_parts_on_one_parse() {
+        let solver: &dyn Solver = &crate::day7::Day7;
+
+        let parsed = solver.parse("16,1,2,0,4,2,7,1,2,14").unwrap();
+
+        assert_eq!(Answer::Number(37), solver.run(&parsed, Part::One));
+        assert_eq!(Answer::Number(168), solver.run(&parsed, Part::Two));
+    }
+
+    #[test]
+    fn test_read_from_any_source() {
+        use crate::day7::Day7;
+
+        let from_file = Day7::read_file("src/day7/test_input.txt").unwrap();
+        let from_reader = Day7::read_from("16,1,2,0,4,2,7,1,2,14\n".as_bytes()).unwrap();
+
+        assert_eq!(from_file, from_reader);
+    }
+
+    #[test]
+    fn test_solver_reports_parse_error() {
+        let solver: &dyn Solver = &crate::day7::Day7;
+
+        let error = solver.solve("16,1,x", Part::One).unwrap_err();
+
+        assert_eq!((7, 1, 6), (error.day, error.line, error.column));
+    }
+}
@@ -0,0 +1,353 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::io::{self, Read, Write};
+#[cfg(feature = "json")]
+use std::path::Path;
+use std::process::{Command, ExitStatus, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::report::Diagnostic;
+#[cfg(feature = "json")]
+use crate::report::Report;
+#[cfg(feature = "json")]
+use crate::runner::run_parallel;
+#[cfg(feature = "json")]
+use crate::solution::Part;
+
+/// How long and how much memory a solver may take. `None` means no limit.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub struct Limits {
+    pub timeout: Option<Duration>,
+    pub memory_bytes: Option<u64>,
+}
+
+impl Limits {
+    pub fn is_unlimited(&self) -> bool {
+        self.timeout.is_none() && self.memory_bytes.is_none()
+    }
+}
+
+#[derive(Debug)]
+pub enum Failure {
+    TimedOut(Duration),
+    OutOfMemory(u64),
+    Crashed(String),
+    Io(io::Error),
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::TimedOut(limit) => write!(f, "timed out after {:?}", limit),
+            Failure::OutOfMemory(limit) => {
+                write!(f, "out of memory with a limit of {} bytes", limit)
+            }
+            Failure::Crashed(message) => write!(f, "crashed: {}", message),
+            Failure::Io(error) => write!(f, "could not run the solver: {}", error),
+        }
+    }
+}
+
+impl Error for Failure {}
+
+impl From<io::Error> for Failure {
+    fn from(error: io::Error) -> Self {
+        Failure::Io(error)
+    }
+}
+
+impl From<Failure> for Diagnostic {
+    fn from(failure: Failure) -> Self {
+        match failure {
+            Failure::TimedOut(limit) => Diagnostic::TimedOut {
+                limit_ms: limit.as_millis() as u64,
+            },
+            Failure::OutOfMemory(limit_bytes) => Diagnostic::OutOfMemory { limit_bytes },
+            Failure::Crashed(message) => Diagnostic::Panic { message },
+            Failure::Io(error) => Diagnostic::Panic {
+                message: error.to_string(),
+            },
+        }
+    }
+}
+
+/// What the standard allocator prints before aborting when it gets no more memory.
+const ALLOCATION_FAILED: &str = "memory allocation of";
+
+/// Runs `command` with `stdin` as its input and returns what it wrote to stdout. The child is
+/// killed once it exceeds the timeout, and its address space is capped at the memory limit.
+pub fn run_limited(mut command: Command, stdin: &str, limits: &Limits) -> Result<String, Failure> {
+    if let Some(bytes) = limits.memory_bytes {
+        limit_memory(&mut command, bytes)?;
+    }
+
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    let mut child_stdin = child.stdin.take().unwrap();
+    let input = stdin.to_string();
+    let writer = thread::spawn(move || child_stdin.write_all(input.as_bytes()));
+    let mut child_stdout = child.stdout.take().unwrap();
+    let reader = thread::spawn(move || {
+        let mut output = String::new();
+        child_stdout.read_to_string(&mut output).map(|_| output)
+    });
+    let mut child_stderr = child.stderr.take().unwrap();
+    let error_reader = thread::spawn(move || {
+        let mut output = String::new();
+        child_stderr.read_to_string(&mut output).map(|_| output)
+    });
+
+    let start = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if let Some(timeout) = limits.timeout.filter(|timeout| start.elapsed() > *timeout) {
+            child.kill()?;
+            child.wait()?;
+            return Err(Failure::TimedOut(timeout));
+        }
+        thread::sleep(Duration::from_millis(5));
+    };
+
+    // The child may exit without reading all of its input, which is not our problem
+    let _ = writer.join().unwrap();
+    let output = reader.join().unwrap()?;
+    let errors = error_reader.join().unwrap()?;
+
+    match (status.success(), limits.memory_bytes) {
+        (true, _) => Ok(output),
+        (false, Some(bytes)) if ran_out_of_memory(&status, &errors) => {
+            Err(Failure::OutOfMemory(bytes))
+        }
+        (false, _) => Err(Failure::Crashed(crash_message(
+            &errors,
+            &status.to_string(),
+        ))),
+    }
+}
+
+/// Only asked when the child had a memory limit. A failed allocation aborts with a message. A
+/// binary or stack that does not even fit into the limit ends in a segmentation fault or a kill
+/// before the child can print anything, while other crashes, like a stack overflow, say why.
+fn ran_out_of_memory(status: &ExitStatus, errors: &str) -> bool {
+    if errors.contains(ALLOCATION_FAILED) {
+        return true;
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+
+        matches!(status.signal(), Some(libc::SIGSEGV | libc::SIGKILL)) && errors.trim().is_empty()
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = status;
+        false
+    }
+}
+
+/// The panic message without the lines around it, or the exit status if there is none.
+fn crash_message(errors: &str, status: &str) -> String {
+    errors
+        .lines()
+        .find(|line| !line.starts_with("thread ") && !line.starts_with("note:"))
+        .unwrap_or(status)
+        .to_string()
+}
+
+#[cfg(unix)]
+fn limit_memory(command: &mut Command, bytes: u64) -> Result<(), Failure> {
+    use std::os::unix::process::CommandExt;
+
+    let limit = libc::rlimit {
+        rlim_cur: bytes as libc::rlim_t,
+        rlim_max: bytes as libc::rlim_t,
+    };
+    // Only async-signal-safe calls are allowed between fork and exec, setrlimit is one
+    unsafe {
+        command.pre_exec(move || match libc::setrlimit(libc::RLIMIT_AS, &limit) {
+            0 => Ok(()),
+            _ => Err(io::Error::last_os_error()),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn limit_memory(_: &mut Command, _: u64) -> Result<(), Failure> {
+    Err(Failure::Io(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "memory limits need a unix system",
+    )))
+}
+
+/// Solves one part in a child process of `program`, which has to understand the hidden
+/// `solve-task --day <day> --part <part>` subcommand and print a JSON report for the input on stdin.
+#[cfg(feature = "json")]
+pub fn supervise(
+    program: &Path,
+    day: u8,
+    part: Part,
+    input_id: &str,
+    input: &str,
+    limits: &Limits,
+) -> Report {
+    let mut command = Command::new(program);
+    command.args([
+        "solve-task",
+        "--day",
+        &day.to_string(),
+        "--part",
+        &part.to_string(),
+    ]);
+
+    let report = run_limited(command, input, limits).and_then(|output| {
+        serde_json::from_str::<Report>(&output)
+            .map_err(|error| Failure::Crashed(format!("unreadable report: {}", error)))
+    });
+
+    match report {
+        Ok(report) => Report {
+            input: input_id.to_string(),
+            ..report
+        },
+        Err(failure) => Report::diagnosed(day, part, input_id, failure.into()),
+    }
+}
+
+/// Like [`crate::report::report_parallel`], but every day and part runs in its own supervised
+/// child process of `program`.
+#[cfg(feature = "json")]
+pub fn supervise_parallel(
+    program: &Path,
+    inputs: &[(u8, String, String)],
+    parts: &[Part],
+    threads: usize,
+    limits: &Limits,
+) -> Vec<Report> {
+    let tasks = inputs
+        .iter()
+        .flat_map(|(day, input_id, input)| {
+            parts
+                .iter()
+                .map(move |part| move || supervise(program, *day, *part, input_id, input, limits))
+        })
+        .collect::<Vec<_>>();
+    let keys = inputs
+        .iter()
+        .flat_map(|(day, input_id, _)| parts.iter().map(move |part| (*day, *part, input_id)));
+
+    run_parallel(tasks, threads)
+        .into_iter()
+        .zip(keys)
+        .map(|(result, (day, part, input_id))| {
+            result.unwrap_or_else(|message| {
+                Report::diagnosed(day, part, input_id, Diagnostic::Panic { message })
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env::{current_exe, var_os};
+    use std::hint::black_box;
+
+    use rstest::*;
+
+    use crate::supervise::*;
+
+    fn shell(script: &str) -> Command {
+        let mut command = Command::new("sh");
+        command.args(["-c", script]);
+        command
+    }
+
+    #[test]
+    fn test_passes_input_and_output() {
+        let output = run_limited(shell("tr a-z A-Z"), "hello", &Limits::default());
+
+        assert_eq!("HELLO", output.unwrap());
+    }
+
+    #[test]
+    fn test_times_out() {
+        let limits = Limits {
+            timeout: Some(Duration::from_millis(100)),
+            memory_bytes: None,
+        };
+        let start = Instant::now();
+
+        let result = run_limited(shell("sleep 10"), "", &limits);
+
+        assert!(matches!(result, Err(Failure::TimedOut(_))));
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_reports_crashes() {
+        let result = run_limited(shell("echo boom >&2; exit 3"), "", &Limits::default());
+
+        assert!(matches!(result, Err(Failure::Crashed(message)) if message == "boom"));
+    }
+
+    #[cfg(unix)]
+    #[rstest]
+    #[case(libc::SIGSEGV, "", true)]
+    #[case(libc::SIGKILL, "", true)]
+    #[case(libc::SIGABRT, "memory allocation of 1073741824 bytes failed\n", true)]
+    #[case(
+        libc::SIGSEGV,
+        "thread 'main' has overflowed its stack\nfatal runtime error: stack overflow\n",
+        false
+    )]
+    #[case(libc::SIGSEGV, "boom\n", false)]
+    #[case(libc::SIGABRT, "", false)]
+    fn test_ran_out_of_memory(#[case] signal: i32, #[case] errors: &str, #[case] expected: bool) {
+        use std::os::unix::process::ExitStatusExt;
+
+        assert_eq!(
+            expected,
+            ran_out_of_memory(&ExitStatus::from_raw(signal), errors)
+        );
+    }
+
+    /// Does nothing in a normal test run. [`test_out_of_memory`] starts the test binary again
+    /// with `AOC2021_ALLOCATE` set to run only this test, which then allocates a gigabyte.
+    #[test]
+    fn allocate_when_asked() {
+        if var_os("AOC2021_ALLOCATE").is_some() {
+            // Release builds would drop an allocation that is never looked at
+            let memory = black_box(vec![1_u8; 1 << 30]);
+            println!("{}", memory.len());
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_out_of_memory() {
+        let mut command = Command::new(current_exe().unwrap());
+        command
+            .args(["--exact", "supervise::tests::allocate_when_asked"])
+            .env("AOC2021_ALLOCATE", "1");
+        let limits = Limits {
+            timeout: Some(Duration::from_secs(30)),
+            memory_bytes: Some(256 << 20),
+        };
+
+        let result = run_limited(command, "", &limits);
+
+        assert!(
+            matches!(result, Err(Failure::OutOfMemory(bytes)) if bytes == 256 << 20),
+            "{:?}",
+            result
+        );
+    }
+}